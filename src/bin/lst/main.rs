@@ -0,0 +1,878 @@
+//! Operator CLI for liquid staking pools. Built with `--features cli`.
+
+mod rpc;
+
+use std::{path::PathBuf, thread, time::Duration};
+
+use clap::{Parser, Subcommand};
+use liquid_staking_token::{
+    PAUSE_CRANKS, PAUSE_DEPOSITS, PAUSE_WITHDRAWALS, PoolState, STAKE_ACCOUNT_SIZE,
+    calculate_exchange_rate,
+    client::{
+        self, ATA_PROGRAM_ID, DepositLimits, KeeperView, Pool, PoolFees, SYSTEM_PROGRAM_ID,
+        StakeDelegation, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID, TokenMetadataArgs,
+        TransientStake,
+    },
+};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer, read_keypair_file},
+    transaction::Transaction,
+};
+
+use rpc::{RpcAccount, RpcClient};
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+const DEFAULT_RPC_URL: &str = "http://127.0.0.1:8899";
+
+/// How many past epochs `crank` checks for unmerged transient stakes.
+const TRANSIENT_LOOKBACK_EPOCHS: u64 = 32;
+
+#[derive(Parser)]
+#[command(name = "lst", about = "Operate liquid staking pools")]
+struct Cli {
+    /// JSON-RPC endpoint, defaults to a local test validator
+    #[arg(long, env = "LST_RPC_URL", default_value = DEFAULT_RPC_URL, global = true)]
+    url: String,
+
+    /// Fee payer and signer, defaults to ~/.config/solana/id.json
+    #[arg(long, env = "LST_KEYPAIR", global = true)]
+    keypair: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a pool delegated to `validator_vote`; the signer becomes manager and staker
+    CreatePool {
+        #[arg(long)]
+        validator_vote: Pubkey,
+        #[arg(long)]
+        seed: u64,
+        #[arg(long, default_value_t = 0)]
+        deposit_fee_bps: u16,
+        #[arg(long, default_value_t = 0)]
+        withdrawal_fee_bps: u16,
+        #[arg(long, default_value_t = 0)]
+        reward_fee_bps: u16,
+        #[arg(long, default_value_t = 0)]
+        sol_withdrawal_fee_bps: u16,
+        /// LST name shown by wallets; creates the mint's metadata account
+        #[arg(long, requires = "symbol")]
+        name: Option<String>,
+        #[arg(long, requires = "name")]
+        symbol: Option<String>,
+        #[arg(long, requires = "name", default_value = "")]
+        uri: String,
+        /// Create the LST mint under Token-2022 instead of SPL Token
+        #[arg(long)]
+        token_2022: bool,
+    },
+    /// Print a pool's state
+    Show {
+        #[arg(long)]
+        pool: Pubkey,
+    },
+    /// Deposit lamports for LST, creating the LST token account if needed
+    Deposit {
+        #[arg(long)]
+        pool: Pubkey,
+        /// Lamports to deposit
+        amount: u64,
+        #[arg(long, default_value_t = 0)]
+        minimum_lst_out: u64,
+        /// Keypair of the deposit authority, for permissioned pools
+        #[arg(long)]
+        deposit_authority: Option<PathBuf>,
+    },
+    /// Burn LST into a deactivating withdraw stake account
+    Withdraw {
+        #[arg(long)]
+        pool: Pubkey,
+        /// LST base units to burn
+        amount: u64,
+        /// Withdraw account nonce, defaults to the lowest unused one
+        #[arg(long, conflicts_with = "ledger")]
+        nonce: Option<u64>,
+        #[arg(long, default_value_t = 0)]
+        minimum_lamports_out: u64,
+        /// Issue a transferable withdraw ticket for the claim
+        #[arg(long)]
+        ticket: bool,
+        /// Take the nonce from the signer's withdraw ledger and record the
+        /// withdrawal there, creating the ledger if needed
+        #[arg(long)]
+        ledger: bool,
+    },
+    /// Claim the lamports of a deactivated withdraw stake account
+    WithdrawComplete {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long, required_unless_present = "ticket", conflicts_with = "ticket")]
+        nonce: Option<u64>,
+        /// Claim with a withdraw ticket held by the signer instead of a nonce
+        #[arg(long)]
+        ticket: Option<Pubkey>,
+        /// Also remove the claim from the signer's withdraw ledger
+        #[arg(long)]
+        ledger: bool,
+    },
+    /// Hand a withdraw ticket to a new owner
+    TransferTicket {
+        #[arg(long)]
+        ticket: Pubkey,
+        new_owner: Pubkey,
+    },
+    /// Close the signer's withdraw ledger, first pruning entries claimed without it
+    CloseLedger {
+        #[arg(long)]
+        pool: Pubkey,
+    },
+    /// List a user's pending withdraw stake accounts by nonce, starting with
+    /// those in their withdraw ledger
+    Withdrawals {
+        #[arg(long)]
+        pool: Pubkey,
+        /// Defaults to the signer
+        #[arg(long)]
+        user: Option<Pubkey>,
+        /// Nonces 0..max_nonce are scanned
+        #[arg(long, default_value_t = 256)]
+        max_nonce: u64,
+    },
+    /// Propose a new manager, who takes over with `accept-manager`
+    SetManager {
+        #[arg(long)]
+        pool: Pubkey,
+        /// Pass the current manager to cancel a pending transfer
+        new_manager: Pubkey,
+    },
+    /// Take over as manager after being proposed with `set-manager`
+    AcceptManager {
+        #[arg(long)]
+        pool: Pubkey,
+    },
+    /// Replace the staker; signed by the manager or the current staker
+    SetStaker {
+        #[arg(long)]
+        pool: Pubkey,
+        new_staker: Pubkey,
+    },
+    /// Require the given authority to co-sign deposits; no authority makes the pool public
+    SetDepositAuthority {
+        #[arg(long)]
+        pool: Pubkey,
+        deposit_authority: Option<Pubkey>,
+    },
+    /// Pause the given operations and resume all others; no flags resumes everything
+    SetStatus {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        pause_deposits: bool,
+        #[arg(long)]
+        pause_withdrawals: bool,
+        #[arg(long)]
+        pause_cranks: bool,
+    },
+    /// Replace the LST name, symbol and URI (manager only)
+    UpdateTokenMetadata {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        name: String,
+        #[arg(long)]
+        symbol: String,
+        #[arg(long, default_value = "")]
+        uri: String,
+    },
+    /// Change deposit limits in lamports (manager only); omitted limits are kept, 0 removes one
+    UpdateConfig {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        max_total_lamports: Option<u64>,
+        #[arg(long)]
+        max_deposit_lamports: Option<u64>,
+        #[arg(long)]
+        min_deposit_lamports: Option<u64>,
+    },
+    /// Change fees in basis points and where they are paid (manager only); omitted values are kept
+    SetFees {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        deposit_fee_bps: Option<u16>,
+        #[arg(long)]
+        withdrawal_fee_bps: Option<u16>,
+        #[arg(long)]
+        reward_fee_bps: Option<u16>,
+        #[arg(long)]
+        sol_withdrawal_fee_bps: Option<u16>,
+        /// LST token account that receives the fees
+        #[arg(long)]
+        manager_fee_account: Option<Pubkey>,
+    },
+    /// Start recording the exchange rate at every epoch update (manager only)
+    InitRateHistory {
+        #[arg(long)]
+        pool: Pubkey,
+    },
+    /// Print the recorded exchange rates, newest first, in lamports per LST
+    RateHistory {
+        #[arg(long)]
+        pool: Pubkey,
+        /// Only the last `epochs` epochs
+        #[arg(long)]
+        epochs: Option<u64>,
+    },
+    /// Upgrade a pool created with an older state layout
+    MigratePool {
+        #[arg(long)]
+        pool: Pubkey,
+    },
+    /// Run the permissionless maintenance instructions that are due
+    Crank {
+        #[arg(long)]
+        pool: Pubkey,
+        /// Keep cranking every `interval` seconds instead of exiting
+        #[arg(long)]
+        interval: Option<u64>,
+        /// Retries per round, each after re-reading pool state
+        #[arg(long, default_value_t = 3)]
+        max_retries: u32,
+    },
+}
+
+fn main() {
+    if let Err(error) = run(Cli::parse()) {
+        eprintln!("error: {error}");
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let rpc = RpcClient::new(cli.url);
+    let keypair_path = match cli.keypair {
+        Some(path) => path,
+        None => PathBuf::from(std::env::var("HOME")?).join(".config/solana/id.json"),
+    };
+    let signer = read_keypair_file(&keypair_path)
+        .map_err(|error| format!("failed to read {}: {error}", keypair_path.display()))?;
+
+    match cli.command {
+        Command::CreatePool {
+            validator_vote,
+            seed,
+            deposit_fee_bps,
+            withdrawal_fee_bps,
+            reward_fee_bps,
+            sol_withdrawal_fee_bps,
+            name,
+            symbol,
+            uri,
+            token_2022,
+        } => {
+            let lst_mint = Keypair::new();
+            let fees = PoolFees {
+                deposit_fee_bps,
+                withdrawal_fee_bps,
+                reward_fee_bps,
+                sol_withdrawal_fee_bps,
+            };
+            let token_program = if token_2022 {
+                TOKEN_2022_PROGRAM_ID
+            } else {
+                TOKEN_PROGRAM_ID
+            };
+            let metadata = match (name, symbol) {
+                (Some(name), Some(symbol)) => Some(TokenMetadataArgs { name, symbol, uri }),
+                _ => None,
+            };
+            let ix = client::initialize_with_token_program(
+                &signer.pubkey(),
+                &lst_mint.pubkey(),
+                &validator_vote,
+                seed,
+                fees,
+                &token_program,
+                metadata.as_ref(),
+            );
+            send(&rpc, &signer, &[&lst_mint], vec![ix])?;
+
+            let (pool, _) = client::find_pool_state_address(seed);
+            println!("pool: {pool}");
+            println!("lst mint: {}", lst_mint.pubkey());
+        }
+        Command::Show { pool } => {
+            let (pool, pool_state) = load_pool_state(&rpc, &pool)?;
+
+            println!("pool: {}", pool.address);
+            println!("version: {}", pool_state.version);
+            println!("manager: {}", pool.manager);
+            println!("staker: {}", pool.staker);
+            println!("lst mint: {}", pool.lst_mint);
+            println!("token program: {}", pool.token_program);
+            println!("validator vote: {}", pool.validator_vote);
+            println!("pool stake: {}", pool.pool_stake);
+            println!("reserve stake: {}", pool.reserve_stake);
+            println!("manager fee account: {}", pool.manager_fee_account);
+            println!("total lamports: {}", { pool_state.total_lamports });
+            println!("lst supply: {}", { pool_state.lst_supply });
+            println!(
+                "exchange rate: {} lamports per LST",
+                calculate_exchange_rate(pool_state.total_lamports, pool_state.lst_supply)
+                    .map_err(|error| format!("invalid pool balance: {error:?}"))?
+            );
+            println!("last update epoch: {}", { pool_state.last_update_epoch });
+            println!("transient lamports: {}", { pool_state.transient_lamports });
+            println!(
+                "deposit limits: pool cap {}, max {}, min {} (0 = none)",
+                pool_state.max_total_lamports(),
+                pool_state.max_deposit_lamports(),
+                pool_state.min_deposit_lamports()
+            );
+            println!(
+                "paused: deposits {}, withdrawals {}, cranks {}",
+                pool_state.deposits_paused(),
+                pool_state.withdrawals_paused(),
+                pool_state.cranks_paused()
+            );
+            if let Some(validator_list) = pool.validator_list {
+                println!("validator list: {validator_list}");
+            }
+            if let Some(pending) = pool.pending_validator_vote {
+                println!("pending validator vote: {pending}");
+            }
+            if let Some(pending) = pool.pending_manager {
+                println!("pending manager: {pending}");
+            }
+            if let Some(deposit_authority) = pool.deposit_authority {
+                println!("deposit authority: {deposit_authority}");
+            }
+            if let Some(rate_history) = pool.rate_history {
+                println!("rate history: {rate_history}");
+            }
+        }
+        Command::Deposit {
+            pool,
+            amount,
+            minimum_lst_out,
+            deposit_authority,
+        } => {
+            let pool = load_pool(&rpc, &pool)?;
+            let deposit_authority = deposit_authority
+                .map(|path| {
+                    read_keypair_file(&path)
+                        .map_err(|error| format!("failed to read {}: {error}", path.display()))
+                })
+                .transpose()?;
+            let ixs = vec![
+                create_associated_token_account_idempotent(&signer.pubkey(), &pool),
+                client::deposit(&pool, &signer.pubkey(), amount, minimum_lst_out),
+            ];
+            let extra_signers: Vec<&Keypair> = deposit_authority.iter().collect();
+            send(&rpc, &signer, &extra_signers, ixs)?;
+        }
+        Command::Withdraw {
+            pool,
+            amount,
+            nonce,
+            minimum_lamports_out,
+            ticket,
+            ledger,
+        } => {
+            let pool = load_pool(&rpc, &pool)?;
+            if ledger {
+                let (ledger_address, _) =
+                    client::find_withdraw_ledger_address(&pool.address, &signer.pubkey());
+                let mut ixs = Vec::new();
+                let ledger_nonce = match rpc.get_account(&ledger_address)? {
+                    Some(account) => {
+                        client::decode_withdraw_ledger(&account.data)
+                            .map_err(|error| {
+                                format!("invalid withdraw ledger {ledger_address}: {error:?}")
+                            })?
+                            .0
+                            .next_nonce
+                    }
+                    None => {
+                        ixs.push(client::initialize_withdraw_ledger(&pool, &signer.pubkey()));
+                        1
+                    }
+                };
+                // Withdrawals made without the ledger may hold later nonces
+                let nonce = next_unused_nonce(&rpc, &pool, &signer.pubkey(), ledger_nonce)?;
+                ixs.push(client::withdraw_with_ledger(
+                    &pool,
+                    &signer.pubkey(),
+                    amount,
+                    nonce,
+                    minimum_lamports_out,
+                    ticket,
+                ));
+                send(&rpc, &signer, &[], ixs)?;
+                println!("withdraw nonce: {nonce}");
+                return Ok(());
+            }
+            let nonce = match nonce {
+                Some(nonce) => nonce,
+                None => next_unused_nonce(&rpc, &pool, &signer.pubkey(), 1)?,
+            };
+            let ix = if ticket {
+                client::withdraw_with_ticket(
+                    &pool,
+                    &signer.pubkey(),
+                    amount,
+                    nonce,
+                    minimum_lamports_out,
+                )
+            } else {
+                client::withdraw(&pool, &signer.pubkey(), amount, nonce, minimum_lamports_out)
+            };
+            send(&rpc, &signer, &[], vec![ix])?;
+            println!("withdraw nonce: {nonce}");
+            if ticket {
+                let (user_stake, _) =
+                    client::find_withdraw_stake_address(&pool.address, &signer.pubkey(), nonce);
+                println!(
+                    "withdraw ticket: {}",
+                    client::find_withdraw_ticket_address(&user_stake).0
+                );
+            }
+        }
+        Command::WithdrawComplete {
+            pool,
+            nonce,
+            ticket,
+            ledger,
+        } => {
+            let pool = load_pool(&rpc, &pool)?;
+            let mut ix = match (nonce, ticket) {
+                (_, Some(ticket)) => {
+                    let ticket = client::decode_withdraw_ticket(&fetch(&rpc, &ticket)?.data)
+                        .map_err(|error| format!("invalid withdraw ticket {ticket}: {error:?}"))?;
+                    client::withdraw_complete_with_ticket(
+                        &pool,
+                        &signer.pubkey(),
+                        &Pubkey::new_from_array(ticket.withdraw_stake),
+                    )
+                }
+                (Some(nonce), None) => client::withdraw_complete(&pool, &signer.pubkey(), nonce),
+                (None, None) => return Err("pass --nonce or --ticket".into()),
+            };
+            if ledger {
+                client::push_withdraw_ledger(&mut ix, &pool, &signer.pubkey());
+            }
+            send(&rpc, &signer, &[], vec![ix])?;
+        }
+        Command::TransferTicket { ticket, new_owner } => {
+            let ix = client::transfer_withdraw_ticket(&ticket, &signer.pubkey(), &new_owner);
+            send(&rpc, &signer, &[], vec![ix])?;
+        }
+        Command::CloseLedger { pool } => {
+            let pool = load_pool(&rpc, &pool)?;
+            let (ledger_address, _) =
+                client::find_withdraw_ledger_address(&pool.address, &signer.pubkey());
+            let account = rpc
+                .get_account(&ledger_address)?
+                .ok_or("no withdraw ledger to close")?;
+            let (_, entries) = client::decode_withdraw_ledger(&account.data)
+                .map_err(|error| format!("invalid withdraw ledger {ledger_address}: {error:?}"))?;
+
+            // Entries of stakes claimed without the ledger, e.g. by ticket
+            let listed: Vec<Pubkey> = entries
+                .iter()
+                .map(|entry| Pubkey::new_from_array(entry.withdraw_stake))
+                .collect();
+            let claimed: Vec<Pubkey> = listed
+                .iter()
+                .zip(rpc.get_multiple_accounts(&listed)?)
+                .filter(|(_, account)| account.is_none())
+                .map(|(address, _)| *address)
+                .collect();
+
+            let mut ixs = Vec::new();
+            if !claimed.is_empty() {
+                ixs.push(client::prune_withdraw_ledger(
+                    &pool,
+                    &signer.pubkey(),
+                    &claimed,
+                ));
+            }
+            ixs.push(client::close_withdraw_ledger(&pool, &signer.pubkey()));
+            send(&rpc, &signer, &[], ixs)?;
+        }
+        Command::Withdrawals {
+            pool,
+            user,
+            max_nonce,
+        } => {
+            let user = user.unwrap_or_else(|| signer.pubkey());
+            let epoch = rpc.get_epoch()?;
+
+            let (ledger_address, _) = client::find_withdraw_ledger_address(&pool, &user);
+            let mut listed = Vec::new();
+            if let Some(account) = rpc.get_account(&ledger_address)? {
+                let (ledger, entries) =
+                    client::decode_withdraw_ledger(&account.data).map_err(|error| {
+                        format!("invalid withdraw ledger {ledger_address}: {error:?}")
+                    })?;
+                println!("ledger {ledger_address}, next nonce {}", ledger.next_nonce);
+                for entry in entries {
+                    let address = Pubkey::new_from_array(entry.withdraw_stake);
+                    let status = if entry.claimable_epoch <= epoch {
+                        "ready".to_string()
+                    } else {
+                        format!("cooling down until epoch {}", entry.claimable_epoch)
+                    };
+                    println!(
+                        "nonce {}: {address} {} lamports, {status}",
+                        entry.nonce, entry.lamports
+                    );
+                    listed.push(address);
+                }
+            }
+
+            let addresses: Vec<Pubkey> = (0..max_nonce)
+                .map(|nonce| client::find_withdraw_stake_address(&pool, &user, nonce).0)
+                .collect();
+            let accounts = rpc.get_multiple_accounts(&addresses)?;
+
+            for (nonce, (address, account)) in addresses.iter().zip(accounts).enumerate() {
+                let Some(account) = account else { continue };
+                if account.owner != client::STAKE_PROGRAM_ID || listed.contains(address) {
+                    continue;
+                }
+
+                let status = match StakeDelegation::parse(&account.data) {
+                    Some(stake) if stake.deactivation_epoch < epoch => "ready".to_string(),
+                    Some(stake) if stake.deactivation_epoch != u64::MAX => {
+                        format!("cooling down until epoch {}", stake.deactivation_epoch + 1)
+                    }
+                    _ => "not deactivating".to_string(),
+                };
+                println!(
+                    "nonce {nonce}: {address} {} lamports, {status}",
+                    account.lamports
+                );
+            }
+        }
+        Command::SetManager { pool, new_manager } => {
+            let pool = load_pool(&rpc, &pool)?;
+            let ix = client::set_manager(&pool, &new_manager);
+            send(&rpc, &signer, &[], vec![ix])?;
+        }
+        Command::AcceptManager { pool } => {
+            let pool = load_pool(&rpc, &pool)?;
+            let ix = client::accept_manager(&pool, &signer.pubkey());
+            send(&rpc, &signer, &[], vec![ix])?;
+        }
+        Command::SetStaker { pool, new_staker } => {
+            let pool = load_pool(&rpc, &pool)?;
+            let ix = client::set_staker(&pool, &signer.pubkey(), &new_staker);
+            send(&rpc, &signer, &[], vec![ix])?;
+        }
+        Command::SetDepositAuthority {
+            pool,
+            deposit_authority,
+        } => {
+            let pool = load_pool(&rpc, &pool)?;
+            let ix = client::set_deposit_authority(&pool, deposit_authority.as_ref());
+            send(&rpc, &signer, &[], vec![ix])?;
+        }
+        Command::SetStatus {
+            pool,
+            pause_deposits,
+            pause_withdrawals,
+            pause_cranks,
+        } => {
+            let pool = load_pool(&rpc, &pool)?;
+            let mut status = 0;
+            if pause_deposits {
+                status |= PAUSE_DEPOSITS;
+            }
+            if pause_withdrawals {
+                status |= PAUSE_WITHDRAWALS;
+            }
+            if pause_cranks {
+                status |= PAUSE_CRANKS;
+            }
+            send(&rpc, &signer, &[], vec![client::set_status(&pool, status)])?;
+        }
+        Command::UpdateTokenMetadata {
+            pool,
+            name,
+            symbol,
+            uri,
+        } => {
+            let pool = load_pool(&rpc, &pool)?;
+            let metadata = TokenMetadataArgs { name, symbol, uri };
+            let ix = client::update_token_metadata(&pool, &metadata);
+            send(&rpc, &signer, &[], vec![ix])?;
+        }
+        Command::UpdateConfig {
+            pool,
+            max_total_lamports,
+            max_deposit_lamports,
+            min_deposit_lamports,
+        } => {
+            let (pool, pool_state) = load_pool_state(&rpc, &pool)?;
+            let limits = DepositLimits {
+                max_total_lamports: max_total_lamports.unwrap_or(pool_state.max_total_lamports()),
+                max_deposit_lamports: max_deposit_lamports
+                    .unwrap_or(pool_state.max_deposit_lamports()),
+                min_deposit_lamports: min_deposit_lamports
+                    .unwrap_or(pool_state.min_deposit_lamports()),
+            };
+            send(
+                &rpc,
+                &signer,
+                &[],
+                vec![client::update_config(&pool, limits)],
+            )?;
+        }
+        Command::SetFees {
+            pool,
+            deposit_fee_bps,
+            withdrawal_fee_bps,
+            reward_fee_bps,
+            sol_withdrawal_fee_bps,
+            manager_fee_account,
+        } => {
+            let (pool, pool_state) = load_pool_state(&rpc, &pool)?;
+            let fees = PoolFees {
+                deposit_fee_bps: deposit_fee_bps.unwrap_or(pool_state.deposit_fee_bps()),
+                withdrawal_fee_bps: withdrawal_fee_bps.unwrap_or(pool_state.withdrawal_fee_bps()),
+                reward_fee_bps: reward_fee_bps.unwrap_or(pool_state.reward_fee_bps()),
+                sol_withdrawal_fee_bps: sol_withdrawal_fee_bps
+                    .unwrap_or(pool_state.sol_withdrawal_fee_bps()),
+            };
+            let manager_fee_account = manager_fee_account.unwrap_or(pool.manager_fee_account);
+            send(
+                &rpc,
+                &signer,
+                &[],
+                vec![client::set_fees(&pool, fees, &manager_fee_account)],
+            )?;
+        }
+        Command::InitRateHistory { pool } => {
+            let pool = load_pool(&rpc, &pool)?;
+            let ix = client::initialize_rate_history(&pool);
+            send(&rpc, &signer, &[], vec![ix])?;
+        }
+        Command::RateHistory { pool, epochs } => {
+            let pool = load_pool(&rpc, &pool)?;
+            let rate_history = pool.rate_history.ok_or("pool has no rate history")?;
+            let account = fetch(&rpc, &rate_history)?;
+            let entries = client::decode_rate_history(&account.data)
+                .map_err(|error| format!("invalid rate history {rate_history}: {error:?}"))?;
+
+            let last_epoch = entries.first().map_or(0, |entry| entry.epoch);
+            let window_start = epochs.map_or(0, |epochs| {
+                last_epoch.saturating_sub(epochs.saturating_sub(1))
+            });
+            for entry in entries
+                .iter()
+                .take_while(|entry| entry.epoch >= window_start)
+            {
+                let rate = entry
+                    .rate()
+                    .map_err(|error| format!("invalid rate at epoch {}: {error:?}", entry.epoch))?;
+                println!(
+                    "epoch {}: {rate} ({} lamports / {} lst)",
+                    entry.epoch, entry.total_lamports, entry.lst_supply
+                );
+            }
+        }
+        Command::MigratePool { pool } => {
+            let ix = client::migrate_pool(&pool, &signer.pubkey());
+            send(&rpc, &signer, &[], vec![ix])?;
+        }
+        Command::Crank {
+            pool,
+            interval,
+            max_retries,
+        } => match interval {
+            // A keeper outlives transient failures and tries again next round
+            Some(seconds) => loop {
+                if let Err(error) = crank(&rpc, &signer, &pool, max_retries) {
+                    eprintln!("error: {error}");
+                }
+                thread::sleep(Duration::from_secs(seconds));
+            },
+            None => crank(&rpc, &signer, &pool, max_retries)?,
+        },
+    }
+
+    Ok(())
+}
+
+/// Sends the due cranks, re-reading pool state before every retry so a crank
+/// that landed despite a reported failure is not sent again.
+fn crank(rpc: &RpcClient, payer: &Keypair, pool_address: &Pubkey, max_retries: u32) -> Result<()> {
+    let mut attempt = 0;
+
+    loop {
+        match crank_once(rpc, payer, pool_address) {
+            Ok(()) => return Ok(()),
+            Err(error) if attempt < max_retries => {
+                attempt += 1;
+                eprintln!("crank failed: {error}; retry {attempt}/{max_retries}");
+                thread::sleep(Duration::from_secs(2 * u64::from(attempt)));
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+fn crank_once(rpc: &RpcClient, payer: &Keypair, pool_address: &Pubkey) -> Result<()> {
+    let epoch = rpc.get_epoch()?;
+    let (pool, pool_state) = load_pool_state(rpc, pool_address)?;
+    let pool_stake = fetch(rpc, &pool.pool_stake)?;
+    let reserve = rpc.get_account(&pool.reserve_stake)?;
+    let stake_rent = rpc.get_minimum_balance_for_rent_exemption(STAKE_ACCOUNT_SIZE as usize)?;
+
+    let validators = match pool.validator_list {
+        Some(validator_list) => {
+            client::decode_validator_list(&fetch(rpc, &validator_list)?.data)
+                .map_err(|error| format!("invalid validator list {validator_list}: {error:?}"))?
+        }
+        None => Vec::new(),
+    };
+
+    // Transient stakes are merged the epoch after activation, so only recent
+    // epochs can still hold one. Entry 0 of the list is the primary validator
+    let transient_keys: Vec<(Pubkey, u64)> = std::iter::once(pool.validator_vote)
+        .chain(
+            validators
+                .iter()
+                .skip(1)
+                .map(|entry| Pubkey::new_from_array(entry.vote_account)),
+        )
+        .flat_map(|validator_vote| {
+            (epoch.saturating_sub(TRANSIENT_LOOKBACK_EPOCHS)..=epoch)
+                .map(move |transient_epoch| (validator_vote, transient_epoch))
+        })
+        .collect();
+    let transient_addresses: Vec<Pubkey> = transient_keys
+        .iter()
+        .map(|(validator_vote, transient_epoch)| {
+            client::find_transient_stake_address(&pool.address, validator_vote, *transient_epoch).0
+        })
+        .collect();
+    let transient_accounts = rpc.get_multiple_accounts(&transient_addresses)?;
+    let transient_stakes: Vec<TransientStake> = transient_keys
+        .iter()
+        .zip(&transient_accounts)
+        .filter_map(|((validator_vote, transient_epoch), account)| {
+            account.as_ref().map(|account| TransientStake {
+                validator_vote: *validator_vote,
+                epoch: *transient_epoch,
+                data: &account.data,
+            })
+        })
+        .collect();
+
+    let cranks = client::due_cranks(&KeeperView {
+        pool_state: &pool_state,
+        pool_stake_data: &pool_stake.data,
+        reserve_lamports: reserve.as_ref().map(|account| account.lamports),
+        reserve_data: reserve.as_ref().map_or(&[], |account| &account.data),
+        transient_stakes: &transient_stakes,
+        stake_rent,
+        epoch,
+    });
+
+    if cranks.is_empty() {
+        println!("nothing to crank in epoch {epoch}");
+    }
+
+    let validator_stakes: Vec<Pubkey> = validators
+        .iter()
+        .skip(1)
+        .map(|entry| Pubkey::new_from_array(entry.stake_account))
+        .collect();
+
+    for crank in cranks {
+        if let Some(ix) = client::crank_instruction(&pool, crank, &validator_stakes) {
+            println!("sending {crank:?}");
+            send(rpc, payer, &[], vec![ix])?;
+        }
+    }
+
+    Ok(())
+}
+
+fn fetch(rpc: &RpcClient, address: &Pubkey) -> Result<RpcAccount> {
+    rpc.get_account(address)?
+        .ok_or_else(|| format!("account {address} not found").into())
+}
+
+fn load_pool_state(rpc: &RpcClient, address: &Pubkey) -> Result<(Pool, PoolState)> {
+    let pool_state = client::decode_pool_state(&fetch(rpc, address)?.data)
+        .map_err(|error| format!("invalid pool {address}: {error:?}"))?;
+
+    Ok((Pool::new(*address, &pool_state), pool_state))
+}
+
+fn load_pool(rpc: &RpcClient, address: &Pubkey) -> Result<Pool> {
+    Ok(load_pool_state(rpc, address)?.0)
+}
+
+/// Lowest nonce from `from` on whose withdraw stake does not exist.
+fn next_unused_nonce(rpc: &RpcClient, pool: &Pool, user: &Pubkey, from: u64) -> Result<u64> {
+    let mut nonce = from;
+
+    loop {
+        let addresses: Vec<Pubkey> = (nonce..nonce + 100)
+            .map(|nonce| client::find_withdraw_stake_address(&pool.address, user, nonce).0)
+            .collect();
+        let accounts = rpc.get_multiple_accounts(&addresses)?;
+
+        if let Some(offset) = accounts.iter().position(Option::is_none) {
+            return Ok(nonce + offset as u64);
+        }
+        nonce += 100;
+    }
+}
+
+fn create_associated_token_account_idempotent(owner: &Pubkey, pool: &Pool) -> Instruction {
+    Instruction {
+        program_id: ATA_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(pool.lst_token_address(owner), false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(pool.lst_mint, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pool.token_program, false),
+        ],
+        data: vec![1],
+    }
+}
+
+fn send(
+    rpc: &RpcClient,
+    payer: &Keypair,
+    extra_signers: &[&Keypair],
+    instructions: Vec<Instruction>,
+) -> Result<()> {
+    let mut signers = vec![payer];
+    signers.extend_from_slice(extra_signers);
+
+    let transaction = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &signers,
+        rpc.get_latest_blockhash()?,
+    );
+    let signature = rpc.send_and_confirm_transaction(&transaction)?;
+    println!("signature: {signature}");
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+use std::{str::FromStr, thread, time::Duration};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde_json::{Value, json};
+use solana_sdk::{hash::Hash, pubkey::Pubkey, signature::Signature, transaction::Transaction};
+
+use crate::Result;
+
+/// Accounts are fetched with `getMultipleAccounts` in chunks of this size,
+/// the RPC limit.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+pub struct RpcAccount {
+    pub lamports: u64,
+    pub owner: Pubkey,
+    pub data: Vec<u8>,
+}
+
+/// Minimal JSON-RPC client covering the calls the CLI needs.
+pub struct RpcClient {
+    url: String,
+    agent: ureq::Agent,
+}
+
+impl RpcClient {
+    pub fn new(url: String) -> Self {
+        Self {
+            url,
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let response: Value = self
+            .agent
+            .post(&self.url)
+            .send_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": method,
+                "params": params,
+            }))?
+            .into_json()?;
+
+        if let Some(error) = response.get("error") {
+            return Err(format!("{method} failed: {error}").into());
+        }
+
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| format!("{method} returned no result").into())
+    }
+
+    pub fn get_account(&self, address: &Pubkey) -> Result<Option<RpcAccount>> {
+        let result = self.request(
+            "getAccountInfo",
+            json!([address.to_string(), { "encoding": "base64", "commitment": "confirmed" }]),
+        )?;
+
+        parse_account(&result["value"])
+    }
+
+    pub fn get_multiple_accounts(&self, addresses: &[Pubkey]) -> Result<Vec<Option<RpcAccount>>> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+
+        for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let keys: Vec<String> = chunk.iter().map(Pubkey::to_string).collect();
+            let result = self.request(
+                "getMultipleAccounts",
+                json!([keys, { "encoding": "base64", "commitment": "confirmed" }]),
+            )?;
+
+            let values = result["value"]
+                .as_array()
+                .ok_or("getMultipleAccounts returned no accounts")?;
+            for value in values {
+                accounts.push(parse_account(value)?);
+            }
+        }
+
+        Ok(accounts)
+    }
+
+    pub fn get_epoch(&self) -> Result<u64> {
+        let result = self.request("getEpochInfo", json!([{ "commitment": "confirmed" }]))?;
+
+        result["epoch"]
+            .as_u64()
+            .ok_or_else(|| "getEpochInfo returned no epoch".into())
+    }
+
+    pub fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> Result<u64> {
+        self.request("getMinimumBalanceForRentExemption", json!([data_len]))?
+            .as_u64()
+            .ok_or_else(|| "getMinimumBalanceForRentExemption returned no lamports".into())
+    }
+
+    pub fn get_latest_blockhash(&self) -> Result<Hash> {
+        let result = self.request("getLatestBlockhash", json!([{ "commitment": "confirmed" }]))?;
+        let blockhash = result["value"]["blockhash"]
+            .as_str()
+            .ok_or("getLatestBlockhash returned no blockhash")?;
+
+        Ok(Hash::from_str(blockhash)?)
+    }
+
+    /// Sends a signed transaction and polls until it is confirmed.
+    pub fn send_and_confirm_transaction(&self, transaction: &Transaction) -> Result<Signature> {
+        let encoded = STANDARD.encode(bincode::serialize(transaction)?);
+        let result = self.request(
+            "sendTransaction",
+            json!([encoded, { "encoding": "base64", "preflightCommitment": "confirmed" }]),
+        )?;
+        let signature = Signature::from_str(
+            result
+                .as_str()
+                .ok_or("sendTransaction returned no signature")?,
+        )?;
+
+        for _ in 0..60 {
+            let result = self.request("getSignatureStatuses", json!([[signature.to_string()]]))?;
+            let status = &result["value"][0];
+
+            if !status.is_null() {
+                if !status["err"].is_null() {
+                    return Err(format!("transaction {signature} failed: {}", status["err"]).into());
+                }
+                if matches!(
+                    status["confirmationStatus"].as_str(),
+                    Some("confirmed" | "finalized")
+                ) {
+                    return Ok(signature);
+                }
+            }
+
+            thread::sleep(Duration::from_millis(500));
+        }
+
+        Err(format!("transaction {signature} was not confirmed in time").into())
+    }
+}
+
+fn parse_account(value: &Value) -> Result<Option<RpcAccount>> {
+    if value.is_null() {
+        return Ok(None);
+    }
+
+    let data = value["data"][0]
+        .as_str()
+        .ok_or("account data is not base64 encoded")?;
+
+    Ok(Some(RpcAccount {
+        lamports: value["lamports"]
+            .as_u64()
+            .ok_or("account has no lamports")?,
+        owner: Pubkey::from_str(value["owner"].as_str().ok_or("account has no owner")?)?,
+        data: STANDARD.decode(data)?,
+    }))
+}
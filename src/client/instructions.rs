@@ -0,0 +1,891 @@
+use solana_instruction::{AccountMeta, Instruction};
+use solana_pubkey::Pubkey;
+
+use super::{
+    ATA_PROGRAM_ID, CLOCK_SYSVAR_ID, PROGRAM_ID, Pool, RENT_SYSVAR_ID, STAKE_CONFIG_ID,
+    STAKE_HISTORY_SYSVAR_ID, STAKE_PROGRAM_ID, SYSTEM_PROGRAM_ID, TOKEN_METADATA_PROGRAM_ID,
+    TOKEN_PROGRAM_ID, find_metadata_address, find_pool_stake_address, find_pool_state_address,
+    find_rate_history_address, find_reserve_stake_address, find_transient_stake_address,
+    find_validator_list_address, find_validator_stake_address, find_withdraw_ledger_address,
+    find_withdraw_stake_address, find_withdraw_ticket_address,
+    get_associated_token_address_with_program_id,
+};
+use crate::{
+    AcceptManager, AddValidator, CloseWithdrawLedger, CompleteRedelegate, Deposit, DepositStake,
+    GetAverageRate, GetExchangeRate, IncreaseValidatorStake, Initialize, InitializeRateHistory,
+    InitializeReserve, InitializeWithdrawLedger, MergeReserve, MigratePool, PruneWithdrawLedger,
+    Redelegate, RemoveValidator, SetDepositAuthority, SetFees, SetManager, SetStaker, SetStatus,
+    TransferWithdrawTicket, UpdateConfig, UpdatePoolBalance, UpdateTokenMetadata, Withdraw,
+    WithdrawComplete, WithdrawSol,
+};
+
+/// Fees set at `Initialize` and replaced with `set_fees`, in basis points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolFees {
+    pub deposit_fee_bps: u16,
+    pub withdrawal_fee_bps: u16,
+    pub reward_fee_bps: u16,
+    pub sol_withdrawal_fee_bps: u16,
+}
+
+/// Deposit limits set with `UpdateConfig`, in lamports; 0 disables a limit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DepositLimits {
+    pub max_total_lamports: u64,
+    pub max_deposit_lamports: u64,
+    pub min_deposit_lamports: u64,
+}
+
+/// LST name, symbol and URI shown by wallets. Limits are `MAX_NAME_LEN`,
+/// `MAX_SYMBOL_LEN` and `MAX_URI_LEN` bytes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TokenMetadataArgs {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+impl TokenMetadataArgs {
+    /// `name_len: u8 | name | symbol_len: u8 | symbol | uri_len: u8 | uri`.
+    /// Over-long fields are rejected by the program, not truncated here.
+    fn encode(&self, data: &mut Vec<u8>) {
+        for field in [&self.name, &self.symbol, &self.uri] {
+            data.push(field.len().min(u8::MAX as usize) as u8);
+            data.extend_from_slice(field.as_bytes());
+        }
+    }
+}
+
+fn instruction_data(discriminator: u8, fields: &[u64]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(1 + fields.len() * 8);
+    data.push(discriminator);
+    for field in fields {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data
+}
+
+/// `lst_mint` is a fresh keypair that must also sign the transaction.
+pub fn initialize(
+    initializer: &Pubkey,
+    lst_mint: &Pubkey,
+    validator_vote: &Pubkey,
+    seed: u64,
+    fees: PoolFees,
+) -> Instruction {
+    initialize_with_token_program(
+        initializer,
+        lst_mint,
+        validator_vote,
+        seed,
+        fees,
+        &TOKEN_PROGRAM_ID,
+        None,
+    )
+}
+
+/// Like `initialize`, but also creates the LST metadata account.
+pub fn initialize_with_metadata(
+    initializer: &Pubkey,
+    lst_mint: &Pubkey,
+    validator_vote: &Pubkey,
+    seed: u64,
+    fees: PoolFees,
+    metadata: &TokenMetadataArgs,
+) -> Instruction {
+    initialize_with_token_program(
+        initializer,
+        lst_mint,
+        validator_vote,
+        seed,
+        fees,
+        &TOKEN_PROGRAM_ID,
+        Some(metadata),
+    )
+}
+
+/// Creates the LST mint under `token_program`, SPL Token or Token-2022.
+/// Token-2022 mints created with metadata also get a metadata pointer to it.
+pub fn initialize_with_token_program(
+    initializer: &Pubkey,
+    lst_mint: &Pubkey,
+    validator_vote: &Pubkey,
+    seed: u64,
+    fees: PoolFees,
+    token_program: &Pubkey,
+    metadata: Option<&TokenMetadataArgs>,
+) -> Instruction {
+    let (pool_state, _) = find_pool_state_address(seed);
+    let (pool_stake, _) = find_pool_stake_address(&pool_state);
+    let (reserve_stake, _) = find_reserve_stake_address(&pool_state);
+
+    let mut data = instruction_data(Initialize::DISCRIMINATOR, &[seed]);
+    data.extend_from_slice(&fees.deposit_fee_bps.to_le_bytes());
+    data.extend_from_slice(&fees.withdrawal_fee_bps.to_le_bytes());
+    data.extend_from_slice(&fees.reward_fee_bps.to_le_bytes());
+    data.extend_from_slice(&fees.sol_withdrawal_fee_bps.to_le_bytes());
+
+    let mut ix = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*initializer, true),
+            AccountMeta::new(
+                get_associated_token_address_with_program_id(initializer, lst_mint, token_program),
+                false,
+            ),
+            AccountMeta::new(pool_state, false),
+            AccountMeta::new(*lst_mint, true),
+            AccountMeta::new(pool_stake, false),
+            AccountMeta::new(reserve_stake, false),
+            AccountMeta::new_readonly(*validator_vote, false),
+            AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+            AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_CONFIG_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ATA_PROGRAM_ID, false),
+        ],
+        data,
+    };
+
+    if let Some(metadata) = metadata {
+        metadata.encode(&mut ix.data);
+        ix.accounts.extend([
+            AccountMeta::new(find_metadata_address(lst_mint).0, false),
+            AccountMeta::new_readonly(TOKEN_METADATA_PROGRAM_ID, false),
+        ]);
+    }
+
+    ix
+}
+
+/// Appends the pool's deposit authority as a signer when it has one.
+fn push_deposit_authority(pool: &Pool, accounts: &mut Vec<AccountMeta>) {
+    if let Some(deposit_authority) = pool.deposit_authority {
+        accounts.push(AccountMeta::new_readonly(deposit_authority, true));
+    }
+}
+
+/// Pass `minimum_lst_out = 0` to skip the slippage check.
+/// Permissioned pools also need `pool.deposit_authority` to sign.
+pub fn deposit(pool: &Pool, depositor: &Pubkey, amount: u64, minimum_lst_out: u64) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*depositor, true),
+        AccountMeta::new(pool.address, false),
+        AccountMeta::new_readonly(pool.pool_stake, false),
+        AccountMeta::new(pool.reserve_stake, false),
+        AccountMeta::new(pool.lst_mint, false),
+        AccountMeta::new(pool.lst_token_address(depositor), false),
+        AccountMeta::new(pool.manager_fee_account, false),
+        AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        AccountMeta::new_readonly(pool.token_program, false),
+        AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+    ];
+    push_deposit_authority(pool, &mut accounts);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction_data(Deposit::DISCRIMINATOR, &[amount, minimum_lst_out]),
+    }
+}
+
+/// `epoch` must be the current epoch; the reserve can be activated once per epoch.
+pub fn initialize_reserve(pool: &Pool, epoch: u64) -> Instruction {
+    let (transient_stake, _) =
+        find_transient_stake_address(&pool.address, &pool.validator_vote, epoch);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new_readonly(pool.pool_stake, false),
+            AccountMeta::new(pool.reserve_stake, false),
+            AccountMeta::new(transient_stake, false),
+            AccountMeta::new_readonly(pool.validator_vote, false),
+            AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+            AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_CONFIG_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+        ],
+        data: instruction_data(InitializeReserve::DISCRIMINATOR, &[]),
+    }
+}
+
+/// Merges the transient stake activated in `transient_epoch`, which must be
+/// an earlier epoch.
+pub fn merge_reserve(pool: &Pool, transient_epoch: u64) -> Instruction {
+    let (transient_stake, _) =
+        find_transient_stake_address(&pool.address, &pool.validator_vote, transient_epoch);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new(pool.pool_stake, false),
+            AccountMeta::new(transient_stake, false),
+            AccountMeta::new(pool.reserve_stake, false),
+            AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+        ],
+        data: instruction_data(MergeReserve::DISCRIMINATOR, &[]),
+    }
+}
+
+/// Merges a reserve delegated by the original `InitializeReserve` into the
+/// pool stake, leaving the reserve undelegated again.
+pub fn merge_legacy_reserve(pool: &Pool) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new(pool.pool_stake, false),
+            AccountMeta::new(pool.reserve_stake, false),
+            AccountMeta::new(pool.reserve_stake, false),
+            AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+        ],
+        data: instruction_data(MergeReserve::DISCRIMINATOR, &[]),
+    }
+}
+
+/// Merges the transient stake activated in `transient_epoch` by
+/// `increase_validator_stake` into the stake of `validator_vote`.
+pub fn merge_reserve_into_validator(
+    pool: &Pool,
+    transient_epoch: u64,
+    validator_vote: &Pubkey,
+) -> Instruction {
+    let (transient_stake, _) =
+        find_transient_stake_address(&pool.address, validator_vote, transient_epoch);
+    let (validator_list, _) = find_validator_list_address(&pool.address);
+    let (validator_stake, _) = find_validator_stake_address(&pool.address, validator_vote);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new(validator_stake, false),
+            AccountMeta::new(transient_stake, false),
+            AccountMeta::new(pool.reserve_stake, false),
+            AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+            AccountMeta::new(validator_list, false),
+        ],
+        data: instruction_data(MergeReserve::DISCRIMINATOR, &[]),
+    }
+}
+
+/// Pass `minimum_lamports_out = 0` to skip the slippage check.
+pub fn withdraw(
+    pool: &Pool,
+    user: &Pubkey,
+    amount: u64,
+    nonce: u64,
+    minimum_lamports_out: u64,
+) -> Instruction {
+    let (user_stake, _) = find_withdraw_stake_address(&pool.address, user, nonce);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new(pool.pool_stake, false),
+            AccountMeta::new_readonly(pool.reserve_stake, false),
+            AccountMeta::new(user_stake, false),
+            AccountMeta::new(pool.lst_mint, false),
+            AccountMeta::new(pool.lst_token_address(user), false),
+            AccountMeta::new(pool.manager_fee_account, false),
+            AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+            AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pool.token_program, false),
+        ],
+        data: instruction_data(
+            Withdraw::DISCRIMINATOR,
+            &[amount, nonce, minimum_lamports_out],
+        ),
+    }
+}
+
+pub fn withdraw_complete(pool: &Pool, user: &Pubkey, nonce: u64) -> Instruction {
+    let (user_stake, _) = find_withdraw_stake_address(&pool.address, user, nonce);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(pool.address, false),
+            AccountMeta::new(user_stake, false),
+            AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+        ],
+        data: instruction_data(WithdrawComplete::DISCRIMINATOR, &[nonce]),
+    }
+}
+
+/// `withdraw` that also issues a transferable `WithdrawTicket` for the
+/// withdraw stake; claim it with `withdraw_complete_with_ticket`.
+pub fn withdraw_with_ticket(
+    pool: &Pool,
+    user: &Pubkey,
+    amount: u64,
+    nonce: u64,
+    minimum_lamports_out: u64,
+) -> Instruction {
+    let (user_stake, _) = find_withdraw_stake_address(&pool.address, user, nonce);
+    let (ticket, _) = find_withdraw_ticket_address(&user_stake);
+
+    let mut ix = withdraw(pool, user, amount, nonce, minimum_lamports_out);
+    ix.accounts.push(AccountMeta::new(ticket, false));
+    ix
+}
+
+/// Claims a ticketed withdrawal; `owner` must hold the ticket and is paid
+/// the stake along with the ticket's rent.
+pub fn withdraw_complete_with_ticket(
+    pool: &Pool,
+    owner: &Pubkey,
+    withdraw_stake: &Pubkey,
+) -> Instruction {
+    let (ticket, _) = find_withdraw_ticket_address(withdraw_stake);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(pool.address, false),
+            AccountMeta::new(*withdraw_stake, false),
+            AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+            AccountMeta::new(ticket, false),
+        ],
+        data: vec![WithdrawComplete::DISCRIMINATOR],
+    }
+}
+
+pub fn transfer_withdraw_ticket(
+    ticket: &Pubkey,
+    owner: &Pubkey,
+    new_owner: &Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(*ticket, false),
+            AccountMeta::new_readonly(*new_owner, false),
+        ],
+        data: instruction_data(TransferWithdrawTicket::DISCRIMINATOR, &[]),
+    }
+}
+
+pub fn initialize_withdraw_ledger(pool: &Pool, user: &Pubkey) -> Instruction {
+    let (ledger, _) = find_withdraw_ledger_address(&pool.address, user);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new_readonly(pool.address, false),
+            AccountMeta::new(ledger, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        data: instruction_data(InitializeWithdrawLedger::DISCRIMINATOR, &[]),
+    }
+}
+
+/// The ledger must have no outstanding withdrawals left.
+pub fn close_withdraw_ledger(pool: &Pool, owner: &Pubkey) -> Instruction {
+    let (ledger, _) = find_withdraw_ledger_address(&pool.address, owner);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(ledger, false),
+        ],
+        data: instruction_data(CloseWithdrawLedger::DISCRIMINATOR, &[]),
+    }
+}
+
+/// Drops the ledger entries of `withdraw_stakes`, which must all have been
+/// claimed without the ledger. Signed by the ledger owner.
+pub fn prune_withdraw_ledger(
+    pool: &Pool,
+    owner: &Pubkey,
+    withdraw_stakes: &[Pubkey],
+) -> Instruction {
+    let (ledger, _) = find_withdraw_ledger_address(&pool.address, owner);
+
+    let mut accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(ledger, false),
+    ];
+    accounts.extend(
+        withdraw_stakes
+            .iter()
+            .map(|stake| AccountMeta::new_readonly(*stake, false)),
+    );
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction_data(PruneWithdrawLedger::DISCRIMINATOR, &[]),
+    }
+}
+
+/// Appends `ledger_owner`'s withdraw ledger to a withdraw or withdraw
+/// complete instruction. When claiming a ticket, `ledger_owner` is the user
+/// who withdrew rather than the ticket's current owner.
+pub fn push_withdraw_ledger(ix: &mut Instruction, pool: &Pool, ledger_owner: &Pubkey) {
+    let (ledger, _) = find_withdraw_ledger_address(&pool.address, ledger_owner);
+    ix.accounts.push(AccountMeta::new(ledger, false));
+}
+
+/// `withdraw` recorded in the user's withdraw ledger. `nonce` must be at
+/// least the ledger's `next_nonce`; pick the first one whose withdraw stake
+/// does not exist yet, since withdrawals made without the ledger may have
+/// taken some.
+pub fn withdraw_with_ledger(
+    pool: &Pool,
+    user: &Pubkey,
+    amount: u64,
+    nonce: u64,
+    minimum_lamports_out: u64,
+    issue_ticket: bool,
+) -> Instruction {
+    let mut ix = if issue_ticket {
+        withdraw_with_ticket(pool, user, amount, nonce, minimum_lamports_out)
+    } else {
+        withdraw(pool, user, amount, nonce, minimum_lamports_out)
+    };
+    push_withdraw_ledger(&mut ix, pool, user);
+    ix
+}
+
+/// `withdraw_complete` that also removes the stake from the user's ledger.
+pub fn withdraw_complete_with_ledger(pool: &Pool, user: &Pubkey, nonce: u64) -> Instruction {
+    let mut ix = withdraw_complete(pool, user, nonce);
+    push_withdraw_ledger(&mut ix, pool, user);
+    ix
+}
+
+/// `validator_stakes` are the stake accounts of validator list entries 1..,
+/// in list order; leave empty for a pool without a validator list. The rate
+/// history, if the pool has one, goes last.
+pub fn update_pool_balance(pool: &Pool, validator_stakes: &[Pubkey]) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(pool.address, false),
+        AccountMeta::new_readonly(pool.pool_stake, false),
+        AccountMeta::new_readonly(pool.reserve_stake, false),
+        AccountMeta::new(pool.lst_mint, false),
+        AccountMeta::new(pool.manager_fee_account, false),
+        AccountMeta::new_readonly(pool.token_program, false),
+    ];
+
+    if let Some(validator_list) = pool.validator_list {
+        accounts.push(AccountMeta::new(validator_list, false));
+        accounts.extend(
+            validator_stakes
+                .iter()
+                .map(|stake| AccountMeta::new_readonly(*stake, false)),
+        );
+    }
+
+    if let Some(rate_history) = pool.rate_history {
+        accounts.push(AccountMeta::new(rate_history, false));
+    }
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction_data(UpdatePoolBalance::DISCRIMINATOR, &[]),
+    }
+}
+
+/// The staker funds the new stake and receives LST for it.
+pub fn add_validator(pool: &Pool, validator_vote: &Pubkey) -> Instruction {
+    let (validator_list, _) = find_validator_list_address(&pool.address);
+    let (validator_stake, _) = find_validator_stake_address(&pool.address, validator_vote);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(pool.staker, true),
+            AccountMeta::new(pool.lst_token_address(&pool.staker), false),
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new(validator_list, false),
+            AccountMeta::new(validator_stake, false),
+            AccountMeta::new_readonly(*validator_vote, false),
+            AccountMeta::new(pool.lst_mint, false),
+            AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+            AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_CONFIG_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pool.token_program, false),
+            AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+        ],
+        data: instruction_data(AddValidator::DISCRIMINATOR, &[]),
+    }
+}
+
+pub fn remove_validator(pool: &Pool, validator_vote: &Pubkey) -> Instruction {
+    let (validator_list, _) = find_validator_list_address(&pool.address);
+    let (validator_stake, _) = find_validator_stake_address(&pool.address, validator_vote);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(pool.staker, true),
+            AccountMeta::new_readonly(pool.address, false),
+            AccountMeta::new(validator_list, false),
+            AccountMeta::new(validator_stake, false),
+            AccountMeta::new(pool.reserve_stake, false),
+            AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+        ],
+        data: instruction_data(RemoveValidator::DISCRIMINATOR, &[]),
+    }
+}
+
+/// Activates `amount` lamports of the reserve towards a listed validator in
+/// this epoch's transient stake. Signed by the staker.
+pub fn increase_validator_stake(
+    pool: &Pool,
+    validator_vote: &Pubkey,
+    amount: u64,
+    epoch: u64,
+) -> Instruction {
+    let (validator_list, _) = find_validator_list_address(&pool.address);
+    let (transient_stake, _) = find_transient_stake_address(&pool.address, validator_vote, epoch);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(pool.staker, true),
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new(validator_list, false),
+            AccountMeta::new(pool.reserve_stake, false),
+            AccountMeta::new(transient_stake, false),
+            AccountMeta::new_readonly(*validator_vote, false),
+            AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+            AccountMeta::new_readonly(RENT_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_CONFIG_ID, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+        ],
+        data: instruction_data(IncreaseValidatorStake::DISCRIMINATOR, &[amount]),
+    }
+}
+
+pub fn redelegate(pool: &Pool, new_validator_vote: &Pubkey) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(pool.staker, true),
+        AccountMeta::new(pool.address, false),
+        AccountMeta::new(pool.pool_stake, false),
+        AccountMeta::new_readonly(*new_validator_vote, false),
+        AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+        AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+    ];
+
+    if let Some(validator_list) = pool.validator_list {
+        accounts.push(AccountMeta::new_readonly(validator_list, false));
+    }
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction_data(Redelegate::DISCRIMINATOR, &[]),
+    }
+}
+
+/// Delegates the pool stake to `pool.pending_validator_vote`; returns `None`
+/// when no redelegation is pending.
+pub fn complete_redelegate(pool: &Pool) -> Option<Instruction> {
+    let new_validator_vote = pool.pending_validator_vote?;
+
+    let mut accounts = vec![
+        AccountMeta::new(pool.address, false),
+        AccountMeta::new(pool.pool_stake, false),
+        AccountMeta::new(pool.reserve_stake, false),
+        AccountMeta::new_readonly(new_validator_vote, false),
+        AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+        AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR_ID, false),
+        AccountMeta::new_readonly(STAKE_CONFIG_ID, false),
+        AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+    ];
+
+    if let Some(validator_list) = pool.validator_list {
+        accounts.push(AccountMeta::new(validator_list, false));
+    }
+
+    Some(Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction_data(CompleteRedelegate::DISCRIMINATOR, &[]),
+    })
+}
+
+/// `deposit_stake` must be an active delegation to the pool validator with
+/// `depositor` as both staker and withdrawer.
+pub fn deposit_stake(
+    pool: &Pool,
+    depositor: &Pubkey,
+    deposit_stake: &Pubkey,
+    minimum_lst_out: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*depositor, true),
+        AccountMeta::new(pool.address, false),
+        AccountMeta::new(pool.pool_stake, false),
+        AccountMeta::new(pool.reserve_stake, false),
+        AccountMeta::new(*deposit_stake, false),
+        AccountMeta::new(pool.lst_mint, false),
+        AccountMeta::new(pool.lst_token_address(depositor), false),
+        AccountMeta::new(pool.manager_fee_account, false),
+        AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+        AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR_ID, false),
+        AccountMeta::new_readonly(pool.token_program, false),
+        AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+    ];
+    push_deposit_authority(pool, &mut accounts);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction_data(DepositStake::DISCRIMINATOR, &[minimum_lst_out]),
+    }
+}
+
+pub fn withdraw_sol(
+    pool: &Pool,
+    user: &Pubkey,
+    amount: u64,
+    minimum_lamports_out: u64,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new(pool.reserve_stake, false),
+            AccountMeta::new(pool.lst_mint, false),
+            AccountMeta::new(pool.lst_token_address(user), false),
+            AccountMeta::new(pool.manager_fee_account, false),
+            AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+            AccountMeta::new_readonly(pool.token_program, false),
+        ],
+        data: instruction_data(WithdrawSol::DISCRIMINATOR, &[amount, minimum_lamports_out]),
+    }
+}
+
+/// Proposes `new_manager`; pass the current manager to cancel a pending transfer.
+pub fn set_manager(pool: &Pool, new_manager: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(pool.manager, true),
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new_readonly(*new_manager, false),
+        ],
+        data: instruction_data(SetManager::DISCRIMINATOR, &[]),
+    }
+}
+
+/// Signed by the manager proposed with `set_manager`.
+pub fn accept_manager(pool: &Pool, new_manager: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*new_manager, true),
+            AccountMeta::new(pool.address, false),
+        ],
+        data: instruction_data(AcceptManager::DISCRIMINATOR, &[]),
+    }
+}
+
+/// `signer` is either the manager or the current staker.
+pub fn set_staker(pool: &Pool, signer: &Pubkey, new_staker: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*signer, true),
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new_readonly(*new_staker, false),
+        ],
+        data: instruction_data(SetStaker::DISCRIMINATOR, &[]),
+    }
+}
+
+/// `status` is a combination of the `PAUSE_*` bits; 0 resumes everything.
+pub fn set_status(pool: &Pool, status: u8) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(pool.manager, true),
+            AccountMeta::new(pool.address, false),
+        ],
+        data: vec![SetStatus::DISCRIMINATOR, status],
+    }
+}
+
+/// Takes the pool address rather than a `Pool`, since pools on an older
+/// layout cannot be decoded until they are migrated.
+pub fn migrate_pool(pool_address: &Pubkey, payer: &Pubkey) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*pool_address, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new(find_pool_stake_address(pool_address).0, false),
+            AccountMeta::new(find_reserve_stake_address(pool_address).0, false),
+            AccountMeta::new_readonly(CLOCK_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR_ID, false),
+            AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+        ],
+        data: instruction_data(MigratePool::DISCRIMINATOR, &[]),
+    }
+}
+
+/// Replaces the LST name, symbol and URI. Signed by the manager.
+pub fn update_token_metadata(pool: &Pool, metadata: &TokenMetadataArgs) -> Instruction {
+    let mut data = vec![UpdateTokenMetadata::DISCRIMINATOR];
+    metadata.encode(&mut data);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(pool.manager, true),
+            AccountMeta::new_readonly(pool.address, false),
+            AccountMeta::new(find_metadata_address(&pool.lst_mint).0, false),
+            AccountMeta::new_readonly(TOKEN_METADATA_PROGRAM_ID, false),
+        ],
+        data,
+    }
+}
+
+/// Replaces all three deposit limits. Signed by the manager.
+pub fn update_config(pool: &Pool, limits: DepositLimits) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(pool.manager, true),
+            AccountMeta::new(pool.address, false),
+        ],
+        data: instruction_data(
+            UpdateConfig::DISCRIMINATOR,
+            &[
+                limits.max_total_lamports,
+                limits.max_deposit_lamports,
+                limits.min_deposit_lamports,
+            ],
+        ),
+    }
+}
+
+/// `None` makes the pool public again. Signed by the manager.
+pub fn set_deposit_authority(pool: &Pool, deposit_authority: Option<&Pubkey>) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(pool.manager, true),
+        AccountMeta::new(pool.address, false),
+    ];
+    if let Some(deposit_authority) = deposit_authority {
+        accounts.push(AccountMeta::new_readonly(*deposit_authority, false));
+    }
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction_data(SetDepositAuthority::DISCRIMINATOR, &[]),
+    }
+}
+
+/// Replaces the fees and the LST account they are paid to. Signed by the
+/// manager.
+pub fn set_fees(pool: &Pool, fees: PoolFees, manager_fee_account: &Pubkey) -> Instruction {
+    let mut data = vec![SetFees::DISCRIMINATOR];
+    for fee_bps in [
+        fees.deposit_fee_bps,
+        fees.withdrawal_fee_bps,
+        fees.reward_fee_bps,
+        fees.sol_withdrawal_fee_bps,
+    ] {
+        data.extend_from_slice(&fee_bps.to_le_bytes());
+    }
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(pool.manager, true),
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new_readonly(*manager_fee_account, false),
+        ],
+        data,
+    }
+}
+
+/// The manager pays for the history; `UpdatePoolBalance` requires it from
+/// then on.
+pub fn initialize_rate_history(pool: &Pool) -> Instruction {
+    let (rate_history, _) = find_rate_history_address(&pool.address);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(pool.manager, true),
+            AccountMeta::new(pool.address, false),
+            AccountMeta::new(rate_history, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+        ],
+        data: instruction_data(InitializeRateHistory::DISCRIMINATOR, &[]),
+    }
+}
+
+/// Read-only; decode the return data with `AverageRate::from_return_data`.
+pub fn get_average_rate(pool: &Pool, epochs: u64) -> Instruction {
+    let (rate_history, _) = find_rate_history_address(&pool.address);
+
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(pool.address, false),
+            AccountMeta::new_readonly(rate_history, false),
+        ],
+        data: instruction_data(GetAverageRate::DISCRIMINATOR, &[epochs]),
+    }
+}
+
+/// Read-only; fails with `StalePoolBalance` until this epoch's
+/// `update_pool_balance`. Decode the return data with
+/// `ExchangeRate::from_return_data`.
+pub fn get_exchange_rate(pool: &Pool) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(pool.address, false),
+            AccountMeta::new_readonly(pool.pool_stake, false),
+            AccountMeta::new_readonly(pool.reserve_stake, false),
+            AccountMeta::new_readonly(pool.lst_mint, false),
+        ],
+        data: instruction_data(GetExchangeRate::DISCRIMINATOR, &[]),
+    }
+}
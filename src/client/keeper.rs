@@ -0,0 +1,167 @@
+use solana_instruction::Instruction;
+use solana_pubkey::Pubkey;
+
+use super::{
+    Pool, complete_redelegate, initialize_reserve, merge_legacy_reserve, merge_reserve,
+    merge_reserve_into_validator, update_pool_balance,
+};
+use crate::{MIN_STAKE_DELEGATION, PoolState, StakeStateV2};
+
+/// Permissionless instructions a keeper sends to move a pool forward.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Crank {
+    UpdatePoolBalance,
+    CompleteRedelegate,
+    InitializeReserve {
+        epoch: u64,
+    },
+    /// `validator_vote` is the transient stake's validator, which decides
+    /// the stake it merges into.
+    MergeReserve {
+        transient_epoch: u64,
+        validator_vote: Pubkey,
+    },
+    /// Folds a reserve the original `InitializeReserve` delegated into the
+    /// pool stake.
+    MergeLegacyReserve,
+}
+
+/// Delegation read from a stake account in the `Stake` state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StakeDelegation {
+    pub voter_pubkey: Pubkey,
+    pub activation_epoch: u64,
+    pub deactivation_epoch: u64,
+}
+
+impl StakeDelegation {
+    /// Returns `None` unless `data` is a delegated stake account.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let delegation = StakeStateV2::load(data).ok()?.delegation()?;
+
+        Some(Self {
+            voter_pubkey: Pubkey::new_from_array(delegation.voter_pubkey),
+            activation_epoch: delegation.activation_epoch(),
+            deactivation_epoch: delegation.deactivation_epoch(),
+        })
+    }
+
+    pub fn is_active(&self, epoch: u64) -> bool {
+        self.activation_epoch < epoch && self.deactivation_epoch == u64::MAX
+    }
+
+    pub fn is_inactive(&self, epoch: u64) -> bool {
+        self.deactivation_epoch < epoch
+    }
+}
+
+/// A transient stake account that exists on-chain, keyed by its PDA
+/// validator and epoch.
+pub struct TransientStake<'a> {
+    pub validator_vote: Pubkey,
+    pub epoch: u64,
+    pub data: &'a [u8],
+}
+
+/// Observed on-chain state the crank decision is based on.
+pub struct KeeperView<'a> {
+    pub pool_state: &'a PoolState,
+    pub pool_stake_data: &'a [u8],
+    /// Balance of the reserve account, `None` when it does not exist.
+    pub reserve_lamports: Option<u64>,
+    pub reserve_data: &'a [u8],
+    /// Existing transient stakes; see `find_transient_stake_address`.
+    pub transient_stakes: &'a [TransientStake<'a>],
+    /// Rent-exempt minimum of a stake account.
+    pub stake_rent: u64,
+    pub epoch: u64,
+}
+
+/// Cranks that are due, in the order they should be sent.
+///
+/// Every crank here fails on-chain once it has already been applied, so a
+/// keeper that re-reads state and calls this again after a failure never
+/// double-applies one.
+pub fn due_cranks(view: &KeeperView) -> Vec<Crank> {
+    let mut cranks = Vec::new();
+    let pool_state = view.pool_state;
+
+    if pool_state.cranks_paused() {
+        return cranks;
+    }
+
+    if pool_state.last_update_epoch != view.epoch {
+        cranks.push(Crank::UpdatePoolBalance);
+    }
+
+    if pool_state.is_redelegating() {
+        // The pool stake takes a new delegation only once its cooldown is over
+        if StakeDelegation::parse(view.pool_stake_data)
+            .is_some_and(|stake| stake.is_inactive(view.epoch))
+        {
+            cranks.push(Crank::CompleteRedelegate);
+        }
+
+        // Reserve SOL cannot be activated towards a validator being left
+        return cranks;
+    }
+
+    if StakeDelegation::parse(view.reserve_data).is_some_and(|stake| stake.is_active(view.epoch)) {
+        cranks.push(Crank::MergeLegacyReserve);
+    }
+
+    for transient in view.transient_stakes {
+        if StakeDelegation::parse(transient.data).is_some_and(|stake| stake.is_active(view.epoch)) {
+            cranks.push(Crank::MergeReserve {
+                transient_epoch: transient.epoch,
+                validator_vote: transient.validator_vote,
+            });
+        }
+    }
+
+    // Other validators' transient stakes do not hold up the reserve
+    let primary_vote = Pubkey::new_from_array(pool_state.validator_vote);
+    let activated_this_epoch = view
+        .transient_stakes
+        .iter()
+        .any(|transient| transient.validator_vote == primary_vote && transient.epoch == view.epoch);
+    let reserve_delegatable = view.reserve_data.is_empty()
+        || StakeStateV2::load(view.reserve_data).is_ok_and(|reserve| reserve.is_uninitialized());
+
+    if !activated_this_epoch
+        && reserve_delegatable
+        && pool_state
+            .reserve_lamports
+            .min(view.reserve_lamports.unwrap_or(0))
+            >= view.stake_rent * 2 + MIN_STAKE_DELEGATION
+    {
+        cranks.push(Crank::InitializeReserve { epoch: view.epoch });
+    }
+
+    cranks
+}
+
+/// `validator_stakes` is only used by `UpdatePoolBalance`; see `update_pool_balance`.
+pub fn crank_instruction(
+    pool: &Pool,
+    crank: Crank,
+    validator_stakes: &[Pubkey],
+) -> Option<Instruction> {
+    match crank {
+        Crank::UpdatePoolBalance => Some(update_pool_balance(pool, validator_stakes)),
+        Crank::CompleteRedelegate => complete_redelegate(pool),
+        Crank::InitializeReserve { epoch } => Some(initialize_reserve(pool, epoch)),
+        Crank::MergeReserve {
+            transient_epoch,
+            validator_vote,
+        } if validator_vote != pool.validator_vote => Some(merge_reserve_into_validator(
+            pool,
+            transient_epoch,
+            &validator_vote,
+        )),
+        Crank::MergeReserve {
+            transient_epoch, ..
+        } => Some(merge_reserve(pool, transient_epoch)),
+        Crank::MergeLegacyReserve => Some(merge_legacy_reserve(pool)),
+    }
+}
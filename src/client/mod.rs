@@ -0,0 +1,31 @@
+//! Off-chain helpers for services talking to the program: PDA derivation,
+//! account decoding and instruction builders. Enabled by the `client` feature.
+
+pub mod instructions;
+pub mod keeper;
+pub mod pda;
+pub mod state;
+
+pub use instructions::*;
+pub use keeper::*;
+pub use pda::*;
+pub use state::*;
+
+use solana_pubkey::Pubkey;
+
+pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(crate::ID);
+pub const STAKE_PROGRAM_ID: Pubkey = Pubkey::new_from_array(crate::STAKE_PROGRAM_ID);
+pub const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array(pinocchio_system::ID);
+pub const TOKEN_PROGRAM_ID: Pubkey = Pubkey::new_from_array(pinocchio_token::ID);
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = Pubkey::new_from_array(crate::TOKEN_2022_PROGRAM_ID);
+pub const ATA_PROGRAM_ID: Pubkey = Pubkey::new_from_array(pinocchio_associated_token_account::ID);
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    Pubkey::new_from_array(crate::TOKEN_METADATA_PROGRAM_ID);
+
+pub const CLOCK_SYSVAR_ID: Pubkey = Pubkey::new_from_array(pinocchio::sysvars::clock::CLOCK_ID);
+pub const RENT_SYSVAR_ID: Pubkey = Pubkey::new_from_array(pinocchio::sysvars::rent::RENT_ID);
+pub const STAKE_HISTORY_SYSVAR_ID: Pubkey = Pubkey::new_from_array(crate::STAKE_HISTORY_ID);
+pub const STAKE_CONFIG_ID: Pubkey = Pubkey::new_from_array([
+    6, 161, 216, 23, 165, 2, 5, 11, 104, 7, 145, 230, 206, 95, 249, 248, 36, 45, 178, 171, 63, 252,
+    207, 199, 82, 86, 83, 0, 0, 99, 1, 1,
+]);
@@ -0,0 +1,109 @@
+use solana_pubkey::Pubkey;
+
+use super::{ATA_PROGRAM_ID, PROGRAM_ID, TOKEN_METADATA_PROGRAM_ID, TOKEN_PROGRAM_ID};
+
+pub fn find_pool_state_address(seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"lst_pool", &seed.to_le_bytes()], &PROGRAM_ID)
+}
+
+pub fn find_pool_stake_address(pool_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"stake", pool_state.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_reserve_stake_address(pool_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"reserve_stake", pool_state.as_ref()], &PROGRAM_ID)
+}
+
+/// Stake account the reserve is activated into towards `validator_vote`
+/// during `epoch`, by `InitializeReserve` or `IncreaseValidatorStake`.
+pub fn find_transient_stake_address(
+    pool_state: &Pubkey,
+    validator_vote: &Pubkey,
+    epoch: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"transient_stake",
+            pool_state.as_ref(),
+            validator_vote.as_ref(),
+            &epoch.to_le_bytes(),
+        ],
+        &PROGRAM_ID,
+    )
+}
+
+/// Stake account created by `Withdraw` and drained by `WithdrawComplete`.
+pub fn find_withdraw_stake_address(pool_state: &Pubkey, user: &Pubkey, nonce: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"withdraw",
+            pool_state.as_ref(),
+            user.as_ref(),
+            &nonce.to_le_bytes(),
+        ],
+        &PROGRAM_ID,
+    )
+}
+
+/// Ticket for a withdraw stake account issued with `withdraw_with_ticket`.
+pub fn find_withdraw_ticket_address(withdraw_stake: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"ticket", withdraw_stake.as_ref()], &PROGRAM_ID)
+}
+
+/// Per-user `WithdrawLedger` that allocates withdraw nonces.
+pub fn find_withdraw_ledger_address(pool_state: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"ledger", pool_state.as_ref(), user.as_ref()],
+        &PROGRAM_ID,
+    )
+}
+
+/// Per-pool `RateHistory` appended to by `UpdatePoolBalance`.
+pub fn find_rate_history_address(pool_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"rate_history", pool_state.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_validator_list_address(pool_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"validator_list", pool_state.as_ref()], &PROGRAM_ID)
+}
+
+pub fn find_validator_stake_address(pool_state: &Pubkey, validator_vote: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"validator_stake",
+            pool_state.as_ref(),
+            validator_vote.as_ref(),
+        ],
+        &PROGRAM_ID,
+    )
+}
+
+pub fn get_associated_token_address(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, &TOKEN_PROGRAM_ID)
+}
+
+/// Associated token account for a mint owned by `token_program`, SPL Token
+/// or Token-2022.
+pub fn get_associated_token_address_with_program_id(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ATA_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Metaplex metadata account for the LST mint.
+pub fn find_metadata_address(mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            TOKEN_METADATA_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &TOKEN_METADATA_PROGRAM_ID,
+    )
+}
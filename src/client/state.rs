@@ -0,0 +1,212 @@
+use pinocchio::program_error::ProgramError;
+use solana_pubkey::Pubkey;
+
+use super::get_associated_token_address_with_program_id;
+use crate::{
+    PinocchioError, PoolState, RateHistory, RateHistoryEntry, ValidatorList, ValidatorStakeInfo,
+    WithdrawLedger, WithdrawLedgerEntry, WithdrawTicket,
+};
+
+/// Copies a `PoolState` out of raw account data. Unlike `PoolState::load`
+/// this does not require the buffer to be aligned.
+pub fn decode_pool_state(data: &[u8]) -> Result<PoolState, ProgramError> {
+    PoolState::check_layout(data)?;
+
+    let pool_state = unsafe { core::ptr::read_unaligned(data.as_ptr() as *const PoolState) };
+
+    if pool_state.discriminator == 0 {
+        return Err(PinocchioError::PoolNotInitialized.into());
+    }
+
+    Ok(pool_state)
+}
+
+/// Copies the entries out of a validator list account, primary stake first.
+pub fn decode_validator_list(data: &[u8]) -> Result<Vec<ValidatorStakeInfo>, ProgramError> {
+    if data.len() < ValidatorList::LEN
+        || !(data.len() - ValidatorList::LEN).is_multiple_of(ValidatorStakeInfo::LEN)
+        || data[0] != ValidatorList::DISCRIMINATOR
+    {
+        return Err(PinocchioError::InvalidAccountData.into());
+    }
+
+    let count = u32::from_le_bytes(data[4..8].try_into().unwrap()) as usize;
+    let entries: Vec<ValidatorStakeInfo> = data[ValidatorList::LEN..]
+        .chunks_exact(ValidatorStakeInfo::LEN)
+        .map(|entry| unsafe {
+            core::ptr::read_unaligned(entry.as_ptr() as *const ValidatorStakeInfo)
+        })
+        .collect();
+
+    if entries.len() != count {
+        return Err(PinocchioError::InvalidAccountData.into());
+    }
+
+    Ok(entries)
+}
+
+/// Copies a `WithdrawTicket` out of raw account data.
+pub fn decode_withdraw_ticket(data: &[u8]) -> Result<WithdrawTicket, ProgramError> {
+    if data.len() != WithdrawTicket::LEN || data[0] != WithdrawTicket::DISCRIMINATOR {
+        return Err(PinocchioError::InvalidWithdrawTicket.into());
+    }
+
+    Ok(unsafe { core::ptr::read_unaligned(data.as_ptr() as *const WithdrawTicket) })
+}
+
+/// Copies the header and the outstanding withdrawals out of a withdraw
+/// ledger account.
+pub fn decode_withdraw_ledger(
+    data: &[u8],
+) -> Result<(WithdrawLedger, Vec<WithdrawLedgerEntry>), ProgramError> {
+    if data.len() < WithdrawLedger::LEN
+        || !(data.len() - WithdrawLedger::LEN).is_multiple_of(WithdrawLedgerEntry::LEN)
+        || data[0] != WithdrawLedger::DISCRIMINATOR
+    {
+        return Err(PinocchioError::InvalidWithdrawLedger.into());
+    }
+
+    let ledger = unsafe { core::ptr::read_unaligned(data.as_ptr() as *const WithdrawLedger) };
+    let entries: Vec<WithdrawLedgerEntry> = data[WithdrawLedger::LEN..]
+        .chunks_exact(WithdrawLedgerEntry::LEN)
+        .map(|entry| unsafe {
+            core::ptr::read_unaligned(entry.as_ptr() as *const WithdrawLedgerEntry)
+        })
+        .collect();
+
+    if entries.len() != ledger.count as usize {
+        return Err(PinocchioError::InvalidWithdrawLedger.into());
+    }
+
+    Ok((ledger, entries))
+}
+
+/// Copies the entries out of a rate history account, newest first.
+pub fn decode_rate_history(data: &[u8]) -> Result<Vec<RateHistoryEntry>, ProgramError> {
+    if data.len() != RateHistory::LEN || data[0] != RateHistory::DISCRIMINATOR {
+        return Err(PinocchioError::InvalidRateHistory.into());
+    }
+
+    let history = unsafe { core::ptr::read_unaligned(data.as_ptr() as *const RateHistory) };
+    if history.count as usize > RateHistory::CAPACITY
+        || history.head as usize >= RateHistory::CAPACITY
+    {
+        return Err(PinocchioError::InvalidRateHistory.into());
+    }
+
+    Ok(history.iter().copied().collect())
+}
+
+/// Return data of `GetAverageRate`. Rates are lamports per whole LST.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AverageRate {
+    pub current_rate: u64,
+    pub average_rate: u64,
+    /// Oldest and newest epoch averaged over
+    pub first_epoch: u64,
+    pub last_epoch: u64,
+}
+
+impl AverageRate {
+    pub fn from_return_data(data: &[u8]) -> Option<Self> {
+        if data.len() != 32 {
+            return None;
+        }
+
+        let field = |i: usize| u64::from_le_bytes(data[i * 8..i * 8 + 8].try_into().unwrap());
+        Some(Self {
+            current_rate: field(0),
+            average_rate: field(1),
+            first_epoch: field(2),
+            last_epoch: field(3),
+        })
+    }
+}
+
+/// Return data of `GetExchangeRate`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExchangeRate {
+    /// Lamports per whole LST
+    pub lamports_per_lst: u64,
+    /// LST base units per SOL, 0 if the pool holds LST but no lamports
+    pub lst_per_sol: u64,
+    pub total_lamports: u64,
+    pub lst_supply: u64,
+}
+
+impl ExchangeRate {
+    pub fn from_return_data(data: &[u8]) -> Option<Self> {
+        if data.len() != 32 {
+            return None;
+        }
+
+        let field = |i: usize| u64::from_le_bytes(data[i * 8..i * 8 + 8].try_into().unwrap());
+        Some(Self {
+            lamports_per_lst: field(0),
+            lst_per_sol: field(1),
+            total_lamports: field(2),
+            lst_supply: field(3),
+        })
+    }
+}
+
+/// Addresses recorded in a pool's `PoolState`, as needed by the instruction builders.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pool {
+    pub address: Pubkey,
+    pub lst_mint: Pubkey,
+    /// SPL Token or Token-2022, whichever owns `lst_mint`
+    pub token_program: Pubkey,
+    pub manager: Pubkey,
+    pub staker: Pubkey,
+    pub validator_vote: Pubkey,
+    pub pool_stake: Pubkey,
+    pub reserve_stake: Pubkey,
+    pub manager_fee_account: Pubkey,
+    pub validator_list: Option<Pubkey>,
+    pub pending_validator_vote: Option<Pubkey>,
+    pub pending_manager: Option<Pubkey>,
+    /// Co-signs every deposit into a permissioned pool
+    pub deposit_authority: Option<Pubkey>,
+    pub rate_history: Option<Pubkey>,
+}
+
+impl Pool {
+    pub fn new(address: Pubkey, pool_state: &PoolState) -> Self {
+        Self {
+            address,
+            lst_mint: Pubkey::new_from_array(pool_state.lst_mint),
+            token_program: Pubkey::new_from_array(pool_state.token_program()),
+            manager: Pubkey::new_from_array(pool_state.manager),
+            staker: Pubkey::new_from_array(pool_state.staker),
+            validator_vote: Pubkey::new_from_array(pool_state.validator_vote),
+            pool_stake: Pubkey::new_from_array(pool_state.stake_account),
+            reserve_stake: Pubkey::new_from_array(pool_state.reserve_stake),
+            manager_fee_account: Pubkey::new_from_array(pool_state.manager_fee_account),
+            validator_list: pool_state
+                .has_validator_list()
+                .then(|| Pubkey::new_from_array(pool_state.validator_list)),
+            pending_validator_vote: pool_state
+                .is_redelegating()
+                .then(|| Pubkey::new_from_array(pool_state.pending_validator_vote)),
+            pending_manager: pool_state
+                .has_pending_manager()
+                .then(|| Pubkey::new_from_array(pool_state.pending_manager)),
+            deposit_authority: pool_state
+                .has_deposit_authority()
+                .then(|| Pubkey::new_from_array(pool_state.deposit_authority)),
+            rate_history: pool_state
+                .has_rate_history()
+                .then(|| Pubkey::new_from_array(pool_state.rate_history)),
+        }
+    }
+
+    /// `owner`'s associated LST account under the pool's token program.
+    pub fn lst_token_address(&self, owner: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(owner, &self.lst_mint, &self.token_program)
+    }
+
+    pub fn from_account_data(address: Pubkey, data: &[u8]) -> Result<Self, ProgramError> {
+        Ok(Self::new(address, &decode_pool_state(data)?))
+    }
+}
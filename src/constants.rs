@@ -8,5 +8,42 @@ pub const VOTE_PROGRAM_ID: [u8; 32] = [
     16, 67, 252, 13, 163, 83, 128, 0, 0, 0, 0,
 ];
 
+/// Stake history sysvar (`SysvarStakeHistory1111111111111111111111111`)
+pub const STAKE_HISTORY_ID: [u8; 32] = [
+    6, 167, 213, 23, 25, 53, 132, 43, 117, 36, 142, 142, 69, 167, 74, 9, 0, 69, 35, 53, 181, 203,
+    213, 234, 92, 199, 0, 0, 0, 0, 0, 0,
+];
+
+/// Token-2022 program (`TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb`)
+pub const TOKEN_2022_PROGRAM_ID: [u8; 32] = [
+    6, 221, 246, 225, 238, 117, 143, 222, 24, 66, 93, 188, 228, 108, 205, 218, 182, 26, 252, 77,
+    131, 185, 13, 39, 254, 189, 249, 40, 216, 161, 139, 252,
+];
+
+/// Metaplex Token Metadata program (`metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s`)
+pub const TOKEN_METADATA_PROGRAM_ID: [u8; 32] = [
+    11, 112, 101, 177, 227, 209, 124, 69, 56, 157, 82, 127, 107, 4, 195, 205, 88, 184, 108, 115,
+    26, 160, 253, 181, 73, 182, 209, 188, 3, 248, 41, 70,
+];
+
 pub const STAKE_ACCOUNT_SIZE: u64 = 200;
-pub const MIN_STAKE_DELEGATION: u64 = 1_000_000_000;
\ No newline at end of file
+pub const MIN_STAKE_DELEGATION: u64 = 1_000_000_000;
+pub const LST_DECIMALS: u8 = 9;
+/// Exchange rates are quoted in lamports per whole LST.
+pub const EXCHANGE_RATE_SCALE: u64 = 1_000_000_000;
+/// Basis points in 100%.
+pub const BPS_DENOMINATOR: u16 = 10_000;
+/// Each fee is capped at 10%.
+pub const MAX_FEE_BPS: u16 = 1_000;
+pub const MAX_VALIDATORS: u32 = 64;
+
+/// `PoolState::status` bits, set with `SetStatus`.
+pub const PAUSE_DEPOSITS: u8 = 1 << 0;
+pub const PAUSE_WITHDRAWALS: u8 = 1 << 1;
+pub const PAUSE_CRANKS: u8 = 1 << 2;
+pub const PAUSE_ALL: u8 = PAUSE_DEPOSITS | PAUSE_WITHDRAWALS | PAUSE_CRANKS;
+
+/// Metaplex limits for the LST name, symbol and URI, in bytes.
+pub const MAX_NAME_LEN: usize = 32;
+pub const MAX_SYMBOL_LEN: usize = 10;
+pub const MAX_URI_LEN: usize = 200;
@@ -2,32 +2,197 @@ use {pinocchio::program_error::ProgramError, thiserror::Error};
 
 #[derive(Debug, Error)]
 pub enum PinocchioError {
-    #[error("Lamport balance below rent-exempt threshold")]
-    NotRentExempt,
-    #[error("Invalid Owner")]
+    // Generic account checks
+    #[error("Account is owned by the wrong program")]
     InvalidOwner,
-    #[error("Invalid Account Data")]
+    #[error("Account data has an unexpected size")]
     InvalidAccountData,
-    #[error("Invalid Adress ")]
-    InvalidAddress,
-    #[error("Uninitialized Account")]
-    UninitializedAccount,
-    #[error("No claimable amount available")]
-    NoClaimableAmount,
-    #[error("Vesting schedule has not started yet")]
-    StartTimestampInPast,
-    #[error("Invalid ")]
-    InvalidDurations,
-    #[error("Step duration must divide total duration evenly")]
-    InvalidStepDuration,
-    #[error("Cannot add participants after cliff period has ended")]
-    CannotAddParticipantsAfterCliff,
-    #[error("Claim exceeds allocated amount")]
-    ClaimExceedsAllocation,
-    #[error("Nonexistent seed value")]
+    #[error("Wrong program account supplied")]
+    InvalidProgram,
+    #[error("Account is not a vote account")]
+    InvalidVoteAccount,
+    #[error("Token account is not the owner's associated token account")]
+    InvalidAssociatedTokenAccount,
+
+    // Pool accounts
+    #[error("Pool seed must be non-zero")]
     InvalidSeed,
-    #[error("Invalid state discriminator")]
-    InvalidDiscriminator,
+    #[error("Pool state is not initialized")]
+    PoolNotInitialized,
+    #[error("Pool state is not the expected PDA")]
+    InvalidPoolAddress,
+    #[error("Stored pool bump does not match the PDA")]
+    InvalidPoolBump,
+    #[error("Signer is not the pool authority")]
+    InvalidAuthority,
+    #[error("Pool stake account does not match the pool")]
+    InvalidPoolStake,
+    #[error("Reserve stake account does not match the pool")]
+    InvalidReserveStake,
+    #[error("LST mint does not match the pool")]
+    InvalidLstMint,
+    #[error("Manager fee account does not match the pool")]
+    InvalidManagerFeeAccount,
+    #[error("Vote account does not match the pool validator")]
+    InvalidValidatorVote,
+    #[error("Withdraw stake account is not the expected PDA")]
+    InvalidWithdrawStake,
+    #[error("Withdraw stake account already exists for this nonce")]
+    WithdrawStakeAlreadyExists,
+    #[error("Withdraw stake account does not exist")]
+    WithdrawStakeNotFound,
+
+    // Pool balance
+    #[error("Pool balance has not been updated this epoch")]
+    StalePoolBalance,
+    #[error("Pool balance already updated this epoch")]
+    PoolBalanceAlreadyUpdated,
+    #[error("Pool has no LST supply")]
+    EmptyPool,
+
+    // Amounts
+    #[error("Fee exceeds the maximum allowed basis points")]
+    InvalidFee,
+    #[error("Amount must be greater than zero")]
+    ZeroAmount,
+    #[error("Withdrawal nonce must be non-zero")]
+    InvalidNonce,
+    #[error("Deposit is below the minimum delegation or the pool's minimum deposit")]
+    BelowMinimumDeposit,
+    #[error("Withdrawal is below the minimum stake account balance")]
+    BelowMinimumWithdrawal,
+    #[error("Withdrawal would leave the pool stake below the minimum delegation")]
+    PoolStakeBelowMinimum,
+    #[error("LST balance is lower than the requested amount")]
+    InsufficientLstBalance,
+    #[error("Amount out is below the requested minimum")]
+    SlippageExceeded,
+
+    // Reserve
+    #[error("Reserve is already delegated")]
+    ReserveAlreadyDelegated,
+    #[error("Reserve holds no lamports")]
+    ReserveEmpty,
+    #[error("Reserve holds less than the minimum delegation")]
+    ReserveBelowMinimum,
+    #[error("Reserve holds no undelegated SOL")]
+    ReserveNotAvailable,
+    #[error("Reserve cannot cover the withdrawal")]
+    InsufficientReserve,
+
+    // Validators
+    #[error("Validator list does not match the pool")]
+    InvalidValidatorList,
+    #[error("Validator list account is required for this pool")]
+    MissingValidatorList,
+    #[error("Validator stake account does not match the validator list")]
+    InvalidValidatorStake,
+    #[error("Validator stake accounts do not cover the validator list")]
+    MissingValidatorStakes,
+    #[error("Validator is already in the pool")]
+    ValidatorAlreadyAdded,
+    #[error("Validator is not in the pool")]
+    ValidatorNotFound,
+    #[error("Validator list is full")]
+    ValidatorListFull,
+    #[error("The primary validator cannot be removed")]
+    CannotRemovePrimaryValidator,
+    #[error("Pool stake is being redelegated")]
+    RedelegationInProgress,
+    #[error("No redelegation is pending")]
+    NoPendingRedelegation,
+
+    // Stake accounts
+    #[error("Account is not a stake account")]
+    InvalidStakeAccount,
+    #[error("Stake account is not delegated")]
+    InvalidStakeState,
+    #[error("Stake is activating or deactivating")]
+    StakeNotActive,
+    #[error("Stake authorities do not match the expected holder")]
+    StakeAuthorityMismatch,
+    #[error("Stake is delegated to a different validator")]
+    StakeValidatorMismatch,
+    #[error("Stake account lockup is in force or has a custodian")]
+    StakeLockupInForce,
+
+    // Transient stakes
+    #[error("Transient stake account is not the expected PDA")]
+    InvalidTransientStake,
+    #[error("Reserve was already activated this epoch")]
+    TransientStakeInUse,
+    #[error("Transient stake is still activating")]
+    TransientStakeActivating,
+    #[error("Transient stakes must be merged first")]
+    TransientStakeOutstanding,
+
+    // Manager transfer
+    #[error("No manager transfer is pending")]
+    NoPendingManager,
+    #[error("Signer is not the pending manager")]
+    InvalidPendingManager,
+
+    // Pool status
+    #[error("Status has unknown bits set")]
+    InvalidStatus,
+    #[error("Deposits are paused")]
+    DepositsPaused,
+    #[error("Withdrawals are paused")]
+    WithdrawalsPaused,
+    #[error("Cranks are paused")]
+    CranksPaused,
+
+    // Pool versions
+    #[error("Pool state uses an older layout and must be migrated")]
+    PoolMigrationRequired,
+    #[error("Pool state version is not supported")]
+    UnsupportedPoolVersion,
+    #[error("Pool state already uses the current layout")]
+    PoolAlreadyMigrated,
+
+    // Token metadata
+    #[error("Token name, symbol or URI is empty, too long or not UTF-8")]
+    InvalidTokenMetadata,
+    #[error("Metadata account is not the LST mint's metadata PDA")]
+    InvalidMetadataAccount,
+
+    // Deposit limits
+    #[error("Deposit is larger than the pool's per-deposit maximum")]
+    DepositAboveMaximum,
+    #[error("Deposit would take the pool past its total value cap")]
+    PoolCapExceeded,
+    #[error("Minimum deposit is larger than the maximum deposit")]
+    InvalidDepositLimits,
+
+    // Deposit authority
+    #[error("Deposit was not signed by the pool's deposit authority")]
+    InvalidDepositAuthority,
+
+    // Withdraw tickets
+    #[error("Withdraw ticket is not the expected PDA or does not match the withdraw stake")]
+    InvalidWithdrawTicket,
+    #[error("Withdraw stake was ticketed and can only be claimed with its ticket")]
+    WithdrawTicketIssued,
+
+    // Withdraw ledgers
+    #[error("Withdraw ledger is not the expected PDA for this pool and user")]
+    InvalidWithdrawLedger,
+    #[error("Withdraw ledger has no entry for the withdraw stake")]
+    WithdrawLedgerEntryNotFound,
+    #[error("Withdraw ledger still lists outstanding withdrawals")]
+    WithdrawLedgerNotEmpty,
+
+    // Rate history
+    #[error("Rate history is not the pool's rate history account")]
+    InvalidRateHistory,
+    #[error("Pool has a rate history, which must be passed to UpdatePoolBalance")]
+    MissingRateHistory,
+    #[error("Pool already has a rate history")]
+    RateHistoryAlreadyInitialized,
+
+    // Withdraw ledger pruning
+    #[error("Withdraw stake still exists, so its ledger entry cannot be pruned")]
+    WithdrawStakeNotClaimed,
 }
 
 impl From<PinocchioError> for ProgramError {
@@ -0,0 +1,26 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{ProgramAccount, SignerAccount};
+
+pub struct AcceptManagerAccounts<'a> {
+    pub new_manager: &'a AccountInfo,
+    pub pool_state: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AcceptManagerAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [new_manager, pool_state] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(new_manager)?;
+        ProgramAccount::check(pool_state)?;
+
+        Ok(Self {
+            new_manager,
+            pool_state,
+        })
+    }
+}
@@ -0,0 +1,58 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{AcceptManagerAccounts, PinocchioError, PoolState, ProgramAccount};
+
+pub struct AcceptManager<'a> {
+    pub accounts: AcceptManagerAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AcceptManager<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = AcceptManagerAccounts::try_from(accounts)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[Seed::from(b"lst_pool"), Seed::from(&seed_bytes)],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if !pool_state.has_pending_manager() {
+            return Err(PinocchioError::NoPendingManager.into());
+        }
+
+        if accounts.new_manager.key() != &pool_state.pending_manager {
+            return Err(PinocchioError::InvalidPendingManager.into());
+        }
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> AcceptManager<'a> {
+    pub const DISCRIMINATOR: u8 = 14;
+
+    /// Completes the transfer started by `SetManager`.
+    pub fn process(&self) -> ProgramResult {
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+
+        pool_state.manager = *self.accounts.new_manager.key();
+        pool_state.pending_manager = Pubkey::default();
+
+        msg!("Manager transferred");
+        Ok(())
+    }
+}
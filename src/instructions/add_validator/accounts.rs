@@ -0,0 +1,78 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    Mint, PinocchioError, ProgramAccount, SignerAccount, Token, constants::VOTE_PROGRAM_ID,
+};
+
+pub struct AddValidatorAccounts<'a> {
+    pub staker: &'a AccountInfo,
+    pub staker_lst_account: &'a AccountInfo,
+    pub pool_state: &'a AccountInfo,
+    pub validator_list: &'a AccountInfo,
+    pub validator_stake: &'a AccountInfo,
+    pub validator_vote: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub clock: &'a AccountInfo,
+    pub rent: &'a AccountInfo,
+    pub stake_history: &'a AccountInfo,
+    pub stake_config: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AddValidatorAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [
+            staker,
+            staker_lst_account,
+            pool_state,
+            validator_list,
+            validator_stake,
+            validator_vote,
+            lst_mint,
+            clock,
+            rent,
+            stake_history,
+            stake_config,
+            system_program,
+            token_program,
+            stake_program,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(staker)?;
+        ProgramAccount::check(pool_state)?;
+        ProgramAccount::check_system_program(system_program)?;
+        ProgramAccount::check_token_program(token_program)?;
+        Mint::check(lst_mint, token_program.key())?;
+        Token::check(staker_lst_account, token_program.key())?;
+
+        ProgramAccount::check_stake_program(stake_program)?;
+
+        if validator_vote.owner() != &VOTE_PROGRAM_ID {
+            return Err(PinocchioError::InvalidVoteAccount.into());
+        }
+
+        Ok(Self {
+            staker,
+            staker_lst_account,
+            pool_state,
+            validator_list,
+            validator_stake,
+            validator_vote,
+            lst_mint,
+            clock,
+            rent,
+            stake_history,
+            stake_config,
+            system_program,
+            token_program,
+            stake_program,
+        })
+    }
+}
@@ -0,0 +1,303 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    msg,
+    program_error::ProgramError,
+    pubkey::{Pubkey, find_program_address},
+    sysvars::{Sysvar, clock::Clock, rent::Rent},
+};
+use pinocchio_system::instructions::Transfer;
+
+use crate::{
+    AddValidatorAccounts, PinocchioError, PoolState, ProgramAccount, ValidatorList,
+    ValidatorStatus, constants::*, create_stake_account, delegate_stake, initialize_stake, mint_to,
+};
+
+pub struct AddValidator<'a> {
+    pub accounts: AddValidatorAccounts<'a>,
+    pub validator_list_bump: u8,
+    pub validator_stake_bump: u8,
+    pub epoch: u64,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for AddValidator<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = AddValidatorAccounts::try_from(accounts)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[
+                Seed::from(b"lst_pool"),
+                Seed::from(&seed_bytes),
+            ],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if accounts.staker.key() != &pool_state.staker {
+            return Err(PinocchioError::InvalidAuthority.into());
+        }
+
+        if accounts.lst_mint.key() != &pool_state.lst_mint {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        if accounts.token_program.key() != &pool_state.token_program() {
+            return Err(PinocchioError::InvalidProgram.into());
+        }
+
+        if accounts.validator_vote.key() == &pool_state.validator_vote {
+            return Err(PinocchioError::ValidatorAlreadyAdded.into());
+        }
+
+        let epoch = Clock::get()?.epoch;
+        if pool_state.last_update_epoch != epoch {
+            return Err(PinocchioError::StalePoolBalance.into());
+        }
+
+        let (list_pda, validator_list_bump) = find_program_address(
+            &[b"validator_list", accounts.pool_state.key().as_ref()],
+            &crate::ID,
+        );
+        if accounts.validator_list.key() != &list_pda {
+            return Err(PinocchioError::InvalidValidatorList.into());
+        }
+
+        if pool_state.has_validator_list() {
+            if !accounts.validator_list.is_owned_by(&crate::ID) {
+                return Err(PinocchioError::InvalidOwner.into());
+            }
+
+            let list_data = accounts.validator_list.try_borrow_data()?;
+            let (list, entries) = ValidatorList::load(&list_data)?;
+
+            if list.count >= MAX_VALIDATORS {
+                return Err(PinocchioError::ValidatorListFull.into());
+            }
+
+            if entries
+                .iter()
+                .any(|entry| &entry.vote_account == accounts.validator_vote.key())
+            {
+                return Err(PinocchioError::ValidatorAlreadyAdded.into());
+            }
+        }
+
+        let (stake_pda, validator_stake_bump) = find_program_address(
+            &[
+                b"validator_stake",
+                accounts.pool_state.key().as_ref(),
+                accounts.validator_vote.key().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if accounts.validator_stake.key() != &stake_pda {
+            return Err(PinocchioError::InvalidValidatorStake.into());
+        }
+
+        Ok(Self {
+            accounts,
+            validator_list_bump,
+            validator_stake_bump,
+            epoch,
+        })
+    }
+}
+
+impl<'a> AddValidator<'a> {
+    pub const DISCRIMINATOR: u8 = 7;
+
+    pub fn process(&self) -> ProgramResult {
+        let (seed, bump, primary_vote, primary_stake, primary_stake_bump, has_list) = {
+            let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
+            let pool_state = PoolState::load(&pool_state_data)?;
+            (
+                pool_state.seed,
+                pool_state.bump,
+                pool_state.validator_vote,
+                pool_state.stake_account,
+                pool_state.stake_bump,
+                pool_state.has_validator_list(),
+            )
+        };
+
+        let seed_bytes = seed.to_le_bytes();
+        let bump_binding = [bump];
+        let pool_seeds = [
+            Seed::from(b"lst_pool"),
+            Seed::from(&seed_bytes),
+            Seed::from(&bump_binding),
+        ];
+
+        let validator_stake_bump = [self.validator_stake_bump];
+        let validator_stake_seeds = [
+            Seed::from(b"validator_stake"),
+            Seed::from(self.accounts.pool_state.key().as_ref()),
+            Seed::from(self.accounts.validator_vote.key().as_ref()),
+            Seed::from(&validator_stake_bump),
+        ];
+
+        create_stake_account(
+            self.accounts.staker,
+            self.accounts.validator_stake,
+            MIN_STAKE_DELEGATION,
+            &validator_stake_seeds,
+        )?;
+
+        initialize_stake(
+            self.accounts.validator_stake,
+            self.accounts.rent,
+            self.accounts.pool_state,
+            self.accounts.pool_state,
+        )?;
+
+        delegate_stake(
+            self.accounts.validator_stake,
+            self.accounts.validator_vote,
+            self.accounts.clock,
+            self.accounts.stake_history,
+            self.accounts.stake_config,
+            self.accounts.pool_state,
+            &pool_seeds,
+        )?;
+
+        if has_list {
+            self.grow_validator_list()?;
+        } else {
+            self.create_validator_list(primary_vote, primary_stake, primary_stake_bump)?;
+        }
+
+        let added_lamports = self.accounts.validator_stake.lamports();
+
+        {
+            let mut list_data = self.accounts.validator_list.try_borrow_mut_data()?;
+            let (_, entries) = ValidatorList::load_mut(&mut list_data)?;
+            entries[entries.len() - 1].set_inner(
+                *self.accounts.validator_vote.key(),
+                *self.accounts.validator_stake.key(),
+                added_lamports,
+                self.epoch,
+                ValidatorStatus::Active,
+                self.validator_stake_bump,
+            );
+        }
+
+        // The staker funds the new stake, so it is paid in LST at the current rate
+        let lst_amount = {
+            let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
+            let pool_state = PoolState::load(&pool_state_data)?;
+
+            (added_lamports as u128)
+                .checked_mul(pool_state.lst_supply as u128)
+                .ok_or(ProgramError::ArithmeticOverflow)?
+                .checked_div(pool_state.total_lamports as u128)
+                .ok_or(ProgramError::ArithmeticOverflow)? as u64
+        };
+
+        mint_to(
+            self.accounts.lst_mint,
+            self.accounts.staker_lst_account,
+            self.accounts.pool_state,
+            self.accounts.token_program,
+            lst_amount,
+            &pool_seeds,
+        )?;
+
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+
+        pool_state.lst_supply = pool_state
+            .lst_supply
+            .checked_add(lst_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        pool_state.total_lamports = pool_state
+            .total_lamports
+            .checked_add(added_lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        pool_state.validator_list = *self.accounts.validator_list.key();
+        pool_state.validator_list_bump = self.validator_list_bump;
+
+        msg!("Validator added");
+        Ok(())
+    }
+
+    /// Creates the list with the pool's primary validator as entry 0 and an
+    /// empty slot for the validator being added.
+    fn create_validator_list(
+        &self,
+        primary_vote: Pubkey,
+        primary_stake: Pubkey,
+        primary_stake_bump: u8,
+    ) -> ProgramResult {
+        let list_bump = [self.validator_list_bump];
+        let list_seeds = [
+            Seed::from(b"validator_list"),
+            Seed::from(self.accounts.pool_state.key().as_ref()),
+            Seed::from(&list_bump),
+        ];
+
+        ProgramAccount::init::<ValidatorList>(
+            self.accounts.staker,
+            self.accounts.validator_list,
+            &list_seeds,
+            ValidatorList::space(2),
+        )?;
+
+        let mut list_data = self.accounts.validator_list.try_borrow_mut_data()?;
+        let header = ValidatorList::load_header_mut(&mut list_data)?;
+        header.set_inner(self.validator_list_bump, 2, *self.accounts.pool_state.key());
+
+        let (_, entries) = ValidatorList::load_mut(&mut list_data)?;
+        entries[0].set_inner(
+            primary_vote,
+            primary_stake,
+            0,
+            0,
+            ValidatorStatus::Active,
+            primary_stake_bump,
+        );
+
+        msg!("Validator list created");
+        Ok(())
+    }
+
+    /// Appends an empty slot, topping up rent from the staker.
+    fn grow_validator_list(&self) -> ProgramResult {
+        let count = {
+            let list_data = self.accounts.validator_list.try_borrow_data()?;
+            ValidatorList::load(&list_data)?.0.count
+        };
+
+        let new_space = ValidatorList::space(count as usize + 1);
+        let rent_due = Rent::get()?
+            .minimum_balance(new_space)
+            .saturating_sub(self.accounts.validator_list.lamports());
+
+        if rent_due > 0 {
+            Transfer {
+                from: self.accounts.staker,
+                to: self.accounts.validator_list,
+                lamports: rent_due,
+            }
+            .invoke()?;
+        }
+
+        self.accounts.validator_list.resize(new_space)?;
+
+        let mut list_data = self.accounts.validator_list.try_borrow_mut_data()?;
+        let header = ValidatorList::load_header_mut(&mut list_data)?;
+        header.count = count + 1;
+
+        Ok(())
+    }
+}
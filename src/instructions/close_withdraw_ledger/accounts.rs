@@ -0,0 +1,27 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{PinocchioError, SignerAccount, WithdrawLedger};
+
+pub struct CloseWithdrawLedgerAccounts<'a> {
+    /// Owner of the ledger; receives its rent.
+    pub owner: &'a AccountInfo,
+    pub ledger: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseWithdrawLedgerAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [owner, ledger] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(owner)?;
+
+        if !WithdrawLedger::is_ledger(ledger) {
+            return Err(PinocchioError::InvalidWithdrawLedger.into());
+        }
+
+        Ok(Self { owner, ledger })
+    }
+}
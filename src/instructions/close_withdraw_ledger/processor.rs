@@ -0,0 +1,44 @@
+use pinocchio::{ProgramResult, account_info::AccountInfo, msg, program_error::ProgramError};
+
+use crate::{CloseWithdrawLedgerAccounts, PinocchioError, WithdrawLedger};
+
+pub struct CloseWithdrawLedger<'a> {
+    pub accounts: CloseWithdrawLedgerAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CloseWithdrawLedger<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = CloseWithdrawLedgerAccounts::try_from(accounts)?;
+
+        let ledger_data = accounts.ledger.try_borrow_data()?;
+        let (ledger, _) = WithdrawLedger::load(&ledger_data)?;
+
+        if accounts.owner.key() != &ledger.owner {
+            return Err(PinocchioError::InvalidAuthority.into());
+        }
+
+        if ledger.count != 0 {
+            return Err(PinocchioError::WithdrawLedgerNotEmpty.into());
+        }
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> CloseWithdrawLedger<'a> {
+    pub const DISCRIMINATOR: u8 = 23;
+
+    /// Returns the rent of an empty ledger to its owner. A new ledger can be
+    /// created later; its nonces start over at 1.
+    pub fn process(&self) -> ProgramResult {
+        let ledger_lamports = self.accounts.ledger.lamports();
+        *self.accounts.owner.try_borrow_mut_lamports()? += ledger_lamports;
+        *self.accounts.ledger.try_borrow_mut_lamports()? = 0;
+        self.accounts.ledger.close()?;
+
+        msg!("Withdraw ledger closed");
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{PinocchioError, ProgramAccount, constants::VOTE_PROGRAM_ID};
+
+pub struct CompleteRedelegateAccounts<'a> {
+    pub pool_state: &'a AccountInfo,
+    pub pool_stake: &'a AccountInfo,
+    /// Takes lamports donated to the pool stake before it is delegated again
+    pub reserve_stake: &'a AccountInfo,
+    pub new_validator_vote: &'a AccountInfo,
+    pub clock: &'a AccountInfo,
+    pub stake_history: &'a AccountInfo,
+    pub stake_config: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+    /// Required once the pool has a validator list.
+    pub validator_list: Option<&'a AccountInfo>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CompleteRedelegateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [
+            pool_state,
+            pool_stake,
+            reserve_stake,
+            new_validator_vote,
+            clock,
+            stake_history,
+            stake_config,
+            stake_program,
+            remaining @ ..,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        ProgramAccount::check(pool_state)?;
+
+        ProgramAccount::check_stake_program(stake_program)?;
+
+        if new_validator_vote.owner() != &VOTE_PROGRAM_ID {
+            return Err(PinocchioError::InvalidVoteAccount.into());
+        }
+
+        Ok(Self {
+            pool_state,
+            pool_stake,
+            reserve_stake,
+            new_validator_vote,
+            clock,
+            stake_history,
+            stake_config,
+            stake_program,
+            validator_list: remaining.first(),
+        })
+    }
+}
@@ -0,0 +1,138 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    CompleteRedelegateAccounts, PinocchioError, PoolState, ProgramAccount, ValidatorList,
+    delegate_stake, stake_value, withdraw_stake,
+};
+
+pub struct CompleteRedelegate<'a> {
+    pub accounts: CompleteRedelegateAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for CompleteRedelegate<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = CompleteRedelegateAccounts::try_from(accounts)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        if pool_state.cranks_paused() {
+            return Err(PinocchioError::CranksPaused.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[
+                Seed::from(b"lst_pool"),
+                Seed::from(&seed_bytes),
+            ],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if accounts.pool_stake.key() != &pool_state.stake_account {
+            return Err(PinocchioError::InvalidPoolStake.into());
+        }
+
+        if accounts.reserve_stake.key() != &pool_state.reserve_stake {
+            return Err(PinocchioError::InvalidReserveStake.into());
+        }
+
+        if !pool_state.is_redelegating() {
+            return Err(PinocchioError::NoPendingRedelegation.into());
+        }
+
+        if accounts.new_validator_vote.key() != &pool_state.pending_validator_vote {
+            return Err(PinocchioError::InvalidValidatorVote.into());
+        }
+
+        if pool_state.has_validator_list() {
+            let validator_list = accounts
+                .validator_list
+                .ok_or(PinocchioError::MissingValidatorList)?;
+
+            if validator_list.key() != &pool_state.validator_list {
+                return Err(PinocchioError::InvalidValidatorList.into());
+            }
+        }
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> CompleteRedelegate<'a> {
+    pub const DISCRIMINATOR: u8 = 10;
+
+    /// Permissionless crank. Fails in the stake program until the pool stake
+    /// has fully deactivated.
+    pub fn process(&self) -> ProgramResult {
+        let has_validator_list = {
+            let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
+            let pool_state = PoolState::load(&pool_state_data)?;
+
+            let seed_bytes = pool_state.seed.to_le_bytes();
+            let bump_binding = [pool_state.bump];
+            let pool_seeds = [
+                Seed::from(b"lst_pool"),
+                Seed::from(&seed_bytes),
+                Seed::from(&bump_binding),
+            ];
+
+            // Delegating stakes the whole balance, so lamports donated to the
+            // pool stake go to the reserve first, where they stay uncounted
+            let donated = self
+                .accounts
+                .pool_stake
+                .lamports()
+                .saturating_sub(stake_value(self.accounts.pool_stake)?);
+
+            if donated > 0 {
+                withdraw_stake(
+                    self.accounts.pool_stake,
+                    self.accounts.reserve_stake,
+                    self.accounts.pool_state,
+                    self.accounts.clock,
+                    self.accounts.stake_history,
+                    &pool_seeds,
+                    donated,
+                )?;
+            }
+
+            delegate_stake(
+                self.accounts.pool_stake,
+                self.accounts.new_validator_vote,
+                self.accounts.clock,
+                self.accounts.stake_history,
+                self.accounts.stake_config,
+                self.accounts.pool_state,
+                &pool_seeds,
+            )?;
+
+            pool_state.has_validator_list()
+        };
+
+        // Entry 0 of the validator list tracks the pool stake
+        if let Some(validator_list) = self.accounts.validator_list.filter(|_| has_validator_list) {
+            let mut list_data = validator_list.try_borrow_mut_data()?;
+            let (_, entries) = ValidatorList::load_mut(&mut list_data)?;
+            entries[0].vote_account = *self.accounts.new_validator_vote.key();
+        }
+
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+        pool_state.validator_vote = pool_state.pending_validator_vote;
+        pool_state.pending_validator_vote = Pubkey::default();
+
+        msg!("Redelegation completed");
+        Ok(())
+    }
+}
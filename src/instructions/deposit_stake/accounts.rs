@@ -1,6 +1,6 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
 
-use crate::{AssociatedToken, Mint, ProgramAccount, STAKE_PROGRAM_ID, SignerAccount};
+use crate::{AssociatedToken, Mint, ProgramAccount, SignerAccount, Token};
 
 pub struct DepositAccounts<'a> {
     pub depositor: &'a AccountInfo,
@@ -9,10 +9,13 @@ pub struct DepositAccounts<'a> {
     pub reserve_stake: &'a AccountInfo,
     pub lst_mint: &'a AccountInfo,
     pub depositor_lst_ata: &'a AccountInfo,
+    pub manager_fee_account: &'a AccountInfo,
     /// Programs
     pub system_program: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
     pub stake_program: &'a AccountInfo,
+    /// Trailing signer, required when the pool has a deposit authority
+    pub deposit_authority: Option<&'a AccountInfo>,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for DepositAccounts<'a> {
@@ -26,9 +29,11 @@ impl<'a> TryFrom<&'a [AccountInfo]> for DepositAccounts<'a> {
             reserve_stake,
             lst_mint,
             depositor_lst_ata,
+            manager_fee_account,
             system_program,
             token_program,
             stake_program,
+            remaining @ ..
         ] = account_infos
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
@@ -38,7 +43,8 @@ impl<'a> TryFrom<&'a [AccountInfo]> for DepositAccounts<'a> {
         ProgramAccount::check_system_program(system_program)?;
         ProgramAccount::check_token_program(token_program)?;
         ProgramAccount::check(pool_state)?;
-        Mint::check(lst_mint)?;
+        Mint::check(lst_mint, token_program.key())?;
+        Token::check(manager_fee_account, token_program.key())?;
 
         AssociatedToken::check(
             depositor_lst_ata,
@@ -47,9 +53,7 @@ impl<'a> TryFrom<&'a [AccountInfo]> for DepositAccounts<'a> {
             *token_program.key(),
         )?;
 
-        if stake_program.key() != &STAKE_PROGRAM_ID {
-            return Err(ProgramError::IncorrectProgramId);
-        }
+        ProgramAccount::check_stake_program(stake_program)?;
 
         Ok(Self {
             depositor,
@@ -58,9 +62,11 @@ impl<'a> TryFrom<&'a [AccountInfo]> for DepositAccounts<'a> {
             reserve_stake,
             lst_mint,
             depositor_lst_ata,
+            manager_fee_account,
             system_program,
             token_program,
             stake_program,
+            deposit_authority: remaining.first(),
         })
     }
 }
@@ -1,26 +1,33 @@
 use pinocchio::program_error::ProgramError;
 
-use crate::{MIN_STAKE_DELEGATION, STAKE_ACCOUNT_SIZE};
+use crate::{MIN_STAKE_DELEGATION, PinocchioError, STAKE_ACCOUNT_SIZE};
 
 #[repr(C, packed)]
 pub struct DepositInstructionData {
     pub amount: u64,
+    /// Least LST the depositor accepts after fees; 0 when omitted
+    pub minimum_lst_out: u64,
 }
 
 impl<'a> TryFrom<&'a [u8]> for DepositInstructionData {
     type Error = ProgramError;
 
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() != size_of::<u64>() {
-            return Err(ProgramError::InvalidInstructionData);
-        }
+        let minimum_lst_out = match data.len() {
+            8 => 0,
+            16 => u64::from_le_bytes(data[8..16].try_into().unwrap()),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
 
         let amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
 
         if amount < (MIN_STAKE_DELEGATION + STAKE_ACCOUNT_SIZE) {
-            return Err(ProgramError::InvalidInstructionData);
+            return Err(PinocchioError::BelowMinimumDeposit.into());
         }
 
-        Ok(Self { amount })
+        Ok(Self {
+            amount,
+            minimum_lst_out,
+        })
     }
 }
@@ -1,10 +1,16 @@
 use pinocchio::{
-    ProgramResult, account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
 };
 use pinocchio_system::instructions::Transfer;
-use pinocchio_token::instructions::MintTo;
 
-use crate::{DepositAccounts, DepositInstructionData, PoolState, ProgramAccount};
+use crate::{
+    DepositAccounts, DepositInstructionData, PinocchioError, PoolState, ProgramAccount,
+    calculate_fee, mint_to,
+};
 
 pub struct Deposit<'a> {
     pub accounts: DepositAccounts<'a>,
@@ -32,19 +38,38 @@ impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for Deposit<'a> {
         )?;
 
         if pool_state.discriminator == 0 {
-            return Err(ProgramError::UninitializedAccount);
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        if pool_state.deposits_paused() {
+            return Err(PinocchioError::DepositsPaused.into());
         }
 
+        pool_state.check_deposit_limits(instruction_data.amount)?;
+        pool_state.check_deposit_authority(accounts.deposit_authority)?;
+
         if accounts.pool_stake.key() != &pool_state.stake_account {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(PinocchioError::InvalidPoolStake.into());
         }
 
         if accounts.reserve_stake.key() != &pool_state.reserve_stake {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(PinocchioError::InvalidReserveStake.into());
         }
 
         if accounts.lst_mint.key() != &pool_state.lst_mint {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        if accounts.token_program.key() != &pool_state.token_program() {
+            return Err(PinocchioError::InvalidProgram.into());
+        }
+
+        if accounts.manager_fee_account.key() != &pool_state.manager_fee_account {
+            return Err(PinocchioError::InvalidManagerFeeAccount.into());
+        }
+
+        if pool_state.last_update_epoch != Clock::get()?.epoch {
+            return Err(PinocchioError::StalePoolBalance.into());
         }
 
         Ok(Self {
@@ -62,7 +87,12 @@ impl<'a> Deposit<'a> {
             let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
             let pool_state = PoolState::load(&pool_state_data)?;
 
-            let lst_amount = self.calculate_lst_amount(&pool_state)?;
+            let lst_amount = self.calculate_lst_amount(pool_state)?;
+            let fee_amount = calculate_fee(lst_amount, pool_state.deposit_fee_bps)?;
+
+            if lst_amount - fee_amount < self.instruction_data.minimum_lst_out {
+                return Err(PinocchioError::SlippageExceeded.into());
+            }
 
             let seed_binding = pool_state.seed.to_le_bytes();
             let bump_binding = [pool_state.bump];
@@ -72,8 +102,6 @@ impl<'a> Deposit<'a> {
                 Seed::from(&bump_binding),
             ];
 
-            let _ = pool_state;
-
             Transfer {
                 from: self.accounts.depositor,
                 to: self.accounts.reserve_stake,
@@ -81,7 +109,15 @@ impl<'a> Deposit<'a> {
             }
             .invoke()?;
 
-            self.mint_lst(lst_amount, &pool_seeds)?;
+            self.mint_lst(
+                self.accounts.depositor_lst_ata,
+                lst_amount - fee_amount,
+                &pool_seeds,
+            )?;
+
+            if fee_amount > 0 {
+                self.mint_lst(self.accounts.manager_fee_account, fee_amount, &pool_seeds)?;
+            }
 
             lst_amount
         };
@@ -92,17 +128,17 @@ impl<'a> Deposit<'a> {
             .lst_supply
             .checked_add(lst_amount)
             .ok_or(ProgramError::ArithmeticOverflow)?;
+        pool.total_lamports = pool
+            .total_lamports
+            .checked_add(self.instruction_data.amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        pool.credit_reserve(self.instruction_data.amount)?;
 
         Ok(())
     }
 
     fn calculate_lst_amount(&self, pool: &PoolState) -> Result<u64, ProgramError> {
-        let pool_stake_sol = self.accounts.pool_stake.lamports();
-        let reserve_stake_sol = self.accounts.reserve_stake.lamports();
-
-        let total_pool_value = pool_stake_sol
-            .checked_add(reserve_stake_sol)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let total_pool_value = pool.total_lamports;
 
         if pool.lst_supply == 0 {
             Ok(self.instruction_data.amount)
@@ -117,17 +153,14 @@ impl<'a> Deposit<'a> {
         }
     }
 
-    fn mint_lst(&self, amount: u64, pool_seeds: &[Seed]) -> ProgramResult {
-        let signer = [pinocchio::instruction::Signer::from(pool_seeds)];
-
-        MintTo {
-            mint: self.accounts.lst_mint,
-            account: self.accounts.depositor_lst_ata,
-            mint_authority: self.accounts.pool_state,
+    fn mint_lst(&self, account: &AccountInfo, amount: u64, pool_seeds: &[Seed]) -> ProgramResult {
+        mint_to(
+            self.accounts.lst_mint,
+            account,
+            self.accounts.pool_state,
+            self.accounts.token_program,
             amount,
-        }
-        .invoke_signed(&signer)?;
-
-        Ok(())
+            pool_seeds,
+        )
     }
 }
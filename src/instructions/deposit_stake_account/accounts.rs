@@ -0,0 +1,88 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{
+    AssociatedToken, Mint, PinocchioError, ProgramAccount, STAKE_ACCOUNT_SIZE, STAKE_PROGRAM_ID,
+    SignerAccount, Token,
+};
+
+pub struct DepositStakeAccounts<'a> {
+    pub depositor: &'a AccountInfo,
+    pub pool_state: &'a AccountInfo,
+    pub pool_stake: &'a AccountInfo,
+    /// Takes the deposited account's rent reserve, which the merge leaves
+    /// undelegated
+    pub reserve_stake: &'a AccountInfo,
+    pub deposit_stake: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub depositor_lst_ata: &'a AccountInfo,
+    pub manager_fee_account: &'a AccountInfo,
+    pub clock: &'a AccountInfo,
+    pub stake_history: &'a AccountInfo,
+    /// Programs
+    pub token_program: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+    /// Trailing signer, required when the pool has a deposit authority
+    pub deposit_authority: Option<&'a AccountInfo>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for DepositStakeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(account_infos: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [
+            depositor,
+            pool_state,
+            pool_stake,
+            reserve_stake,
+            deposit_stake,
+            lst_mint,
+            depositor_lst_ata,
+            manager_fee_account,
+            clock,
+            stake_history,
+            token_program,
+            stake_program,
+            remaining @ ..
+        ] = account_infos
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(depositor)?;
+        ProgramAccount::check_token_program(token_program)?;
+        ProgramAccount::check(pool_state)?;
+        Mint::check(lst_mint, token_program.key())?;
+        Token::check(manager_fee_account, token_program.key())?;
+
+        AssociatedToken::check(
+            depositor_lst_ata,
+            *depositor.key(),
+            *lst_mint.key(),
+            *token_program.key(),
+        )?;
+
+        ProgramAccount::check_stake_program(stake_program)?;
+
+        if !deposit_stake.is_owned_by(&STAKE_PROGRAM_ID)
+            || deposit_stake.data_len() != STAKE_ACCOUNT_SIZE as usize
+        {
+            return Err(PinocchioError::InvalidStakeAccount.into());
+        }
+
+        Ok(Self {
+            depositor,
+            pool_state,
+            pool_stake,
+            reserve_stake,
+            deposit_stake,
+            lst_mint,
+            depositor_lst_ata,
+            manager_fee_account,
+            clock,
+            stake_history,
+            token_program,
+            stake_program,
+            deposit_authority: remaining.first(),
+        })
+    }
+}
@@ -0,0 +1,21 @@
+use pinocchio::program_error::ProgramError;
+
+#[repr(C, packed)]
+pub struct DepositStakeInstructionData {
+    /// Least LST the depositor accepts after fees; 0 when omitted
+    pub minimum_lst_out: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for DepositStakeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let minimum_lst_out = match data.len() {
+            0 => 0,
+            8 => u64::from_le_bytes(data[0..8].try_into().unwrap()),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        Ok(Self { minimum_lst_out })
+    }
+}
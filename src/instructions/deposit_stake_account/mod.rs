@@ -0,0 +1,7 @@
+mod accounts;
+mod data;
+mod processor;
+
+pub use accounts::*;
+pub use data::*;
+pub use processor::*;
@@ -0,0 +1,237 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    msg,
+    program_error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{
+    DepositStakeAccounts, DepositStakeInstructionData, PinocchioError, PoolState, ProgramAccount,
+    StakeHistory, StakeStateV2, authorize_stake, calculate_fee, check_pool_stake, check_stake_meta,
+    merge_stake, mint_to, withdraw_stake,
+};
+
+pub struct DepositStake<'a> {
+    pub accounts: DepositStakeAccounts<'a>,
+    pub instruction_data: DepositStakeInstructionData,
+    /// Active stake delegated by the deposited account
+    pub stake_amount: u64,
+}
+
+impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for DepositStake<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&[u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = DepositStakeAccounts::try_from(accounts)?;
+        let instruction_data = DepositStakeInstructionData::try_from(data)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        if pool_state.deposits_paused() {
+            return Err(PinocchioError::DepositsPaused.into());
+        }
+
+        pool_state.check_deposit_limits(accounts.deposit_stake.lamports())?;
+        pool_state.check_deposit_authority(accounts.deposit_authority)?;
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[Seed::from(b"lst_pool"), Seed::from(&seed_bytes)],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if accounts.pool_stake.key() != &pool_state.stake_account {
+            return Err(PinocchioError::InvalidPoolStake.into());
+        }
+
+        if accounts.reserve_stake.key() != &pool_state.reserve_stake {
+            return Err(PinocchioError::InvalidReserveStake.into());
+        }
+
+        let clock = Clock::get()?;
+        check_pool_stake(
+            accounts.pool_stake,
+            accounts.pool_state.key(),
+            &pool_state.validator_vote,
+            &clock,
+        )?;
+
+        if accounts.lst_mint.key() != &pool_state.lst_mint {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        if accounts.token_program.key() != &pool_state.token_program() {
+            return Err(PinocchioError::InvalidProgram.into());
+        }
+
+        if accounts.manager_fee_account.key() != &pool_state.manager_fee_account {
+            return Err(PinocchioError::InvalidManagerFeeAccount.into());
+        }
+
+        if pool_state.is_redelegating() {
+            return Err(PinocchioError::RedelegationInProgress.into());
+        }
+
+        if pool_state.last_update_epoch != clock.epoch {
+            return Err(PinocchioError::StalePoolBalance.into());
+        }
+
+        let stake_data = accounts.deposit_stake.try_borrow_data()?;
+        let stake_state = StakeStateV2::load(&stake_data)?;
+        let (Some(meta), Some(delegation)) = (stake_state.meta(), stake_state.delegation()) else {
+            return Err(PinocchioError::InvalidStakeState.into());
+        };
+
+        // The depositor must hold both authorities to hand them over
+        check_stake_meta(
+            meta,
+            accounts.depositor.key(),
+            accounts.depositor.key(),
+            &clock,
+        )?;
+
+        if delegation.voter_pubkey != pool_state.validator_vote {
+            return Err(PinocchioError::StakeValidatorMismatch.into());
+        }
+
+        // Only fully active stake merges into the pool stake
+        let history_data = accounts.stake_history.try_borrow_data()?;
+        let history = StakeHistory::from_account(accounts.stake_history, &history_data)?;
+        let stake_amount = delegation.stake();
+        let activation = delegation.activation(clock.epoch, &history);
+
+        if delegation.is_deactivating()
+            || !activation.is_settled()
+            || activation.effective != stake_amount
+        {
+            return Err(PinocchioError::StakeNotActive.into());
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            stake_amount,
+        })
+    }
+}
+
+impl<'a> DepositStake<'a> {
+    pub const DISCRIMINATOR: u8 = 11;
+
+    pub fn process(&self) -> ProgramResult {
+        let deposit_lamports = self.accounts.deposit_stake.lamports();
+
+        let lst_amount = {
+            let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
+            let pool_state = PoolState::load(&pool_state_data)?;
+
+            let lst_amount = self.calculate_lst_amount(pool_state)?;
+            let fee_amount = calculate_fee(lst_amount, pool_state.deposit_fee_bps)?;
+
+            if lst_amount - fee_amount < self.instruction_data.minimum_lst_out {
+                return Err(PinocchioError::SlippageExceeded.into());
+            }
+
+            let seed_binding = pool_state.seed.to_le_bytes();
+            let bump_binding = [pool_state.bump];
+            let pool_seeds = [
+                Seed::from(b"lst_pool"),
+                Seed::from(&seed_binding),
+                Seed::from(&bump_binding),
+            ];
+
+            // Staker first: changing the withdrawer needs the current withdrawer's signature
+            for stake_authorize in [0u32, 1u32] {
+                authorize_stake(
+                    self.accounts.deposit_stake,
+                    self.accounts.clock,
+                    self.accounts.depositor,
+                    self.accounts.pool_state.key(),
+                    stake_authorize,
+                )?;
+            }
+
+            merge_stake(
+                self.accounts.pool_stake,
+                self.accounts.deposit_stake,
+                self.accounts.clock,
+                self.accounts.stake_history,
+                self.accounts.pool_state,
+                &pool_seeds,
+            )?;
+
+            // The merge only adds the delegation to the pool stake; the rest
+            // of the account would sit there uncounted
+            withdraw_stake(
+                self.accounts.pool_stake,
+                self.accounts.reserve_stake,
+                self.accounts.pool_state,
+                self.accounts.clock,
+                self.accounts.stake_history,
+                &pool_seeds,
+                deposit_lamports - self.stake_amount,
+            )?;
+
+            self.mint_lst(
+                self.accounts.depositor_lst_ata,
+                lst_amount - fee_amount,
+                &pool_seeds,
+            )?;
+
+            if fee_amount > 0 {
+                self.mint_lst(self.accounts.manager_fee_account, fee_amount, &pool_seeds)?;
+            }
+
+            lst_amount
+        };
+
+        let mut pool_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool = PoolState::load_mut(&mut pool_data)?;
+        pool.lst_supply = pool
+            .lst_supply
+            .checked_add(lst_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        pool.total_lamports = pool
+            .total_lamports
+            .checked_add(deposit_lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        pool.credit_reserve(deposit_lamports - self.stake_amount)?;
+
+        msg!("Stake deposited");
+        Ok(())
+    }
+
+    /// LST for the active stake only; the account's rent reserve goes to the pool.
+    fn calculate_lst_amount(&self, pool: &PoolState) -> Result<u64, ProgramError> {
+        if pool.lst_supply == 0 {
+            return Ok(self.stake_amount);
+        }
+
+        let lst_amount = (self.stake_amount as u128)
+            .checked_mul(pool.lst_supply as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_div(pool.total_lamports as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(lst_amount as u64)
+    }
+
+    fn mint_lst(&self, account: &AccountInfo, amount: u64, pool_seeds: &[Seed]) -> ProgramResult {
+        mint_to(
+            self.accounts.lst_mint,
+            account,
+            self.accounts.pool_state,
+            self.accounts.token_program,
+            amount,
+            pool_seeds,
+        )
+    }
+}
@@ -0,0 +1,29 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{PinocchioError, ProgramAccount, RateHistory};
+
+pub struct GetAverageRateAccounts<'a> {
+    pub pool_state: &'a AccountInfo,
+    pub rate_history: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetAverageRateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [pool_state, rate_history] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        ProgramAccount::check(pool_state)?;
+
+        if !RateHistory::is_history(rate_history) {
+            return Err(PinocchioError::InvalidRateHistory.into());
+        }
+
+        Ok(Self {
+            pool_state,
+            rate_history,
+        })
+    }
+}
@@ -0,0 +1,25 @@
+use pinocchio::program_error::ProgramError;
+
+#[repr(C, packed)]
+pub struct GetAverageRateInstructionData {
+    /// Averaging window, ending at the newest entry; at least 1
+    pub epochs: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for GetAverageRateInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<GetAverageRateInstructionData>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let epochs = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+        if epochs == 0 {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        Ok(Self { epochs })
+    }
+}
@@ -0,0 +1,94 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, cpi::set_return_data, instruction::Seed,
+    program_error::ProgramError,
+};
+
+use crate::{
+    GetAverageRateAccounts, GetAverageRateInstructionData, PinocchioError, PoolState,
+    ProgramAccount, RateHistory, calculate_exchange_rate,
+};
+
+pub struct GetAverageRate<'a> {
+    pub accounts: GetAverageRateAccounts<'a>,
+    pub instruction_data: GetAverageRateInstructionData,
+}
+
+impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for GetAverageRate<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&[u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = GetAverageRateAccounts::try_from(accounts)?;
+        let instruction_data = GetAverageRateInstructionData::try_from(data)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[Seed::from(b"lst_pool"), Seed::from(&seed_bytes)],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if accounts.rate_history.key() != &pool_state.rate_history {
+            return Err(PinocchioError::InvalidRateHistory.into());
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> GetAverageRate<'a> {
+    pub const DISCRIMINATOR: u8 = 25;
+
+    /// Sets the return data to `current_rate | average_rate | first_epoch |
+    /// last_epoch`, little-endian u64s. Rates are lamports per whole LST; the
+    /// average is taken over the entries of the last `epochs` epochs up to
+    /// the newest one, which `first_epoch` and `last_epoch` bound.
+    pub fn process(&self) -> ProgramResult {
+        let current_rate = {
+            let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
+            let pool_state = PoolState::load(&pool_state_data)?;
+            calculate_exchange_rate(pool_state.total_lamports, pool_state.lst_supply)?
+        };
+
+        let history_data = self.accounts.rate_history.try_borrow_data()?;
+        let history = RateHistory::load(&history_data)?;
+
+        // Created with an entry, so never empty
+        let last_epoch = history
+            .latest()
+            .ok_or(ProgramError::InvalidAccountData)?
+            .epoch;
+        let window_start = last_epoch.saturating_sub(self.instruction_data.epochs - 1);
+
+        let mut rate_sum: u128 = 0;
+        let mut samples: u128 = 0;
+        let mut first_epoch = last_epoch;
+        for entry in history
+            .iter()
+            .take_while(|entry| entry.epoch >= window_start)
+        {
+            rate_sum += entry.rate()? as u128;
+            samples += 1;
+            first_epoch = entry.epoch;
+        }
+        let average_rate = (rate_sum / samples) as u64;
+
+        let mut return_data = [0u8; 32];
+        return_data[0..8].copy_from_slice(&current_rate.to_le_bytes());
+        return_data[8..16].copy_from_slice(&average_rate.to_le_bytes());
+        return_data[16..24].copy_from_slice(&first_epoch.to_le_bytes());
+        return_data[24..32].copy_from_slice(&last_epoch.to_le_bytes());
+        set_return_data(&return_data);
+
+        Ok(())
+    }
+}
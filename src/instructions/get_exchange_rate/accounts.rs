@@ -0,0 +1,29 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::ProgramAccount;
+
+pub struct GetExchangeRateAccounts<'a> {
+    pub pool_state: &'a AccountInfo,
+    pub pool_stake: &'a AccountInfo,
+    pub reserve_stake: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetExchangeRateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [pool_state, pool_stake, reserve_stake, lst_mint] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        ProgramAccount::check(pool_state)?;
+
+        Ok(Self {
+            pool_state,
+            pool_stake,
+            reserve_stake,
+            lst_mint,
+        })
+    }
+}
@@ -0,0 +1,88 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    cpi::set_return_data,
+    instruction::Seed,
+    program_error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{
+    GetExchangeRateAccounts, Mint, PinocchioError, PoolState, ProgramAccount,
+    calculate_exchange_rate, calculate_inverse_exchange_rate,
+};
+
+pub struct GetExchangeRate<'a> {
+    pub accounts: GetExchangeRateAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for GetExchangeRate<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = GetExchangeRateAccounts::try_from(accounts)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        // The pool account checks of `Deposit`, so a quote is only returned
+        // when a deposit would be priced at the same rate
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[Seed::from(b"lst_pool"), Seed::from(&seed_bytes)],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        if accounts.pool_stake.key() != &pool_state.stake_account {
+            return Err(PinocchioError::InvalidPoolStake.into());
+        }
+
+        if accounts.reserve_stake.key() != &pool_state.reserve_stake {
+            return Err(PinocchioError::InvalidReserveStake.into());
+        }
+
+        if accounts.lst_mint.key() != &pool_state.lst_mint {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+        Mint::check(accounts.lst_mint, &pool_state.token_program())?;
+
+        if pool_state.last_update_epoch != Clock::get()?.epoch {
+            return Err(PinocchioError::StalePoolBalance.into());
+        }
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> GetExchangeRate<'a> {
+    pub const DISCRIMINATOR: u8 = 26;
+
+    /// Sets the return data to `lamports_per_lst | lst_per_sol |
+    /// total_lamports | lst_supply`, little-endian u64s. The rates are per
+    /// whole LST and per SOL; the raw totals let callers convert amounts
+    /// with a single rounding, as `Deposit` and `Withdraw` do. `lst_per_sol`
+    /// is 0 when LST is outstanding but `total_lamports` is 0.
+    pub fn process(&self) -> ProgramResult {
+        let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        let total_lamports = pool_state.total_lamports;
+        let lst_supply = pool_state.lst_supply;
+        let lamports_per_lst = calculate_exchange_rate(total_lamports, lst_supply)?;
+        let lst_per_sol = calculate_inverse_exchange_rate(total_lamports, lst_supply)?;
+
+        let mut return_data = [0u8; 32];
+        return_data[0..8].copy_from_slice(&lamports_per_lst.to_le_bytes());
+        return_data[8..16].copy_from_slice(&lst_per_sol.to_le_bytes());
+        return_data[16..24].copy_from_slice(&total_lamports.to_le_bytes());
+        return_data[24..32].copy_from_slice(&lst_supply.to_le_bytes());
+        set_return_data(&return_data);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{PinocchioError, ProgramAccount, SignerAccount, VOTE_PROGRAM_ID};
+
+pub struct IncreaseValidatorStakeAccounts<'a> {
+    pub staker: &'a AccountInfo,
+    pub pool_state: &'a AccountInfo,
+    pub validator_list: &'a AccountInfo,
+    pub reserve_stake: &'a AccountInfo,
+    pub transient_stake: &'a AccountInfo,
+    pub validator_vote: &'a AccountInfo,
+    pub clock: &'a AccountInfo,
+    pub rent: &'a AccountInfo,
+    pub stake_history: &'a AccountInfo,
+    pub stake_config: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for IncreaseValidatorStakeAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [
+            staker,
+            pool_state,
+            validator_list,
+            reserve_stake,
+            transient_stake,
+            validator_vote,
+            clock,
+            rent,
+            stake_history,
+            stake_config,
+            system_program,
+            stake_program,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(staker)?;
+        ProgramAccount::check(pool_state)?;
+        ProgramAccount::check_system_program(system_program)?;
+
+        if !validator_list.is_owned_by(&crate::ID) {
+            return Err(PinocchioError::InvalidOwner.into());
+        }
+
+        ProgramAccount::check_stake_program(stake_program)?;
+
+        if validator_vote.owner() != &VOTE_PROGRAM_ID {
+            return Err(PinocchioError::InvalidVoteAccount.into());
+        }
+
+        Ok(Self {
+            staker,
+            pool_state,
+            validator_list,
+            reserve_stake,
+            transient_stake,
+            validator_vote,
+            clock,
+            rent,
+            stake_history,
+            stake_config,
+            system_program,
+            stake_program,
+        })
+    }
+}
@@ -0,0 +1,27 @@
+use pinocchio::program_error::ProgramError;
+
+use crate::PinocchioError;
+
+#[repr(C, packed)]
+pub struct IncreaseValidatorStakeInstructionData {
+    /// Reserve lamports moved into the transient stake, including its rent
+    pub amount: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for IncreaseValidatorStakeInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<IncreaseValidatorStakeInstructionData>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+        if amount == 0 {
+            return Err(PinocchioError::ZeroAmount.into());
+        }
+
+        Ok(Self { amount })
+    }
+}
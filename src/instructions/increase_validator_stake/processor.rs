@@ -0,0 +1,231 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    msg,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{Sysvar, clock::Clock, rent::Rent},
+};
+
+use crate::{
+    IncreaseValidatorStakeAccounts, IncreaseValidatorStakeInstructionData, MIN_STAKE_DELEGATION,
+    PinocchioError, PoolState, ProgramAccount, STAKE_ACCOUNT_SIZE, StakeStateV2, ValidatorList,
+    ValidatorStatus, delegate_stake, initialize_stake, reinit_stake_account, withdraw_stake,
+};
+
+pub struct IncreaseValidatorStake<'a> {
+    pub accounts: IncreaseValidatorStakeAccounts<'a>,
+    pub instruction_data: IncreaseValidatorStakeInstructionData,
+    pub epoch: u64,
+    pub transient_bump: u8,
+}
+
+impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for IncreaseValidatorStake<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&[u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = IncreaseValidatorStakeAccounts::try_from(accounts)?;
+        let instruction_data = IncreaseValidatorStakeInstructionData::try_from(data)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[
+                Seed::from(b"lst_pool"),
+                Seed::from(&seed_bytes),
+            ],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if accounts.staker.key() != &pool_state.staker {
+            return Err(PinocchioError::InvalidAuthority.into());
+        }
+
+        if !pool_state.has_validator_list()
+            || accounts.validator_list.key() != &pool_state.validator_list
+        {
+            return Err(PinocchioError::InvalidValidatorList.into());
+        }
+
+        if accounts.reserve_stake.key() != &pool_state.reserve_stake {
+            return Err(PinocchioError::InvalidReserveStake.into());
+        }
+
+        {
+            let list_data = accounts.validator_list.try_borrow_data()?;
+            let (_, entries) = ValidatorList::load(&list_data)?;
+
+            let entry = entries
+                .iter()
+                .find(|entry| &entry.vote_account == accounts.validator_vote.key())
+                .ok_or(PinocchioError::ValidatorNotFound)?;
+
+            // A deactivating stake could never take the transient stake in
+            if entry.status()? != ValidatorStatus::Active {
+                return Err(PinocchioError::InvalidValidatorStake.into());
+            }
+        }
+
+        // The primary validator is being left; see `InitializeReserve`
+        if pool_state.is_redelegating()
+            && accounts.validator_vote.key() == &pool_state.validator_vote
+        {
+            return Err(PinocchioError::RedelegationInProgress.into());
+        }
+
+        // Reserves of pools created before transient stakes may still be delegated
+        if accounts.reserve_stake.data_len() != 0 {
+            let reserve_data = accounts.reserve_stake.try_borrow_data()?;
+            if !StakeStateV2::load(&reserve_data)?.is_uninitialized() {
+                return Err(PinocchioError::ReserveAlreadyDelegated.into());
+            }
+        }
+
+        // For the primary validator, this is the transient stake
+        // `InitializeReserve` uses
+        let epoch = Clock::get()?.epoch;
+        let epoch_bytes = epoch.to_le_bytes();
+        let (transient_pda, transient_bump) = find_program_address(
+            &[
+                b"transient_stake",
+                accounts.pool_state.key().as_ref(),
+                accounts.validator_vote.key().as_ref(),
+                &epoch_bytes,
+            ],
+            &crate::ID,
+        );
+        if accounts.transient_stake.key() != &transient_pda {
+            return Err(PinocchioError::InvalidTransientStake.into());
+        }
+
+        if accounts.transient_stake.lamports() != 0 || accounts.transient_stake.data_len() != 0 {
+            return Err(PinocchioError::TransientStakeInUse.into());
+        }
+
+        let stake_rent = Rent::get()?.minimum_balance(STAKE_ACCOUNT_SIZE as usize);
+        if instruction_data.amount < stake_rent + MIN_STAKE_DELEGATION {
+            return Err(PinocchioError::ReserveBelowMinimum.into());
+        }
+
+        // The reserve keeps its rent
+        let available = pool_state.reserve_lamports.saturating_sub(stake_rent);
+        if instruction_data.amount > available {
+            return Err(PinocchioError::InsufficientReserve.into());
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+            epoch,
+            transient_bump,
+        })
+    }
+}
+
+impl<'a> IncreaseValidatorStake<'a> {
+    pub const DISCRIMINATOR: u8 = 29;
+
+    /// Like `InitializeReserve`, but delegates `amount` of the reserve to a
+    /// listed validator chosen by the staker. `MergeReserve` later merges the
+    /// transient stake into that validator's stake account.
+    pub fn process(&self) -> ProgramResult {
+        let amount = self.instruction_data.amount;
+
+        {
+            let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
+            let pool_state = PoolState::load(&pool_state_data)?;
+
+            let seed_binding = pool_state.seed.to_le_bytes();
+            let binding = [pool_state.bump];
+            let pool_seeds = [
+                Seed::from(b"lst_pool"),
+                Seed::from(&seed_binding),
+                Seed::from(&binding),
+            ];
+
+            let reserve_bump_binding = [pool_state.reserve_bump];
+            let reserve_seeds = [
+                Seed::from(b"reserve_stake"),
+                Seed::from(self.accounts.pool_state.key().as_ref()),
+                Seed::from(&reserve_bump_binding),
+            ];
+
+            let epoch_binding = self.epoch.to_le_bytes();
+            let transient_bump_binding = [self.transient_bump];
+            let transient_seeds = [
+                Seed::from(b"transient_stake"),
+                Seed::from(self.accounts.pool_state.key().as_ref()),
+                Seed::from(self.accounts.validator_vote.key().as_ref()),
+                Seed::from(&epoch_binding),
+                Seed::from(&transient_bump_binding),
+            ];
+
+            // Reserves closed by an earlier MergeReserve are plain system accounts
+            if self.accounts.reserve_stake.data_len() == 0 {
+                reinit_stake_account(self.accounts.reserve_stake, &reserve_seeds)?;
+            }
+
+            withdraw_stake(
+                self.accounts.reserve_stake,
+                self.accounts.transient_stake,
+                self.accounts.reserve_stake,
+                self.accounts.clock,
+                self.accounts.stake_history,
+                &reserve_seeds,
+                amount,
+            )?;
+
+            reinit_stake_account(self.accounts.transient_stake, &transient_seeds)?;
+
+            initialize_stake(
+                self.accounts.transient_stake,
+                self.accounts.rent,
+                self.accounts.pool_state,
+                self.accounts.pool_state,
+            )?;
+
+            delegate_stake(
+                self.accounts.transient_stake,
+                self.accounts.validator_vote,
+                self.accounts.clock,
+                self.accounts.stake_history,
+                self.accounts.stake_config,
+                self.accounts.pool_state,
+                &pool_seeds,
+            )?;
+        }
+
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+        pool_state.transient_lamports = pool_state
+            .transient_lamports
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        pool_state.debit_reserve(amount)?;
+
+        // The primary validator's share is what no other entry holds
+        if self.accounts.validator_vote.key() != &pool_state.validator_vote {
+            let mut list_data = self.accounts.validator_list.try_borrow_mut_data()?;
+            let (_, entries) = ValidatorList::load_mut(&mut list_data)?;
+            let entry = entries
+                .iter_mut()
+                .find(|entry| &entry.vote_account == self.accounts.validator_vote.key())
+                .ok_or(PinocchioError::ValidatorNotFound)?;
+            entry.transient_lamports = entry
+                .transient_lamports
+                .checked_add(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+
+        msg!("Reserve activated towards validator");
+        Ok(())
+    }
+}
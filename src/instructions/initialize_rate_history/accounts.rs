@@ -0,0 +1,32 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{ProgramAccount, SignerAccount};
+
+pub struct InitializeRateHistoryAccounts<'a> {
+    /// Pays the history's rent.
+    pub manager: &'a AccountInfo,
+    pub pool_state: &'a AccountInfo,
+    pub rate_history: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for InitializeRateHistoryAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [manager, pool_state, rate_history, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(manager)?;
+        ProgramAccount::check(pool_state)?;
+        ProgramAccount::check_system_program(system_program)?;
+
+        Ok(Self {
+            manager,
+            pool_state,
+            rate_history,
+            system_program,
+        })
+    }
+}
@@ -0,0 +1,95 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, msg, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+
+use crate::{
+    InitializeRateHistoryAccounts, PinocchioError, PoolState, ProgramAccount, RateHistory,
+};
+
+pub struct InitializeRateHistory<'a> {
+    pub accounts: InitializeRateHistoryAccounts<'a>,
+    pub rate_history_bump: u8,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for InitializeRateHistory<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = InitializeRateHistoryAccounts::try_from(accounts)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[Seed::from(b"lst_pool"), Seed::from(&seed_bytes)],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if accounts.manager.key() != &pool_state.manager {
+            return Err(PinocchioError::InvalidAuthority.into());
+        }
+
+        if pool_state.has_rate_history() {
+            return Err(PinocchioError::RateHistoryAlreadyInitialized.into());
+        }
+
+        let (history_pda, rate_history_bump) = find_program_address(
+            &[b"rate_history", accounts.pool_state.key().as_ref()],
+            &crate::ID,
+        );
+        if accounts.rate_history.key() != &history_pda {
+            return Err(PinocchioError::InvalidRateHistory.into());
+        }
+
+        Ok(Self {
+            accounts,
+            rate_history_bump,
+        })
+    }
+}
+
+impl<'a> InitializeRateHistory<'a> {
+    pub const DISCRIMINATOR: u8 = 24;
+
+    /// Creates the history with the pool's last recorded balance as its
+    /// first entry, and makes `UpdatePoolBalance` require it from now on.
+    pub fn process(&self) -> ProgramResult {
+        let bump_binding = [self.rate_history_bump];
+        let history_seeds = [
+            Seed::from(b"rate_history"),
+            Seed::from(self.accounts.pool_state.key().as_ref()),
+            Seed::from(&bump_binding),
+        ];
+
+        ProgramAccount::init::<RateHistory>(
+            self.accounts.manager,
+            self.accounts.rate_history,
+            &history_seeds,
+            RateHistory::LEN,
+        )?;
+
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+
+        let mut history_data = self.accounts.rate_history.try_borrow_mut_data()?;
+        let history = RateHistory::load_mut(&mut history_data)?;
+        history.set_inner(self.rate_history_bump, *self.accounts.pool_state.key());
+        history.push(
+            pool_state.last_update_epoch,
+            pool_state.total_lamports,
+            pool_state.lst_supply,
+        );
+
+        pool_state.rate_history = *self.accounts.rate_history.key();
+
+        msg!("Rate history initialized");
+        Ok(())
+    }
+}
@@ -1,11 +1,12 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
 
-use crate::{ProgramAccount, STAKE_PROGRAM_ID, VOTE_PROGRAM_ID};
+use crate::{PinocchioError, ProgramAccount, VOTE_PROGRAM_ID};
 
 pub struct InitializeReserveAccounts<'a> {
     pub pool_state: &'a AccountInfo,
     pub pool_stake: &'a AccountInfo,
     pub reserve_stake: &'a AccountInfo,
+    pub transient_stake: &'a AccountInfo,
     pub validator_vote: &'a AccountInfo,
     pub clock: &'a AccountInfo,
     pub rent: &'a AccountInfo,
@@ -23,6 +24,7 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitializeReserveAccounts<'a> {
             pool_state,
             pool_stake,
             reserve_stake,
+            transient_stake,
             validator_vote,
             clock,
             rent,
@@ -38,18 +40,17 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitializeReserveAccounts<'a> {
         ProgramAccount::check(pool_state)?;
         ProgramAccount::check_system_program(system_program)?;
 
-        if stake_program.key() != &STAKE_PROGRAM_ID {
-            return Err(ProgramError::IncorrectProgramId);
-        }
+        ProgramAccount::check_stake_program(stake_program)?;
 
         if validator_vote.owner() != &VOTE_PROGRAM_ID {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(PinocchioError::InvalidVoteAccount.into());
         }
 
         Ok(Self {
             pool_state,
             pool_stake,
             reserve_stake,
+            transient_stake,
             validator_vote,
             clock,
             rent,
@@ -1,13 +1,24 @@
 use pinocchio::{
-    ProgramResult, account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    msg,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{Sysvar, clock::Clock, rent::Rent},
 };
 
 use crate::{
-    InitializeReserveAccounts, MIN_STAKE_DELEGATION, PoolState, ProgramAccount, STAKE_ACCOUNT_SIZE, delegate_stake, initialize_stake, reinit_stake_account
+    InitializeReserveAccounts, MIN_STAKE_DELEGATION, PinocchioError, PoolState, ProgramAccount,
+    STAKE_ACCOUNT_SIZE, StakeStateV2, delegate_stake, initialize_stake, reinit_stake_account,
+    withdraw_stake,
 };
 
 pub struct InitializeReserve<'a> {
     pub accounts: InitializeReserveAccounts<'a>,
+    pub epoch: u64,
+    pub transient_bump: u8,
+    pub amount: u64,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for InitializeReserve<'a> {
@@ -20,7 +31,11 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitializeReserve<'a> {
         let pool_state = PoolState::load(&pool_state_data)?;
 
         if pool_state.discriminator == 0 {
-            return Err(ProgramError::UninitializedAccount);
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        if pool_state.cranks_paused() {
+            return Err(PinocchioError::CranksPaused.into());
         }
 
         let seed_bytes = pool_state.seed.to_le_bytes();
@@ -34,79 +49,151 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitializeReserve<'a> {
         )?;
 
         if accounts.pool_stake.key() != &pool_state.stake_account {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(PinocchioError::InvalidPoolStake.into());
         }
 
         if accounts.reserve_stake.key() != &pool_state.reserve_stake {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(PinocchioError::InvalidReserveStake.into());
         }
 
         if accounts.validator_vote.key() != &pool_state.validator_vote {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(PinocchioError::InvalidValidatorVote.into());
+        }
+
+        // The transient stake could never merge into a pool stake delegated elsewhere
+        if pool_state.is_redelegating() {
+            return Err(PinocchioError::RedelegationInProgress.into());
         }
 
-        if accounts.reserve_stake.lamports() < (STAKE_ACCOUNT_SIZE + MIN_STAKE_DELEGATION) {
-            return Err(ProgramError::InsufficientFunds);
+        // Reserves of pools created before transient stakes may still be delegated
+        if accounts.reserve_stake.data_len() != 0 {
+            let reserve_data = accounts.reserve_stake.try_borrow_data()?;
+            if !StakeStateV2::load(&reserve_data)?.is_uninitialized() {
+                return Err(PinocchioError::ReserveAlreadyDelegated.into());
+            }
         }
 
-        Ok(Self { accounts })
+        let epoch = Clock::get()?.epoch;
+        let epoch_bytes = epoch.to_le_bytes();
+        let (transient_pda, transient_bump) = find_program_address(
+            &[
+                b"transient_stake",
+                accounts.pool_state.key().as_ref(),
+                accounts.validator_vote.key().as_ref(),
+                &epoch_bytes,
+            ],
+            &crate::ID,
+        );
+        if accounts.transient_stake.key() != &transient_pda {
+            return Err(PinocchioError::InvalidTransientStake.into());
+        }
+
+        if accounts.transient_stake.lamports() != 0 || accounts.transient_stake.data_len() != 0 {
+            return Err(PinocchioError::TransientStakeInUse.into());
+        }
+
+        // The reserve keeps its rent; the transient stake needs its own on top
+        // of the minimum delegation. Lamports donated to the reserve stay put
+        let stake_rent = Rent::get()?.minimum_balance(STAKE_ACCOUNT_SIZE as usize);
+        let amount = pool_state
+            .reserve_lamports
+            .checked_sub(stake_rent)
+            .ok_or(PinocchioError::ReserveBelowMinimum)?;
+
+        if amount < stake_rent + MIN_STAKE_DELEGATION {
+            return Err(PinocchioError::ReserveBelowMinimum.into());
+        }
+
+        Ok(Self {
+            accounts,
+            epoch,
+            transient_bump,
+            amount,
+        })
     }
 }
 
 impl<'a> InitializeReserve<'a> {
     pub const DISCRIMINATOR: u8 = 2;
 
+    /// Moves the tracked reserve SOL above its rent into this epoch's
+    /// transient stake and delegates it. The reserve itself is never
+    /// delegated, so it keeps accepting deposits while the transient stake
+    /// activates.
     pub fn process(&self) -> ProgramResult {
-        let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
-        let pool_state = PoolState::load(&pool_state_data)?;
+        {
+            let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
+            let pool_state = PoolState::load(&pool_state_data)?;
+
+            let seed_binding = pool_state.seed.to_le_bytes();
+            let binding = [pool_state.bump];
+            let pool_seeds = [
+                Seed::from(b"lst_pool"),
+                Seed::from(&seed_binding),
+                Seed::from(&binding),
+            ];
 
-        let seed_binding = pool_state.seed.to_le_bytes();
-        let binding = [pool_state.bump];
-        let pool_seeds = [
-            Seed::from(b"lst_pool"),
-            Seed::from(&seed_binding),
-            Seed::from(&binding),
-        ];
-
-        if self.accounts.reserve_stake.data_len() > 0 {
-            let reserve_data = self.accounts.reserve_stake.try_borrow_data()?;
-            if reserve_data.len() >= 4 {
-                let state = u32::from_le_bytes(reserve_data[0..4].try_into().unwrap());
-                if state >= 1 {
-                    return Err(ProgramError::AccountAlreadyInitialized);
-                }
-            }
-        }
-    
-        // Reallocate if needed (account was created with 0 space)
-        if self.accounts.reserve_stake.data_len() == 0 {
             let reserve_bump_binding = [pool_state.reserve_bump];
             let reserve_seeds = [
                 Seed::from(b"reserve_stake"),
                 Seed::from(self.accounts.pool_state.key().as_ref()),
                 Seed::from(&reserve_bump_binding),
             ];
-    
-            reinit_stake_account(self.accounts.reserve_stake, &reserve_seeds)?;
-        }
 
-        initialize_stake(
-            self.accounts.reserve_stake,
-            self.accounts.rent,
-            self.accounts.pool_state,
-            self.accounts.pool_state,
-        )?;
+            let epoch_binding = self.epoch.to_le_bytes();
+            let transient_bump_binding = [self.transient_bump];
+            let transient_seeds = [
+                Seed::from(b"transient_stake"),
+                Seed::from(self.accounts.pool_state.key().as_ref()),
+                Seed::from(self.accounts.validator_vote.key().as_ref()),
+                Seed::from(&epoch_binding),
+                Seed::from(&transient_bump_binding),
+            ];
 
-        delegate_stake(
-            self.accounts.reserve_stake,
-            self.accounts.validator_vote,
-            self.accounts.clock,
-            self.accounts.stake_history,
-            self.accounts.stake_config,
-            self.accounts.pool_state,
-            &pool_seeds,
-        )?;
+            // Reserves closed by the original MergeReserve are plain system accounts
+            if self.accounts.reserve_stake.data_len() == 0 {
+                reinit_stake_account(self.accounts.reserve_stake, &reserve_seeds)?;
+            }
+
+            withdraw_stake(
+                self.accounts.reserve_stake,
+                self.accounts.transient_stake,
+                self.accounts.reserve_stake,
+                self.accounts.clock,
+                self.accounts.stake_history,
+                &reserve_seeds,
+                self.amount,
+            )?;
+
+            reinit_stake_account(self.accounts.transient_stake, &transient_seeds)?;
+
+            initialize_stake(
+                self.accounts.transient_stake,
+                self.accounts.rent,
+                self.accounts.pool_state,
+                self.accounts.pool_state,
+            )?;
+
+            delegate_stake(
+                self.accounts.transient_stake,
+                self.accounts.validator_vote,
+                self.accounts.clock,
+                self.accounts.stake_history,
+                self.accounts.stake_config,
+                self.accounts.pool_state,
+                &pool_seeds,
+            )?;
+        }
+
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+        pool_state.transient_lamports = pool_state
+            .transient_lamports
+            .checked_add(self.amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        pool_state.debit_reserve(self.amount)?;
 
+        msg!("Reserve activated into transient stake");
         Ok(())
     }
 }
@@ -1,9 +1,6 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
 
-use crate::{
-    ProgramAccount, SignerAccount,
-    constants::{STAKE_PROGRAM_ID, VOTE_PROGRAM_ID},
-};
+use crate::{PinocchioError, ProgramAccount, SignerAccount, constants::VOTE_PROGRAM_ID};
 
 pub struct InitializeAccounts<'a> {
     pub initializer: &'a AccountInfo,
@@ -21,6 +18,9 @@ pub struct InitializeAccounts<'a> {
     pub token_program: &'a AccountInfo,
     pub stake_program: &'a AccountInfo,
     pub ata_program: &'a AccountInfo,
+    /// Trailing `[metadata, token_metadata_program]`, required when the
+    /// instruction data carries token metadata
+    pub metadata: Option<(&'a AccountInfo, &'a AccountInfo)>,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
@@ -43,6 +43,7 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
             token_program,
             stake_program,
             ata_program,
+            remaining @ ..
         ] = accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
@@ -54,14 +55,21 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
         ProgramAccount::check_token_program(token_program)?;
         ProgramAccount::check_ata_program(ata_program)?;
 
-        if stake_program.key() != &STAKE_PROGRAM_ID {
-            return Err(ProgramError::IncorrectProgramId);
-        }
+        ProgramAccount::check_stake_program(stake_program)?;
 
         if validator_vote.owner() != &VOTE_PROGRAM_ID {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(PinocchioError::InvalidVoteAccount.into());
         }
 
+        let metadata = match remaining {
+            [] => None,
+            [metadata, token_metadata_program, ..] => {
+                ProgramAccount::check_token_metadata_program(token_metadata_program)?;
+                Some((metadata, token_metadata_program))
+            }
+            _ => return Err(ProgramError::NotEnoughAccountKeys),
+        };
+
         Ok(Self {
             initializer,
             initializer_lst_ata,
@@ -77,7 +85,8 @@ impl<'a> TryFrom<&'a [AccountInfo]> for InitializeAccounts<'a> {
             system_program,
             token_program,
             stake_program,
-            ata_program
+            ata_program,
+            metadata,
         })
     }
 }
@@ -1,27 +1,60 @@
-use crate::PinocchioError;
+use crate::{MAX_FEE_BPS, PinocchioError, TokenMetadata};
 use pinocchio::{program_error::ProgramError};
 
-#[repr(C, packed)]
 pub struct InitializeData {
     pub seed: u64,
+    pub deposit_fee_bps: u16,
+    pub withdrawal_fee_bps: u16,
+    pub reward_fee_bps: u16,
+    pub sol_withdrawal_fee_bps: u16,
+    /// Optional trailing name/symbol/URI; when present the LST metadata
+    /// account is created alongside the mint
+    pub metadata: Option<TokenMetadata>,
+}
+
+impl InitializeData {
+    /// Seed plus the four fees; token metadata may follow.
+    pub const FIXED_LEN: usize = 16;
 }
 
 impl<'a> TryFrom<&'a [u8]> for InitializeData {
     type Error = ProgramError;
 
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() != size_of::<u64>() {
+        if data.len() < Self::FIXED_LEN {
             return Err(ProgramError::InvalidInstructionData);
         }
 
         let seed = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let deposit_fee_bps = u16::from_le_bytes(data[8..10].try_into().unwrap());
+        let withdrawal_fee_bps = u16::from_le_bytes(data[10..12].try_into().unwrap());
+        let reward_fee_bps = u16::from_le_bytes(data[12..14].try_into().unwrap());
+        let sol_withdrawal_fee_bps = u16::from_le_bytes(data[14..16].try_into().unwrap());
 
         if seed == 0 {
             return Err(PinocchioError::InvalidSeed.into());
         }
 
+        if deposit_fee_bps > MAX_FEE_BPS
+            || withdrawal_fee_bps > MAX_FEE_BPS
+            || reward_fee_bps > MAX_FEE_BPS
+            || sol_withdrawal_fee_bps > MAX_FEE_BPS
+        {
+            return Err(PinocchioError::InvalidFee.into());
+        }
+
+        let metadata = match &data[Self::FIXED_LEN..] {
+            [] => None,
+            rest => Some(TokenMetadata::try_from(rest)?),
+        };
+
         Ok(Self {
             seed,
+            deposit_fee_bps,
+            withdrawal_fee_bps,
+            reward_fee_bps,
+            sol_withdrawal_fee_bps,
+            metadata,
         })
     }
 }
@@ -1,19 +1,20 @@
 use pinocchio::{
     ProgramResult,
     account_info::AccountInfo,
-    instruction::{Seed, Signer},
+    instruction::Seed,
     msg,
     program_error::ProgramError,
-    pubkey::find_program_address,
-    sysvars::{Sysvar, rent::Rent},
+    pubkey::{Pubkey, find_program_address},
+    sysvars::{Sysvar, clock::Clock, rent::Rent},
 };
 use pinocchio_system::instructions::CreateAccount;
-use pinocchio_token::instructions::{InitializeMint2, MintTo};
 
 use super::{InitializeAccounts, InitializeData};
 use crate::{
-    AssociatedToken, PoolState, ProgramAccount, constants::*, create_stake_account, delegate_stake,
-    initialize_stake,
+    AssociatedToken, MINT_WITH_METADATA_POINTER_LEN, PinocchioError, PoolState, ProgramAccount,
+    constants::*, create_metadata_account, create_stake_account, delegate_stake,
+    initialize_metadata_pointer, initialize_mint, initialize_stake, mint_to,
+    verify_metadata_account,
 };
 
 pub struct Initialize<'a> {
@@ -35,13 +36,13 @@ impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for Initialize<'a> {
 
         let (pool_pda, pool_bump) = find_program_address(&[b"lst_pool", &seed_bytes], &crate::ID);
         if accounts.pool_state.key() != &pool_pda {
-            return Err(ProgramError::InvalidSeeds);
+            return Err(PinocchioError::InvalidPoolAddress.into());
         }
 
         let (stake_pda, stake_bump) =
             find_program_address(&[b"stake", accounts.pool_state.key().as_ref()], &crate::ID);
         if accounts.stake_account.key() != &stake_pda {
-            return Err(ProgramError::InvalidSeeds);
+            return Err(PinocchioError::InvalidPoolStake.into());
         }
 
         let (reserve_pda, reserve_bump) = find_program_address(
@@ -49,7 +50,14 @@ impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for Initialize<'a> {
             &crate::ID,
         );
         if accounts.reserve_stake.key() != &reserve_pda {
-            return Err(ProgramError::InvalidSeeds);
+            return Err(PinocchioError::InvalidReserveStake.into());
+        }
+
+        if data.metadata.is_some() {
+            let Some((metadata, _)) = accounts.metadata else {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            };
+            verify_metadata_account(metadata, accounts.lst_mint.key())?;
         }
 
         Ok(Self {
@@ -92,6 +100,20 @@ impl<'a> Initialize<'a> {
         self.create_pool_state(&pool_seeds)?;
         self.create_lst_mint()?;
 
+        if let (Some(metadata), Some((metadata_account, _))) =
+            (&self.data.metadata, self.accounts.metadata)
+        {
+            create_metadata_account(
+                metadata_account,
+                self.accounts.lst_mint,
+                self.accounts.pool_state,
+                self.accounts.initializer,
+                self.accounts.system_program,
+                metadata,
+                &pool_seeds,
+            )?;
+        }
+
         AssociatedToken::init(
             self.accounts.initializer_lst_ata,
             self.accounts.lst_mint,
@@ -138,15 +160,14 @@ impl<'a> Initialize<'a> {
     }
 
     fn mint_initial_lst(&self, pool_seeds: &[Seed]) -> ProgramResult {
-        let signer = [Signer::from(pool_seeds)];
-
-        MintTo {
-            mint: self.accounts.lst_mint,
-            account: self.accounts.initializer_lst_ata,
-            mint_authority: self.accounts.pool_state,
-            amount: MIN_STAKE_DELEGATION,
-        }
-        .invoke_signed(&signer)?;
+        mint_to(
+            self.accounts.lst_mint,
+            self.accounts.initializer_lst_ata,
+            self.accounts.pool_state,
+            self.accounts.token_program,
+            MIN_STAKE_DELEGATION,
+            pool_seeds,
+        )?;
 
         Ok(())
     }
@@ -159,6 +180,10 @@ impl<'a> Initialize<'a> {
             PoolState::LEN,
         )?;
 
+        // Pool stake (rent + MIN_STAKE_DELEGATION) plus the rent-only reserve
+        let stake_rent = Rent::get()?.minimum_balance(STAKE_ACCOUNT_SIZE as usize);
+        let total_lamports = stake_rent * 2 + MIN_STAKE_DELEGATION;
+
         let mut data = self.accounts.pool_state.try_borrow_mut_data()?;
         let pool = PoolState::load_mut(&mut data)?;
         pool.set_inner(
@@ -168,11 +193,33 @@ impl<'a> Initialize<'a> {
             *self.accounts.validator_vote.key(),
             *self.accounts.stake_account.key(),
             *self.accounts.reserve_stake.key(),
+            PoolState::CURRENT_VERSION,
             self.data.seed,
             self.pool_bump,
             self.stake_bump,
             self.reserve_bump,
             MIN_STAKE_DELEGATION,
+            *self.accounts.initializer_lst_ata.key(),
+            self.data.deposit_fee_bps,
+            self.data.withdrawal_fee_bps,
+            self.data.reward_fee_bps,
+            self.data.sol_withdrawal_fee_bps,
+            total_lamports,
+            Clock::get()?.epoch,
+            stake_rent,
+            Pubkey::default(),
+            0,
+            Pubkey::default(),
+            0,
+            *self.accounts.initializer.key(),
+            Pubkey::default(),
+            0,
+            *self.accounts.token_program.key(),
+            0,
+            0,
+            0,
+            Pubkey::default(),
+            Pubkey::default(),
         );
 
         msg!("Pool state initialized");
@@ -182,22 +229,44 @@ impl<'a> Initialize<'a> {
     fn create_lst_mint(&self) -> ProgramResult {
         let rent = Rent::get()?;
 
+        // Token-2022 mints point wallets at the Metaplex metadata account
+        let metadata_pointer = match self.accounts.metadata {
+            Some((metadata, _))
+                if self.data.metadata.is_some()
+                    && self.accounts.token_program.key() == &TOKEN_2022_PROGRAM_ID =>
+            {
+                Some(metadata.key())
+            }
+            _ => None,
+        };
+        let space = match metadata_pointer {
+            Some(_) => MINT_WITH_METADATA_POINTER_LEN,
+            None => pinocchio_token::state::Mint::LEN,
+        };
+
         CreateAccount {
             from: self.accounts.initializer,
             to: self.accounts.lst_mint,
-            lamports: rent.minimum_balance(pinocchio_token::state::Mint::LEN),
-            space: pinocchio_token::state::Mint::LEN as u64,
-            owner: &pinocchio_token::ID,
+            lamports: rent.minimum_balance(space),
+            space: space as u64,
+            owner: self.accounts.token_program.key(),
         }
         .invoke()?;
 
-        InitializeMint2 {
-            mint: self.accounts.lst_mint,
-            decimals: 9,
-            mint_authority: self.accounts.pool_state.key(),
-            freeze_authority: None,
+        if let Some(metadata_address) = metadata_pointer {
+            initialize_metadata_pointer(
+                self.accounts.lst_mint,
+                self.accounts.pool_state.key(),
+                metadata_address,
+                self.accounts.token_program,
+            )?;
         }
-        .invoke()?;
+
+        initialize_mint(
+            self.accounts.lst_mint,
+            self.accounts.pool_state.key(),
+            self.accounts.token_program,
+        )?;
 
         msg!("LST mint initialized");
         Ok(())
@@ -0,0 +1,32 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{ProgramAccount, SignerAccount};
+
+pub struct InitializeWithdrawLedgerAccounts<'a> {
+    /// Owner of the new ledger; pays its rent.
+    pub user: &'a AccountInfo,
+    pub pool_state: &'a AccountInfo,
+    pub ledger: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for InitializeWithdrawLedgerAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [user, pool_state, ledger, system_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(user)?;
+        ProgramAccount::check(pool_state)?;
+        ProgramAccount::check_system_program(system_program)?;
+
+        Ok(Self {
+            user,
+            pool_state,
+            ledger,
+            system_program,
+        })
+    }
+}
@@ -0,0 +1,84 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, msg, program_error::ProgramError,
+    pubkey::find_program_address,
+};
+
+use crate::{
+    InitializeWithdrawLedgerAccounts, PinocchioError, PoolState, ProgramAccount, WithdrawLedger,
+};
+
+pub struct InitializeWithdrawLedger<'a> {
+    pub accounts: InitializeWithdrawLedgerAccounts<'a>,
+    pub ledger_bump: u8,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for InitializeWithdrawLedger<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = InitializeWithdrawLedgerAccounts::try_from(accounts)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[Seed::from(b"lst_pool"), Seed::from(&seed_bytes)],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        let (ledger_pda, ledger_bump) = find_program_address(
+            &[
+                b"ledger",
+                accounts.pool_state.key().as_ref(),
+                accounts.user.key().as_ref(),
+            ],
+            &crate::ID,
+        );
+        if accounts.ledger.key() != &ledger_pda {
+            return Err(PinocchioError::InvalidWithdrawLedger.into());
+        }
+
+        Ok(Self {
+            accounts,
+            ledger_bump,
+        })
+    }
+}
+
+impl<'a> InitializeWithdrawLedger<'a> {
+    pub const DISCRIMINATOR: u8 = 22;
+
+    /// Creates an empty ledger that hands out nonces from 1.
+    pub fn process(&self) -> ProgramResult {
+        let ledger_bump_binding = [self.ledger_bump];
+        let ledger_seeds = [
+            Seed::from(b"ledger"),
+            Seed::from(self.accounts.pool_state.key().as_ref()),
+            Seed::from(self.accounts.user.key().as_ref()),
+            Seed::from(&ledger_bump_binding),
+        ];
+
+        ProgramAccount::init::<WithdrawLedger>(
+            self.accounts.user,
+            self.accounts.ledger,
+            &ledger_seeds,
+            WithdrawLedger::space(0),
+        )?;
+
+        let mut ledger_data = self.accounts.ledger.try_borrow_mut_data()?;
+        WithdrawLedger::load_header_mut(&mut ledger_data)?.set_inner(
+            self.ledger_bump,
+            *self.accounts.pool_state.key(),
+            *self.accounts.user.key(),
+        );
+
+        msg!("Withdraw ledger initialized");
+        Ok(())
+    }
+}
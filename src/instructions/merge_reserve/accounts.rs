@@ -1,14 +1,20 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
 
-use crate::{ProgramAccount, STAKE_PROGRAM_ID};
+use crate::ProgramAccount;
 
 pub struct MergeReserveAccounts<'a> {
     pub pool_state: &'a AccountInfo,
-    pub pool_stake: &'a AccountInfo,
+    /// The pool stake, or the listed validator stake the transient stake is
+    /// delegated to
+    pub destination_stake: &'a AccountInfo,
+    pub transient_stake: &'a AccountInfo,
+    /// Gets back the rent reserve the merge leaves undelegated
     pub reserve_stake: &'a AccountInfo,
     pub clock: &'a AccountInfo,
     pub stake_history: &'a AccountInfo,
     pub stake_program: &'a AccountInfo,
+    /// Required when merging into a listed validator stake.
+    pub validator_list: Option<&'a AccountInfo>,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for MergeReserveAccounts<'a> {
@@ -17,11 +23,13 @@ impl<'a> TryFrom<&'a [AccountInfo]> for MergeReserveAccounts<'a> {
     fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
         let [
             pool_state,
-            pool_stake,
+            destination_stake,
+            transient_stake,
             reserve_stake,
             clock,
             stake_history,
             stake_program,
+            remaining @ ..,
         ] = accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
@@ -29,17 +37,17 @@ impl<'a> TryFrom<&'a [AccountInfo]> for MergeReserveAccounts<'a> {
 
         ProgramAccount::check(pool_state)?;
 
-        if stake_program.key() != &STAKE_PROGRAM_ID {
-            return Err(ProgramError::IncorrectProgramId);
-        }
+        ProgramAccount::check_stake_program(stake_program)?;
 
         Ok(Self {
             pool_state,
-            pool_stake,
+            destination_stake,
+            transient_stake,
             reserve_stake,
             clock,
             stake_history,
             stake_program,
+            validator_list: remaining.first(),
         })
     }
 }
@@ -1,11 +1,29 @@
 use pinocchio::{
-    ProgramResult, account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    msg,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{Sysvar, clock::Clock},
 };
 
-use crate::{MergeReserveAccounts, PoolState, ProgramAccount, merge_stake};
+use crate::{
+    MergeReserveAccounts, PinocchioError, PoolState, ProgramAccount, StakeHistory, StakeStateV2,
+    ValidatorList, check_pool_stake, merge_stake, withdraw_stake,
+};
 
 pub struct MergeReserve<'a> {
     pub accounts: MergeReserveAccounts<'a>,
+    /// Reserve SOL the transient stake accounts for
+    pub transient_value: u64,
+    /// Lamports withdrawn back to the reserve: the transient's rent reserve,
+    /// or all a legacy reserve holds beyond its delegation
+    pub transient_rent: u64,
+    /// The source is a reserve the original `InitializeReserve` delegated
+    pub legacy_reserve: bool,
+    /// Validator list entry of the destination, unless it is the pool stake
+    pub validator_index: Option<usize>,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for MergeReserve<'a> {
@@ -18,7 +36,11 @@ impl<'a> TryFrom<&'a [AccountInfo]> for MergeReserve<'a> {
         let pool_state = PoolState::load(&pool_state_data)?;
 
         if pool_state.discriminator == 0 {
-            return Err(ProgramError::UninitializedAccount);
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        if pool_state.cranks_paused() {
+            return Err(PinocchioError::CranksPaused.into());
         }
 
         let seed_bytes = pool_state.seed.to_le_bytes();
@@ -30,54 +52,221 @@ impl<'a> TryFrom<&'a [AccountInfo]> for MergeReserve<'a> {
             accounts.pool_state,
             pool_state.bump,
         )?;
-        if accounts.pool_stake.owner() != accounts.stake_program.key() {
-            return Err(ProgramError::InvalidAccountData);
+        if accounts.destination_stake.owner() != accounts.stake_program.key() {
+            return Err(PinocchioError::InvalidPoolStake.into());
         }
 
-        if accounts.reserve_stake.owner() != accounts.stake_program.key() {
-            return Err(ProgramError::InvalidAccountData);
+        if accounts.reserve_stake.key() != &pool_state.reserve_stake {
+            return Err(PinocchioError::InvalidReserveStake.into());
         }
 
-        if accounts.pool_stake.key() != &pool_state.stake_account {
-            return Err(ProgramError::InvalidAccountData);
+        if accounts.transient_stake.lamports() == 0 {
+            return Err(PinocchioError::ReserveEmpty.into());
         }
 
-        if accounts.reserve_stake.key() != &pool_state.reserve_stake {
-            return Err(ProgramError::InvalidAccountData);
+        if accounts.transient_stake.owner() != accounts.stake_program.key()
+            || accounts.transient_stake.data_len() < StakeStateV2::LEN
+        {
+            return Err(PinocchioError::InvalidTransientStake.into());
         }
 
-        Ok(Self { accounts })
+        // Pools from before transient stakes delegated the reserve itself
+        let legacy_reserve = accounts.transient_stake.key() == &pool_state.reserve_stake;
+
+        let clock = Clock::get()?;
+        let epoch = clock.epoch;
+
+        // Transient stakes are seeded by their validator and the epoch they
+        // were delegated in
+        let (activation_epoch, activation, voter, transient_value, transient_rent) = {
+            let transient_data = accounts.transient_stake.try_borrow_data()?;
+            let transient = StakeStateV2::load(&transient_data)?;
+            let (Some(meta), Some(delegation), Some(value)) = (
+                transient.meta(),
+                transient.delegation(),
+                transient.delegated_lamports(),
+            ) else {
+                return Err(PinocchioError::InvalidTransientStake.into());
+            };
+
+            let history_data = accounts.stake_history.try_borrow_data()?;
+            let history = StakeHistory::from_account(accounts.stake_history, &history_data)?;
+
+            // A legacy reserve's deposits were all delegated up to the last
+            // `InitializeReserve`; later ones and its rent go back undelegated
+            let (transient_value, transient_rent) = if legacy_reserve {
+                let stake = delegation.stake();
+                (
+                    stake,
+                    accounts.transient_stake.lamports().saturating_sub(stake),
+                )
+            } else {
+                (value, meta.rent_exempt_reserve())
+            };
+
+            (
+                delegation.activation_epoch(),
+                delegation.activation(epoch, &history),
+                delegation.voter_pubkey,
+                transient_value,
+                transient_rent,
+            )
+        };
+
+        if legacy_reserve {
+            // The original program delegated the reserve to the pool validator
+            if voter != pool_state.validator_vote {
+                return Err(PinocchioError::InvalidReserveStake.into());
+            }
+        } else {
+            let (transient_pda, _) = find_program_address(
+                &[
+                    b"transient_stake",
+                    accounts.pool_state.key().as_ref(),
+                    voter.as_ref(),
+                    &activation_epoch.to_le_bytes(),
+                ],
+                &crate::ID,
+            );
+            if accounts.transient_stake.key() != &transient_pda {
+                return Err(PinocchioError::InvalidTransientStake.into());
+            }
+        }
+
+        // Transients of the primary validator merge into the pool stake, those
+        // of `IncreaseValidatorStake` into the listed validator's stake
+        let validator_index = if voter == pool_state.validator_vote {
+            if accounts.destination_stake.key() != &pool_state.stake_account {
+                return Err(PinocchioError::InvalidPoolStake.into());
+            }
+            None
+        } else {
+            let validator_list = accounts
+                .validator_list
+                .ok_or(PinocchioError::MissingValidatorList)?;
+            if !pool_state.has_validator_list()
+                || validator_list.key() != &pool_state.validator_list
+            {
+                return Err(PinocchioError::InvalidValidatorList.into());
+            }
+
+            if !validator_list.is_owned_by(&crate::ID) {
+                return Err(PinocchioError::InvalidOwner.into());
+            }
+
+            let list_data = validator_list.try_borrow_data()?;
+            let (_, entries) = ValidatorList::load(&list_data)?;
+            let index = entries
+                .iter()
+                .position(|entry| entry.vote_account == voter)
+                .ok_or(PinocchioError::ValidatorNotFound)?;
+
+            if accounts.destination_stake.key() != &entries[index].stake_account {
+                return Err(PinocchioError::InvalidValidatorStake.into());
+            }
+            Some(index)
+        };
+
+        check_pool_stake(
+            accounts.destination_stake,
+            accounts.pool_state.key(),
+            &voter,
+            &clock,
+        )?;
+        check_pool_stake(
+            accounts.transient_stake,
+            accounts.pool_state.key(),
+            &voter,
+            &clock,
+        )?;
+
+        // Only fully active stake merges into an active stake
+        if activation_epoch >= epoch || activation.activating != 0 {
+            return Err(PinocchioError::TransientStakeActivating.into());
+        }
+
+        Ok(Self {
+            accounts,
+            transient_value,
+            transient_rent,
+            legacy_reserve,
+            validator_index,
+        })
     }
 }
 
 impl<'a> MergeReserve<'a> {
     pub const DISCRIMINATOR: u8 = 3;
 
+    /// Merges the transient stake into the stake account of its validator.
+    /// Active stakes merge only their delegations, so the transient's rent
+    /// reserve is withdrawn back to the reserve rather than left uncounted in
+    /// the destination.
+    ///
+    /// A reserve delegated by the original `InitializeReserve` is merged the
+    /// same way; it is left an undelegated stake account holding what it had
+    /// beyond its delegation, so deposits land in it again.
     pub fn process(&self) -> ProgramResult {
-        let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
-        let pool_state = PoolState::load(&pool_state_data)?;
+        {
+            let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
+            let pool_state = PoolState::load(&pool_state_data)?;
+
+            let seed_binding = pool_state.seed.to_le_bytes();
+            let binding = [pool_state.bump];
+            let pool_seeds = [
+                Seed::from(b"lst_pool"),
+                Seed::from(&seed_binding),
+                Seed::from(&binding),
+            ];
 
-        let seed_binding = pool_state.seed.to_le_bytes();
-        let binding = [pool_state.bump];
-        let pool_seeds = [
-            Seed::from(b"lst_pool"),
-            Seed::from(&seed_binding),
-            Seed::from(&binding),
-        ];
+            merge_stake(
+                self.accounts.destination_stake,
+                self.accounts.transient_stake,
+                self.accounts.clock,
+                self.accounts.stake_history,
+                self.accounts.pool_state,
+                &pool_seeds,
+            )?;
 
-        if self.accounts.reserve_stake.lamports() == 0 {
-            return Err(ProgramError::UninitializedAccount);
+            withdraw_stake(
+                self.accounts.destination_stake,
+                self.accounts.reserve_stake,
+                self.accounts.pool_state,
+                self.accounts.clock,
+                self.accounts.stake_history,
+                &pool_seeds,
+                self.transient_rent,
+            )?;
         }
 
-        merge_stake(
-            self.accounts.pool_stake,
-            self.accounts.reserve_stake,
-            self.accounts.clock,
-            self.accounts.stake_history,
-            self.accounts.pool_state,
-            &pool_seeds,
-        )?;
+        // Rewards the transient stake earned were never recorded
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+        if self.legacy_reserve {
+            // The delegation now counts through the pool stake
+            pool_state.reserve_lamports = pool_state
+                .reserve_lamports
+                .saturating_sub(self.transient_value);
+        } else {
+            pool_state.transient_lamports = pool_state
+                .transient_lamports
+                .saturating_sub(self.transient_value);
+            pool_state.credit_reserve(self.transient_rent)?;
+        }
+
+        if let Some(index) = self.validator_index {
+            let validator_list = self
+                .accounts
+                .validator_list
+                .ok_or(PinocchioError::MissingValidatorList)?;
+            let mut list_data = validator_list.try_borrow_mut_data()?;
+            let (_, entries) = ValidatorList::load_mut(&mut list_data)?;
+            entries[index].transient_lamports = entries[index]
+                .transient_lamports
+                .saturating_sub(self.transient_value);
+        }
 
+        msg!("Transient stake merged");
         Ok(())
     }
 }
@@ -0,0 +1,56 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{PinocchioError, ProgramAccount, SignerAccount};
+
+pub struct MigratePoolAccounts<'a> {
+    pub payer: &'a AccountInfo,
+    pub pool_state: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    /// Pool stake and reserve, to value layouts without `total_lamports` and
+    /// start tracking their reserve
+    pub pool_stake: &'a AccountInfo,
+    pub reserve_stake: &'a AccountInfo,
+    pub clock: &'a AccountInfo,
+    pub stake_history: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for MigratePoolAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [
+            payer,
+            pool_state,
+            system_program,
+            pool_stake,
+            reserve_stake,
+            clock,
+            stake_history,
+            stake_program,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(payer)?;
+        ProgramAccount::check_system_program(system_program)?;
+        ProgramAccount::check_stake_program(stake_program)?;
+
+        // Older layouts are shorter, so only the owner is checked here
+        if !pool_state.is_owned_by(&crate::ID) {
+            return Err(PinocchioError::InvalidOwner.into());
+        }
+
+        Ok(Self {
+            payer,
+            pool_state,
+            system_program,
+            pool_stake,
+            reserve_stake,
+            clock,
+            stake_history,
+            stake_program,
+        })
+    }
+}
@@ -0,0 +1,208 @@
+use core::mem::offset_of;
+
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    msg,
+    program_error::ProgramError,
+    pubkey::{Pubkey, find_program_address},
+    sysvars::{Sysvar, clock::Clock, rent::Rent},
+};
+use pinocchio_system::instructions::Transfer;
+
+use crate::{
+    MigratePoolAccounts, PinocchioError, PoolState, ProgramAccount, stake_value, withdraw_stake,
+};
+
+pub struct MigratePool<'a> {
+    pub accounts: MigratePoolAccounts<'a>,
+    pub old_len: usize,
+    /// Pool value for layouts from before the cached balance
+    pub total_lamports: Option<u64>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for MigratePool<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = MigratePoolAccounts::try_from(accounts)?;
+        let old_len = accounts.pool_state.data_len();
+
+        if old_len == PoolState::LEN {
+            let pool_state_data = accounts.pool_state.try_borrow_data()?;
+            let pool_state = PoolState::load(&pool_state_data)?;
+
+            if pool_state.version == PoolState::CURRENT_VERSION {
+                return Err(PinocchioError::PoolAlreadyMigrated.into());
+            }
+        } else if !PoolState::LEGACY_LENS.contains(&old_len) {
+            return Err(PinocchioError::InvalidAccountData.into());
+        }
+
+        // Every layout shares the prefix up to the bumps
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        if pool_state_data[offset_of!(PoolState, discriminator)] == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        let seed_offset = offset_of!(PoolState, seed);
+        let seed_bytes: [u8; 8] = pool_state_data[seed_offset..seed_offset + 8]
+            .try_into()
+            .unwrap();
+        ProgramAccount::verify(
+            &[Seed::from(b"lst_pool"), Seed::from(&seed_bytes)],
+            accounts.pool_state,
+            pool_state_data[offset_of!(PoolState, bump)],
+        )?;
+
+        let key_at = |offset: usize| &pool_state_data[offset..offset + 32];
+        if accounts.pool_stake.key().as_ref() != key_at(offset_of!(PoolState, stake_account)) {
+            return Err(PinocchioError::InvalidPoolStake.into());
+        }
+        if accounts.reserve_stake.key().as_ref() != key_at(offset_of!(PoolState, reserve_stake)) {
+            return Err(PinocchioError::InvalidReserveStake.into());
+        }
+
+        // Layouts from before the cached balance valued the pool from its
+        // stake and reserve lamports on every deposit and withdrawal
+        let total_lamports = if old_len <= offset_of!(PoolState, total_lamports) {
+            Some(
+                accounts
+                    .pool_stake
+                    .lamports()
+                    .checked_add(accounts.reserve_stake.lamports())
+                    .ok_or(ProgramError::ArithmeticOverflow)?,
+            )
+        } else {
+            None
+        };
+
+        Ok(Self {
+            accounts,
+            old_len,
+            total_lamports,
+        })
+    }
+}
+
+impl<'a> MigratePool<'a> {
+    pub const DISCRIMINATOR: u8 = 17;
+
+    /// Upgrades the pool state to the current layout in place. Permissionless:
+    /// fields missing from the old layout only get their defaults, and the
+    /// payer covers any extra rent.
+    pub fn process(&self) -> ProgramResult {
+        if self.old_len != PoolState::LEN {
+            let rent_due = Rent::get()?
+                .minimum_balance(PoolState::LEN)
+                .saturating_sub(self.accounts.pool_state.lamports());
+
+            if rent_due > 0 {
+                Transfer {
+                    from: self.accounts.payer,
+                    to: self.accounts.pool_state,
+                    lamports: rent_due,
+                }
+                .invoke()?;
+            }
+
+            // Zero-extends, so the new fields start out as their defaults
+            self.accounts.pool_state.resize(PoolState::LEN)?;
+        }
+
+        let tracks_reserve = self.old_len > offset_of!(PoolState, reserve_lamports);
+        if !tracks_reserve {
+            self.sweep_pool_stake()?;
+        }
+
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+
+        // The baseline layout predates fees. They start at zero, and the fee
+        // account is the manager's token account that `Initialize` created
+        if self.old_len <= offset_of!(PoolState, manager_fee_account) {
+            let (manager_fee_account, _) = find_program_address(
+                &[
+                    pool_state.manager.as_ref(),
+                    pinocchio_token::ID.as_ref(),
+                    pool_state.lst_mint.as_ref(),
+                ],
+                &pinocchio_associated_token_account::ID,
+            );
+            pool_state.manager_fee_account = manager_fee_account;
+            pool_state.deposit_fee_bps = 0;
+            pool_state.withdrawal_fee_bps = 0;
+            pool_state.reward_fee_bps = 0;
+            pool_state.sol_withdrawal_fee_bps = 0;
+        }
+
+        // Counts as updated this epoch, so the first UpdatePoolBalance only
+        // charges the reward fee on what the pool earns from here on
+        if let Some(total_lamports) = self.total_lamports {
+            pool_state.total_lamports = total_lamports;
+            pool_state.last_update_epoch = Clock::get()?.epoch;
+        }
+
+        // The reserve balance was all counted until now, and is tracked from
+        // here on
+        if !tracks_reserve {
+            pool_state.reserve_lamports = self.accounts.reserve_stake.lamports();
+        }
+
+        // Layouts from before the staker role gave the authority every right
+        if self.old_len <= offset_of!(PoolState, staker) {
+            pool_state.staker = pool_state.manager;
+        }
+
+        // Pools from before Token-2022 support all use SPL Token
+        if pool_state.token_program == Pubkey::default() {
+            pool_state.token_program = pinocchio_token::ID;
+        }
+
+        // Deposit limits, the deposit authority and the rate history keep
+        // their zero defaults: no limits, a public pool and no history
+        pool_state.version = PoolState::CURRENT_VERSION;
+
+        msg!("Pool state migrated");
+        Ok(())
+    }
+
+    /// The original MergeReserve left rent reserves undelegated in the pool
+    /// stake, which the pool counted until now. Moving them to the reserve
+    /// keeps them in the pool value, now that stakes only count their
+    /// delegation.
+    fn sweep_pool_stake(&self) -> ProgramResult {
+        let undelegated = self
+            .accounts
+            .pool_stake
+            .lamports()
+            .saturating_sub(stake_value(self.accounts.pool_stake)?);
+
+        if undelegated == 0 {
+            return Ok(());
+        }
+
+        let (seed_bytes, bump) = {
+            let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
+            let pool_state = PoolState::load(&pool_state_data)?;
+            (pool_state.seed.to_le_bytes(), pool_state.bump)
+        };
+        let bump_binding = [bump];
+        let pool_seeds = [
+            Seed::from(b"lst_pool"),
+            Seed::from(&seed_bytes),
+            Seed::from(&bump_binding),
+        ];
+
+        withdraw_stake(
+            self.accounts.pool_stake,
+            self.accounts.reserve_stake,
+            self.accounts.pool_state,
+            self.accounts.clock,
+            self.accounts.stake_history,
+            &pool_seeds,
+            undelegated,
+        )
+    }
+}
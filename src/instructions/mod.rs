@@ -4,10 +4,58 @@ pub mod initialize_reserve;
 pub mod merge_reserve;
 pub mod withdraw;
 pub mod withdraw_complete;
+pub mod update_pool_balance;
+pub mod add_validator;
+pub mod remove_validator;
+pub mod redelegate;
+pub mod complete_redelegate;
+pub mod deposit_stake_account;
+pub mod withdraw_sol;
+pub mod set_manager;
+pub mod accept_manager;
+pub mod set_staker;
+pub mod set_status;
+pub mod migrate_pool;
+pub mod update_token_metadata;
+pub mod update_config;
+pub mod set_deposit_authority;
+pub mod transfer_withdraw_ticket;
+pub mod initialize_withdraw_ledger;
+pub mod close_withdraw_ledger;
+pub mod initialize_rate_history;
+pub mod get_average_rate;
+pub mod get_exchange_rate;
+pub mod set_fees;
+pub mod prune_withdraw_ledger;
+pub mod increase_validator_stake;
 
 pub use initialize_stake::*;
 pub use deposit_stake::*;
 pub use initialize_reserve::*;
 pub use merge_reserve::*;
 pub use withdraw::*;
-pub use withdraw_complete::*;
\ No newline at end of file
+pub use withdraw_complete::*;
+pub use update_pool_balance::*;
+pub use add_validator::*;
+pub use remove_validator::*;
+pub use redelegate::*;
+pub use complete_redelegate::*;
+pub use deposit_stake_account::*;
+pub use withdraw_sol::*;
+pub use set_manager::*;
+pub use accept_manager::*;
+pub use set_staker::*;
+pub use set_status::*;
+pub use migrate_pool::*;
+pub use update_token_metadata::*;
+pub use update_config::*;
+pub use set_deposit_authority::*;
+pub use transfer_withdraw_ticket::*;
+pub use initialize_withdraw_ledger::*;
+pub use close_withdraw_ledger::*;
+pub use initialize_rate_history::*;
+pub use get_average_rate::*;
+pub use get_exchange_rate::*;
+pub use set_fees::*;
+pub use prune_withdraw_ledger::*;
+pub use increase_validator_stake::*;
\ No newline at end of file
@@ -0,0 +1,37 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{PinocchioError, SignerAccount, WithdrawLedger};
+
+pub struct PruneWithdrawLedgerAccounts<'a> {
+    /// Owner of the ledger; receives the freed rent.
+    pub owner: &'a AccountInfo,
+    pub ledger: &'a AccountInfo,
+    /// Withdraw stakes listed in the ledger that no longer exist
+    pub withdraw_stakes: &'a [AccountInfo],
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for PruneWithdrawLedgerAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [owner, ledger, withdraw_stakes @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        if withdraw_stakes.is_empty() {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        }
+
+        SignerAccount::check(owner)?;
+
+        if !WithdrawLedger::is_ledger(ledger) {
+            return Err(PinocchioError::InvalidWithdrawLedger.into());
+        }
+
+        Ok(Self {
+            owner,
+            ledger,
+            withdraw_stakes,
+        })
+    }
+}
@@ -0,0 +1,87 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    msg,
+    program_error::ProgramError,
+    sysvars::{Sysvar, rent::Rent},
+};
+
+use crate::{PinocchioError, PruneWithdrawLedgerAccounts, WithdrawLedger};
+
+pub struct PruneWithdrawLedger<'a> {
+    pub accounts: PruneWithdrawLedgerAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for PruneWithdrawLedger<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = PruneWithdrawLedgerAccounts::try_from(accounts)?;
+
+        let ledger_data = accounts.ledger.try_borrow_data()?;
+        let (ledger, entries) = WithdrawLedger::load(&ledger_data)?;
+
+        if accounts.owner.key() != &ledger.owner {
+            return Err(PinocchioError::InvalidAuthority.into());
+        }
+
+        for withdraw_stake in accounts.withdraw_stakes {
+            if !entries
+                .iter()
+                .any(|entry| &entry.withdraw_stake == withdraw_stake.key())
+            {
+                return Err(PinocchioError::WithdrawLedgerEntryNotFound.into());
+            }
+
+            // Claimed stakes are drained and closed by the stake program
+            if withdraw_stake.lamports() != 0 {
+                return Err(PinocchioError::WithdrawStakeNotClaimed.into());
+            }
+        }
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> PruneWithdrawLedger<'a> {
+    pub const DISCRIMINATOR: u8 = 28;
+
+    /// Drops the entries of withdraw stakes that were claimed without the
+    /// ledger, such as through a ticket, shrinks the ledger and returns the
+    /// freed rent to its owner.
+    pub fn process(&self) -> ProgramResult {
+        let new_count = {
+            let mut ledger_data = self.accounts.ledger.try_borrow_mut_data()?;
+            let (ledger, entries) = WithdrawLedger::load_mut(&mut ledger_data)?;
+
+            let mut count = entries.len();
+            for withdraw_stake in self.accounts.withdraw_stakes {
+                // A stake passed twice was already removed
+                if let Some(index) = entries[..count]
+                    .iter()
+                    .position(|entry| &entry.withdraw_stake == withdraw_stake.key())
+                {
+                    entries[index] = entries[count - 1];
+                    count -= 1;
+                }
+            }
+
+            ledger.count = count as u32;
+            count
+        };
+
+        let new_space = WithdrawLedger::space(new_count);
+        self.accounts.ledger.resize(new_space)?;
+
+        let refund = self
+            .accounts
+            .ledger
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(new_space));
+        *self.accounts.ledger.try_borrow_mut_lamports()? -= refund;
+        *self.accounts.owner.try_borrow_mut_lamports()? += refund;
+
+        msg!("Withdraw ledger pruned");
+        Ok(())
+    }
+}
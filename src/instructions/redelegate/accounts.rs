@@ -0,0 +1,52 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{PinocchioError, ProgramAccount, SignerAccount, constants::VOTE_PROGRAM_ID};
+
+pub struct RedelegateAccounts<'a> {
+    pub staker: &'a AccountInfo,
+    pub pool_state: &'a AccountInfo,
+    pub pool_stake: &'a AccountInfo,
+    pub new_validator_vote: &'a AccountInfo,
+    pub clock: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+    /// Required once the pool has a validator list.
+    pub validator_list: Option<&'a AccountInfo>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RedelegateAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [
+            staker,
+            pool_state,
+            pool_stake,
+            new_validator_vote,
+            clock,
+            stake_program,
+            remaining @ ..,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(staker)?;
+        ProgramAccount::check(pool_state)?;
+
+        ProgramAccount::check_stake_program(stake_program)?;
+
+        if new_validator_vote.owner() != &VOTE_PROGRAM_ID {
+            return Err(PinocchioError::InvalidVoteAccount.into());
+        }
+
+        Ok(Self {
+            staker,
+            pool_state,
+            pool_stake,
+            new_validator_vote,
+            clock,
+            stake_program,
+            validator_list: remaining.first(),
+        })
+    }
+}
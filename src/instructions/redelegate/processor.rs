@@ -0,0 +1,121 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, msg, program_error::ProgramError,
+};
+
+use crate::{
+    PinocchioError, PoolState, ProgramAccount, RedelegateAccounts, ValidatorList,
+    deactivate_stake,
+};
+
+pub struct Redelegate<'a> {
+    pub accounts: RedelegateAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for Redelegate<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = RedelegateAccounts::try_from(accounts)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[
+                Seed::from(b"lst_pool"),
+                Seed::from(&seed_bytes),
+            ],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if accounts.staker.key() != &pool_state.staker {
+            return Err(PinocchioError::InvalidAuthority.into());
+        }
+
+        if accounts.pool_stake.key() != &pool_state.stake_account {
+            return Err(PinocchioError::InvalidPoolStake.into());
+        }
+
+        if pool_state.is_redelegating() {
+            return Err(PinocchioError::RedelegationInProgress.into());
+        }
+
+        if accounts.new_validator_vote.key() == &pool_state.validator_vote {
+            return Err(PinocchioError::ValidatorAlreadyAdded.into());
+        }
+
+        // Transient stakes of other validators are all listed
+        let mut primary_transient_lamports = pool_state.transient_lamports;
+
+        if pool_state.has_validator_list() {
+            let validator_list = accounts
+                .validator_list
+                .ok_or(PinocchioError::MissingValidatorList)?;
+
+            if validator_list.key() != &pool_state.validator_list {
+                return Err(PinocchioError::InvalidValidatorList.into());
+            }
+
+            let list_data = validator_list.try_borrow_data()?;
+            let (_, entries) = ValidatorList::load(&list_data)?;
+
+            if entries
+                .iter()
+                .any(|entry| &entry.vote_account == accounts.new_validator_vote.key())
+            {
+                return Err(PinocchioError::ValidatorAlreadyAdded.into());
+            }
+
+            let listed: u64 = entries.iter().map(|entry| entry.transient_lamports).sum();
+            primary_transient_lamports = primary_transient_lamports.saturating_sub(listed);
+        }
+
+        // Transient stakes stay delegated to the current validator
+        if primary_transient_lamports != 0 {
+            return Err(PinocchioError::TransientStakeOutstanding.into());
+        }
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> Redelegate<'a> {
+    pub const DISCRIMINATOR: u8 = 9;
+
+    /// Deactivates the pool stake; `CompleteRedelegate` delegates it to the new
+    /// vote account once the cooldown has passed.
+    pub fn process(&self) -> ProgramResult {
+        {
+            let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
+            let pool_state = PoolState::load(&pool_state_data)?;
+
+            let seed_bytes = pool_state.seed.to_le_bytes();
+            let bump_binding = [pool_state.bump];
+            let pool_seeds = [
+                Seed::from(b"lst_pool"),
+                Seed::from(&seed_bytes),
+                Seed::from(&bump_binding),
+            ];
+
+            deactivate_stake(
+                self.accounts.pool_stake,
+                self.accounts.clock,
+                self.accounts.pool_state,
+                &pool_seeds,
+            )?;
+        }
+
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+        pool_state.pending_validator_vote = *self.accounts.new_validator_vote.key();
+
+        msg!("Redelegation started");
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{PinocchioError, ProgramAccount, SignerAccount};
+
+pub struct RemoveValidatorAccounts<'a> {
+    pub staker: &'a AccountInfo,
+    pub pool_state: &'a AccountInfo,
+    pub validator_list: &'a AccountInfo,
+    pub validator_stake: &'a AccountInfo,
+    pub reserve_stake: &'a AccountInfo,
+    pub clock: &'a AccountInfo,
+    pub stake_history: &'a AccountInfo,
+    pub stake_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RemoveValidatorAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [
+            staker,
+            pool_state,
+            validator_list,
+            validator_stake,
+            reserve_stake,
+            clock,
+            stake_history,
+            stake_program,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(staker)?;
+        ProgramAccount::check(pool_state)?;
+
+        if !validator_list.is_owned_by(&crate::ID) {
+            return Err(PinocchioError::InvalidOwner.into());
+        }
+
+        ProgramAccount::check_stake_program(stake_program)?;
+
+        Ok(Self {
+            staker,
+            pool_state,
+            validator_list,
+            validator_stake,
+            reserve_stake,
+            clock,
+            stake_history,
+            stake_program,
+        })
+    }
+}
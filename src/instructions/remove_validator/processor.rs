@@ -0,0 +1,173 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    msg,
+    program_error::ProgramError,
+    sysvars::{Sysvar, rent::Rent},
+};
+
+use crate::{
+    PinocchioError, PoolState, ProgramAccount, RemoveValidatorAccounts, ValidatorList,
+    ValidatorStatus, deactivate_stake, stake_value, withdraw_stake,
+};
+
+pub struct RemoveValidator<'a> {
+    pub accounts: RemoveValidatorAccounts<'a>,
+    pub index: usize,
+    pub status: ValidatorStatus,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for RemoveValidator<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = RemoveValidatorAccounts::try_from(accounts)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[
+                Seed::from(b"lst_pool"),
+                Seed::from(&seed_bytes),
+            ],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if accounts.staker.key() != &pool_state.staker {
+            return Err(PinocchioError::InvalidAuthority.into());
+        }
+
+        if accounts.validator_list.key() != &pool_state.validator_list {
+            return Err(PinocchioError::InvalidValidatorList.into());
+        }
+
+        if accounts.reserve_stake.key() != &pool_state.reserve_stake {
+            return Err(PinocchioError::InvalidReserveStake.into());
+        }
+
+        if accounts.validator_stake.key() == &pool_state.stake_account {
+            return Err(PinocchioError::CannotRemovePrimaryValidator.into());
+        }
+
+        let list_data = accounts.validator_list.try_borrow_data()?;
+        let (_, entries) = ValidatorList::load(&list_data)?;
+
+        let index = entries
+            .iter()
+            .position(|entry| &entry.stake_account == accounts.validator_stake.key())
+            .ok_or(PinocchioError::ValidatorNotFound)?;
+
+        if index == 0 {
+            return Err(PinocchioError::CannotRemovePrimaryValidator.into());
+        }
+
+        let status = entries[index].status()?;
+
+        // A transient stake from `IncreaseValidatorStake` may still need to
+        // merge into the validator's stake
+        if status == ValidatorStatus::Active && entries[index].transient_lamports != 0 {
+            return Err(PinocchioError::TransientStakeOutstanding.into());
+        }
+
+        Ok(Self {
+            accounts,
+            index,
+            status,
+        })
+    }
+}
+
+impl<'a> RemoveValidator<'a> {
+    pub const DISCRIMINATOR: u8 = 8;
+
+    /// First call deactivates the validator's stake; once it has cooled down a
+    /// second call moves its lamports to the reserve and drops the entry.
+    pub fn process(&self) -> ProgramResult {
+        let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        let bump_binding = [pool_state.bump];
+        let pool_seeds = [
+            Seed::from(b"lst_pool"),
+            Seed::from(&seed_bytes),
+            Seed::from(&bump_binding),
+        ];
+
+        match self.status {
+            ValidatorStatus::Active => {
+                deactivate_stake(
+                    self.accounts.validator_stake,
+                    self.accounts.clock,
+                    self.accounts.pool_state,
+                    &pool_seeds,
+                )?;
+
+                let mut list_data = self.accounts.validator_list.try_borrow_mut_data()?;
+                let (_, entries) = ValidatorList::load_mut(&mut list_data)?;
+                entries[self.index].status = ValidatorStatus::Deactivating as u8;
+
+                msg!("Validator deactivating");
+            }
+            ValidatorStatus::Deactivating => {
+                // Lamports donated to the stake land in the reserve uncounted
+                let value = stake_value(self.accounts.validator_stake)?;
+
+                withdraw_stake(
+                    self.accounts.validator_stake,
+                    self.accounts.reserve_stake,
+                    self.accounts.pool_state,
+                    self.accounts.clock,
+                    self.accounts.stake_history,
+                    &pool_seeds,
+                    self.accounts.validator_stake.lamports(),
+                )?;
+
+                self.remove_entry()?;
+
+                drop(pool_state_data);
+                let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+                PoolState::load_mut(&mut pool_state_data)?.credit_reserve(value)?;
+
+                msg!("Validator removed");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Swap-removes the entry, shrinks the list and refunds the freed rent.
+    fn remove_entry(&self) -> ProgramResult {
+        let new_count = {
+            let mut list_data = self.accounts.validator_list.try_borrow_mut_data()?;
+            let (list, entries) = ValidatorList::load_mut(&mut list_data)?;
+
+            let last = entries.len() - 1;
+            entries[self.index] = entries[last];
+            list.count -= 1;
+            list.count as usize
+        };
+
+        let new_space = ValidatorList::space(new_count);
+        self.accounts.validator_list.resize(new_space)?;
+
+        let refund = self
+            .accounts
+            .validator_list
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(new_space));
+
+        *self.accounts.validator_list.try_borrow_mut_lamports()? -= refund;
+        *self.accounts.staker.try_borrow_mut_lamports()? += refund;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{ProgramAccount, SignerAccount};
+
+pub struct SetDepositAuthorityAccounts<'a> {
+    pub manager: &'a AccountInfo,
+    pub pool_state: &'a AccountInfo,
+    /// Omitted to make the pool public again
+    pub new_deposit_authority: Option<&'a AccountInfo>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetDepositAuthorityAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [manager, pool_state, remaining @ ..] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(manager)?;
+        ProgramAccount::check(pool_state)?;
+
+        Ok(Self {
+            manager,
+            pool_state,
+            new_deposit_authority: remaining.first(),
+        })
+    }
+}
@@ -0,0 +1,57 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{PinocchioError, PoolState, ProgramAccount, SetDepositAuthorityAccounts};
+
+pub struct SetDepositAuthority<'a> {
+    pub accounts: SetDepositAuthorityAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetDepositAuthority<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = SetDepositAuthorityAccounts::try_from(accounts)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[Seed::from(b"lst_pool"), Seed::from(&seed_bytes)],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if accounts.manager.key() != &pool_state.manager {
+            return Err(PinocchioError::InvalidAuthority.into());
+        }
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> SetDepositAuthority<'a> {
+    pub const DISCRIMINATOR: u8 = 20;
+
+    /// Makes `Deposit` and `DepositStake` require the new authority's
+    /// signature, or lifts the requirement when none is passed.
+    pub fn process(&self) -> ProgramResult {
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+
+        pool_state.deposit_authority = self
+            .accounts
+            .new_deposit_authority
+            .map_or(Pubkey::default(), |authority| *authority.key());
+
+        msg!("Deposit authority updated");
+        Ok(())
+    }
+}
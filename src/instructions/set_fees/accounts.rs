@@ -0,0 +1,28 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{ProgramAccount, SignerAccount};
+
+pub struct SetFeesAccounts<'a> {
+    pub manager: &'a AccountInfo,
+    pub pool_state: &'a AccountInfo,
+    pub manager_fee_account: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetFeesAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [manager, pool_state, manager_fee_account] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(manager)?;
+        ProgramAccount::check(pool_state)?;
+
+        Ok(Self {
+            manager,
+            pool_state,
+            manager_fee_account,
+        })
+    }
+}
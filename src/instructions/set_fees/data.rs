@@ -0,0 +1,41 @@
+use pinocchio::program_error::ProgramError;
+
+use crate::{MAX_FEE_BPS, PinocchioError};
+
+#[repr(C, packed)]
+pub struct SetFeesInstructionData {
+    pub deposit_fee_bps: u16,
+    pub withdrawal_fee_bps: u16,
+    pub reward_fee_bps: u16,
+    pub sol_withdrawal_fee_bps: u16,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetFeesInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<SetFeesInstructionData>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let deposit_fee_bps = u16::from_le_bytes(data[0..2].try_into().unwrap());
+        let withdrawal_fee_bps = u16::from_le_bytes(data[2..4].try_into().unwrap());
+        let reward_fee_bps = u16::from_le_bytes(data[4..6].try_into().unwrap());
+        let sol_withdrawal_fee_bps = u16::from_le_bytes(data[6..8].try_into().unwrap());
+
+        if deposit_fee_bps > MAX_FEE_BPS
+            || withdrawal_fee_bps > MAX_FEE_BPS
+            || reward_fee_bps > MAX_FEE_BPS
+            || sol_withdrawal_fee_bps > MAX_FEE_BPS
+        {
+            return Err(PinocchioError::InvalidFee.into());
+        }
+
+        Ok(Self {
+            deposit_fee_bps,
+            withdrawal_fee_bps,
+            reward_fee_bps,
+            sol_withdrawal_fee_bps,
+        })
+    }
+}
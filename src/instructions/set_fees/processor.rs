@@ -0,0 +1,72 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, msg, program_error::ProgramError,
+};
+
+use crate::{
+    PinocchioError, PoolState, ProgramAccount, SetFeesAccounts, SetFeesInstructionData, Token,
+};
+
+pub struct SetFees<'a> {
+    pub accounts: SetFeesAccounts<'a>,
+    pub instruction_data: SetFeesInstructionData,
+}
+
+impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for SetFees<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&[u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = SetFeesAccounts::try_from(accounts)?;
+        let instruction_data = SetFeesInstructionData::try_from(data)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[Seed::from(b"lst_pool"), Seed::from(&seed_bytes)],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if accounts.manager.key() != &pool_state.manager {
+            return Err(PinocchioError::InvalidAuthority.into());
+        }
+
+        // Fees are paid in LST, so any LST account of the pool's token
+        // program will do
+        Token::check(accounts.manager_fee_account, &pool_state.token_program())?;
+        let fee_account_data = accounts.manager_fee_account.try_borrow_data()?;
+        if fee_account_data[0..32] != pool_state.lst_mint {
+            return Err(PinocchioError::InvalidManagerFeeAccount.into());
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SetFees<'a> {
+    pub const DISCRIMINATOR: u8 = 27;
+
+    /// Replaces the four fees and the account they are paid to. The new
+    /// reward fee applies to rewards counted by the next `UpdatePoolBalance`.
+    pub fn process(&self) -> ProgramResult {
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+
+        pool_state.deposit_fee_bps = self.instruction_data.deposit_fee_bps;
+        pool_state.withdrawal_fee_bps = self.instruction_data.withdrawal_fee_bps;
+        pool_state.reward_fee_bps = self.instruction_data.reward_fee_bps;
+        pool_state.sol_withdrawal_fee_bps = self.instruction_data.sol_withdrawal_fee_bps;
+        pool_state.manager_fee_account = *self.accounts.manager_fee_account.key();
+
+        msg!("Pool fees updated");
+        Ok(())
+    }
+}
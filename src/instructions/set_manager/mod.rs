@@ -0,0 +1,5 @@
+mod accounts;
+mod processor;
+
+pub use accounts::*;
+pub use processor::*;
@@ -0,0 +1,60 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{PinocchioError, PoolState, ProgramAccount, SetManagerAccounts};
+
+pub struct SetManager<'a> {
+    pub accounts: SetManagerAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetManager<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = SetManagerAccounts::try_from(accounts)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[Seed::from(b"lst_pool"), Seed::from(&seed_bytes)],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if accounts.manager.key() != &pool_state.manager {
+            return Err(PinocchioError::InvalidAuthority.into());
+        }
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> SetManager<'a> {
+    pub const DISCRIMINATOR: u8 = 13;
+
+    /// Proposes `new_manager`; the transfer only takes effect once it signs
+    /// `AcceptManager`, so a mistyped key cannot lock the pool. Proposing the
+    /// current manager cancels a pending transfer.
+    pub fn process(&self) -> ProgramResult {
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+
+        if self.accounts.new_manager.key() == &pool_state.manager {
+            pool_state.pending_manager = Pubkey::default();
+            msg!("Manager transfer cancelled");
+        } else {
+            pool_state.pending_manager = *self.accounts.new_manager.key();
+            msg!("Manager transfer proposed");
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{ProgramAccount, SignerAccount};
+
+pub struct SetStakerAccounts<'a> {
+    /// The manager or the current staker.
+    pub signer: &'a AccountInfo,
+    pub pool_state: &'a AccountInfo,
+    pub new_staker: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetStakerAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [signer, pool_state, new_staker] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(signer)?;
+        ProgramAccount::check(pool_state)?;
+
+        Ok(Self {
+            signer,
+            pool_state,
+            new_staker,
+        })
+    }
+}
@@ -0,0 +1,56 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, msg, program_error::ProgramError,
+};
+
+use crate::{PinocchioError, PoolState, ProgramAccount, SetStakerAccounts};
+
+pub struct SetStaker<'a> {
+    pub accounts: SetStakerAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for SetStaker<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = SetStakerAccounts::try_from(accounts)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[Seed::from(b"lst_pool"), Seed::from(&seed_bytes)],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        // The manager can always take back a compromised staker role
+        if accounts.signer.key() != &pool_state.manager
+            && accounts.signer.key() != &pool_state.staker
+        {
+            return Err(PinocchioError::InvalidAuthority.into());
+        }
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> SetStaker<'a> {
+    pub const DISCRIMINATOR: u8 = 15;
+
+    /// Replaces the staker right away; it only controls where the pool
+    /// delegates, never its funds or configuration.
+    pub fn process(&self) -> ProgramResult {
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+
+        pool_state.staker = *self.accounts.new_staker.key();
+
+        msg!("Staker updated");
+        Ok(())
+    }
+}
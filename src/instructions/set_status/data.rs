@@ -0,0 +1,25 @@
+use pinocchio::program_error::ProgramError;
+
+use crate::{PAUSE_ALL, PinocchioError};
+
+#[repr(C, packed)]
+pub struct SetStatusInstructionData {
+    /// `PAUSE_*` bits; replaces the current status
+    pub status: u8,
+}
+
+impl<'a> TryFrom<&'a [u8]> for SetStatusInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let [status] = data else {
+            return Err(ProgramError::InvalidInstructionData);
+        };
+
+        if status & !PAUSE_ALL != 0 {
+            return Err(PinocchioError::InvalidStatus.into());
+        }
+
+        Ok(Self { status: *status })
+    }
+}
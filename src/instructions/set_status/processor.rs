@@ -0,0 +1,59 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, msg, program_error::ProgramError,
+};
+
+use crate::{
+    PinocchioError, PoolState, ProgramAccount, SetStatusAccounts, SetStatusInstructionData,
+};
+
+pub struct SetStatus<'a> {
+    pub accounts: SetStatusAccounts<'a>,
+    pub instruction_data: SetStatusInstructionData,
+}
+
+impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for SetStatus<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&[u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = SetStatusAccounts::try_from(accounts)?;
+        let instruction_data = SetStatusInstructionData::try_from(data)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[Seed::from(b"lst_pool"), Seed::from(&seed_bytes)],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if accounts.manager.key() != &pool_state.manager {
+            return Err(PinocchioError::InvalidAuthority.into());
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> SetStatus<'a> {
+    pub const DISCRIMINATOR: u8 = 16;
+
+    /// Pauses or resumes deposits, withdrawals and cranks independently.
+    pub fn process(&self) -> ProgramResult {
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+
+        pool_state.status = self.instruction_data.status;
+
+        msg!("Pool status updated");
+        Ok(())
+    }
+}
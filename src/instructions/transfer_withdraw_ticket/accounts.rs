@@ -0,0 +1,32 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{PinocchioError, SignerAccount};
+
+pub struct TransferWithdrawTicketAccounts<'a> {
+    /// Current owner of the ticket.
+    pub owner: &'a AccountInfo,
+    pub ticket: &'a AccountInfo,
+    pub new_owner: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for TransferWithdrawTicketAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [owner, ticket, new_owner] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(owner)?;
+
+        if !ticket.is_owned_by(&crate::ID) {
+            return Err(PinocchioError::InvalidWithdrawTicket.into());
+        }
+
+        Ok(Self {
+            owner,
+            ticket,
+            new_owner,
+        })
+    }
+}
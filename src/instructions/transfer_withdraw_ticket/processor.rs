@@ -0,0 +1,44 @@
+use pinocchio::{ProgramResult, account_info::AccountInfo, msg, program_error::ProgramError};
+
+use crate::{PinocchioError, TransferWithdrawTicketAccounts, WithdrawTicket};
+
+pub struct TransferWithdrawTicket<'a> {
+    pub accounts: TransferWithdrawTicketAccounts<'a>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for TransferWithdrawTicket<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = TransferWithdrawTicketAccounts::try_from(accounts)?;
+
+        let ticket_data = accounts.ticket.try_borrow_data()?;
+        let ticket = WithdrawTicket::load(&ticket_data)?;
+
+        if ticket.discriminator != WithdrawTicket::DISCRIMINATOR {
+            return Err(PinocchioError::InvalidWithdrawTicket.into());
+        }
+
+        if accounts.owner.key() != &ticket.owner {
+            return Err(PinocchioError::InvalidAuthority.into());
+        }
+
+        Ok(Self { accounts })
+    }
+}
+
+impl<'a> TransferWithdrawTicket<'a> {
+    pub const DISCRIMINATOR: u8 = 21;
+
+    /// Hands the pending claim to `new_owner`, who can then present the
+    /// ticket to `WithdrawComplete`. Works in any pool state, paused or not.
+    pub fn process(&self) -> ProgramResult {
+        let mut ticket_data = self.accounts.ticket.try_borrow_mut_data()?;
+        let ticket = WithdrawTicket::load_mut(&mut ticket_data)?;
+
+        ticket.owner = *self.accounts.new_owner.key();
+
+        msg!("Withdraw ticket transferred");
+        Ok(())
+    }
+}
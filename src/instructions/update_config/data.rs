@@ -0,0 +1,37 @@
+use pinocchio::program_error::ProgramError;
+
+use crate::PinocchioError;
+
+#[repr(C, packed)]
+pub struct UpdateConfigInstructionData {
+    /// Cap on the pool's total value; 0 for none
+    pub max_total_lamports: u64,
+    /// Largest single deposit; 0 for none
+    pub max_deposit_lamports: u64,
+    /// Smallest single deposit; 0 for none
+    pub min_deposit_lamports: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for UpdateConfigInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        if data.len() != size_of::<UpdateConfigInstructionData>() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let max_total_lamports = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let max_deposit_lamports = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        let min_deposit_lamports = u64::from_le_bytes(data[16..24].try_into().unwrap());
+
+        if max_deposit_lamports != 0 && min_deposit_lamports > max_deposit_lamports {
+            return Err(PinocchioError::InvalidDepositLimits.into());
+        }
+
+        Ok(Self {
+            max_total_lamports,
+            max_deposit_lamports,
+            min_deposit_lamports,
+        })
+    }
+}
@@ -0,0 +1,62 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, msg, program_error::ProgramError,
+};
+
+use crate::{
+    PinocchioError, PoolState, ProgramAccount, UpdateConfigAccounts, UpdateConfigInstructionData,
+};
+
+pub struct UpdateConfig<'a> {
+    pub accounts: UpdateConfigAccounts<'a>,
+    pub instruction_data: UpdateConfigInstructionData,
+}
+
+impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for UpdateConfig<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&[u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = UpdateConfigAccounts::try_from(accounts)?;
+        let instruction_data = UpdateConfigInstructionData::try_from(data)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[Seed::from(b"lst_pool"), Seed::from(&seed_bytes)],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if accounts.manager.key() != &pool_state.manager {
+            return Err(PinocchioError::InvalidAuthority.into());
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> UpdateConfig<'a> {
+    pub const DISCRIMINATOR: u8 = 19;
+
+    /// Replaces the deposit limits. Lowering the cap below the current pool
+    /// value only blocks new deposits.
+    pub fn process(&self) -> ProgramResult {
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+
+        pool_state.max_total_lamports = self.instruction_data.max_total_lamports;
+        pool_state.max_deposit_lamports = self.instruction_data.max_deposit_lamports;
+        pool_state.min_deposit_lamports = self.instruction_data.min_deposit_lamports;
+
+        msg!("Pool config updated");
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{Mint, ProgramAccount, RateHistory, Token};
+
+pub struct UpdatePoolBalanceAccounts<'a> {
+    pub pool_state: &'a AccountInfo,
+    pub pool_stake: &'a AccountInfo,
+    pub reserve_stake: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub manager_fee_account: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    /// Required once the pool has a validator list.
+    pub validator_list: Option<&'a AccountInfo>,
+    /// Stake accounts of validator list entries 1.., in list order.
+    pub validator_stakes: &'a [AccountInfo],
+    /// Required once the pool has a rate history; always the last account.
+    pub rate_history: Option<&'a AccountInfo>,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdatePoolBalanceAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [
+            pool_state,
+            pool_stake,
+            reserve_stake,
+            lst_mint,
+            manager_fee_account,
+            token_program,
+            remaining @ ..,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        ProgramAccount::check(pool_state)?;
+        ProgramAccount::check_token_program(token_program)?;
+        Mint::check(lst_mint, token_program.key())?;
+        Token::check(manager_fee_account, token_program.key())?;
+
+        let (rate_history, remaining) = match remaining.split_last() {
+            Some((last, rest)) if RateHistory::is_history(last) => (Some(last), rest),
+            _ => (None, remaining),
+        };
+
+        let (validator_list, validator_stakes) = match remaining.split_first() {
+            Some((validator_list, validator_stakes)) => (Some(validator_list), validator_stakes),
+            None => (None, remaining),
+        };
+
+        Ok(Self {
+            pool_state,
+            pool_stake,
+            reserve_stake,
+            lst_mint,
+            manager_fee_account,
+            token_program,
+            validator_list,
+            validator_stakes,
+            rate_history,
+        })
+    }
+}
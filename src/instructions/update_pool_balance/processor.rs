@@ -0,0 +1,226 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    msg,
+    program_error::ProgramError,
+    sysvars::{Sysvar, clock::Clock},
+};
+
+use crate::{
+    PinocchioError, PoolState, ProgramAccount, RateHistory, UpdatePoolBalanceAccounts,
+    ValidatorList, calculate_fee, mint_to, stake_value,
+};
+
+pub struct UpdatePoolBalance<'a> {
+    pub accounts: UpdatePoolBalanceAccounts<'a>,
+    pub epoch: u64,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdatePoolBalance<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let accounts = UpdatePoolBalanceAccounts::try_from(accounts)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        if pool_state.cranks_paused() {
+            return Err(PinocchioError::CranksPaused.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[
+                Seed::from(b"lst_pool"),
+                Seed::from(&seed_bytes),
+            ],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if accounts.pool_stake.key() != &pool_state.stake_account {
+            return Err(PinocchioError::InvalidPoolStake.into());
+        }
+
+        if accounts.reserve_stake.key() != &pool_state.reserve_stake {
+            return Err(PinocchioError::InvalidReserveStake.into());
+        }
+
+        if accounts.lst_mint.key() != &pool_state.lst_mint {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        if accounts.token_program.key() != &pool_state.token_program() {
+            return Err(PinocchioError::InvalidProgram.into());
+        }
+
+        if accounts.manager_fee_account.key() != &pool_state.manager_fee_account {
+            return Err(PinocchioError::InvalidManagerFeeAccount.into());
+        }
+
+        let epoch = Clock::get()?.epoch;
+        if pool_state.last_update_epoch >= epoch {
+            return Err(PinocchioError::PoolBalanceAlreadyUpdated.into());
+        }
+
+        // Every validator stake must be counted, otherwise the rate can be pushed down
+        match accounts.validator_list {
+            Some(validator_list) if pool_state.has_validator_list() => {
+                if validator_list.key() != &pool_state.validator_list {
+                    return Err(PinocchioError::InvalidValidatorList.into());
+                }
+
+                if !validator_list.is_owned_by(&crate::ID) {
+                    return Err(PinocchioError::InvalidOwner.into());
+                }
+
+                let list_data = validator_list.try_borrow_data()?;
+                let (_, entries) = ValidatorList::load(&list_data)?;
+
+                if entries.len() != accounts.validator_stakes.len() + 1 {
+                    return Err(PinocchioError::MissingValidatorStakes.into());
+                }
+
+                for (entry, stake) in entries[1..].iter().zip(accounts.validator_stakes) {
+                    if stake.key() != &entry.stake_account {
+                        return Err(PinocchioError::InvalidValidatorStake.into());
+                    }
+                }
+            }
+            None if !pool_state.has_validator_list() => {}
+            None => return Err(PinocchioError::MissingValidatorList.into()),
+            Some(_) => return Err(PinocchioError::InvalidValidatorList.into()),
+        }
+
+        match accounts.rate_history {
+            Some(rate_history) if pool_state.has_rate_history() => {
+                if rate_history.key() != &pool_state.rate_history {
+                    return Err(PinocchioError::InvalidRateHistory.into());
+                }
+            }
+            None if !pool_state.has_rate_history() => {}
+            None => return Err(PinocchioError::MissingRateHistory.into()),
+            Some(_) => return Err(PinocchioError::InvalidRateHistory.into()),
+        }
+
+        Ok(Self { accounts, epoch })
+    }
+}
+
+impl<'a> UpdatePoolBalance<'a> {
+    pub const DISCRIMINATOR: u8 = 6;
+
+    pub fn process(&self) -> ProgramResult {
+        // Stakes count by their delegation and the reserve by what the pool
+        // recorded, so lamports donated to any of them are neither counted as
+        // rewards nor charged the reward fee
+        let pool_stake_value = stake_value(self.accounts.pool_stake)?;
+        let mut total_lamports = {
+            let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
+            let pool_state = PoolState::load(&pool_state_data)?;
+
+            // Transient stakes hold reserve SOL while it activates
+            pool_stake_value
+                .checked_add(pool_state.reserve_lamports)
+                .and_then(|total| total.checked_add(pool_state.transient_lamports))
+                .ok_or(ProgramError::ArithmeticOverflow)?
+        };
+
+        if let Some(validator_list) = self.accounts.validator_list {
+            let mut list_data = validator_list.try_borrow_mut_data()?;
+            let (_, entries) = ValidatorList::load_mut(&mut list_data)?;
+
+            entries[0].active_lamports = pool_stake_value;
+            entries[0].last_update_epoch = self.epoch;
+
+            for (entry, stake) in entries[1..].iter_mut().zip(self.accounts.validator_stakes) {
+                let value = stake_value(stake)?;
+                total_lamports = total_lamports
+                    .checked_add(value)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+
+                entry.active_lamports = value;
+                entry.last_update_epoch = self.epoch;
+            }
+        }
+
+        let fee_lst = {
+            let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
+            let pool_state = PoolState::load(&pool_state_data)?;
+
+            let fee_lst = self.calculate_reward_fee(pool_state, total_lamports)?;
+
+            if fee_lst > 0 {
+                let seed_bytes = pool_state.seed.to_le_bytes();
+                let bump_binding = [pool_state.bump];
+                let pool_seeds = [
+                    Seed::from(b"lst_pool"),
+                    Seed::from(&seed_bytes),
+                    Seed::from(&bump_binding),
+                ];
+
+                mint_to(
+                    self.accounts.lst_mint,
+                    self.accounts.manager_fee_account,
+                    self.accounts.pool_state,
+                    self.accounts.token_program,
+                    fee_lst,
+                    &pool_seeds,
+                )?;
+            }
+
+            fee_lst
+        };
+
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+
+        pool_state.lst_supply = pool_state
+            .lst_supply
+            .checked_add(fee_lst)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        pool_state.total_lamports = total_lamports;
+        pool_state.last_update_epoch = self.epoch;
+
+        if let Some(rate_history) = self.accounts.rate_history {
+            let mut history_data = rate_history.try_borrow_mut_data()?;
+            RateHistory::load_mut(&mut history_data)?.push(
+                self.epoch,
+                pool_state.total_lamports,
+                pool_state.lst_supply,
+            );
+        }
+
+        msg!("Pool balance updated");
+        Ok(())
+    }
+
+    /// LST to mint so the manager owns `reward_fee_bps` of the rewards earned
+    /// since the last update.
+    fn calculate_reward_fee(
+        &self,
+        pool: &PoolState,
+        total_lamports: u64,
+    ) -> Result<u64, ProgramError> {
+        let rewards = total_lamports.saturating_sub(pool.total_lamports);
+        let fee_lamports = calculate_fee(rewards, pool.reward_fee_bps)?;
+
+        if fee_lamports == 0 || pool.lst_supply == 0 {
+            return Ok(0);
+        }
+
+        let fee_lst = (fee_lamports as u128)
+            .checked_mul(pool.lst_supply as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_div((total_lamports - fee_lamports) as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(fee_lst as u64)
+    }
+}
@@ -0,0 +1,31 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{ProgramAccount, SignerAccount};
+
+pub struct UpdateTokenMetadataAccounts<'a> {
+    pub manager: &'a AccountInfo,
+    pub pool_state: &'a AccountInfo,
+    pub metadata: &'a AccountInfo,
+    pub token_metadata_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for UpdateTokenMetadataAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [manager, pool_state, metadata, token_metadata_program] = accounts else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(manager)?;
+        ProgramAccount::check(pool_state)?;
+        ProgramAccount::check_token_metadata_program(token_metadata_program)?;
+
+        Ok(Self {
+            manager,
+            pool_state,
+            metadata,
+            token_metadata_program,
+        })
+    }
+}
@@ -0,0 +1,18 @@
+use pinocchio::program_error::ProgramError;
+
+use crate::TokenMetadata;
+
+pub struct UpdateTokenMetadataInstructionData {
+    /// Replaces the current name, symbol and URI
+    pub metadata: TokenMetadata,
+}
+
+impl<'a> TryFrom<&'a [u8]> for UpdateTokenMetadataInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        Ok(Self {
+            metadata: TokenMetadata::try_from(data)?,
+        })
+    }
+}
@@ -0,0 +1,74 @@
+use pinocchio::{
+    ProgramResult, account_info::AccountInfo, instruction::Seed, program_error::ProgramError,
+};
+
+use crate::{
+    PinocchioError, PoolState, ProgramAccount, UpdateTokenMetadataAccounts,
+    UpdateTokenMetadataInstructionData, update_metadata_account, verify_metadata_account,
+};
+
+pub struct UpdateTokenMetadata<'a> {
+    pub accounts: UpdateTokenMetadataAccounts<'a>,
+    pub instruction_data: UpdateTokenMetadataInstructionData,
+}
+
+impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for UpdateTokenMetadata<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&[u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = UpdateTokenMetadataAccounts::try_from(accounts)?;
+        let instruction_data = UpdateTokenMetadataInstructionData::try_from(data)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[Seed::from(b"lst_pool"), Seed::from(&seed_bytes)],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if accounts.manager.key() != &pool_state.manager {
+            return Err(PinocchioError::InvalidAuthority.into());
+        }
+
+        verify_metadata_account(accounts.metadata, &pool_state.lst_mint)?;
+        drop(pool_state_data);
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> UpdateTokenMetadata<'a> {
+    pub const DISCRIMINATOR: u8 = 18;
+
+    /// Replaces the LST name, symbol and URI; the pool PDA signs as update
+    /// authority.
+    pub fn process(&self) -> ProgramResult {
+        let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        let bump = [pool_state.bump];
+        let pool_seeds = [
+            Seed::from(b"lst_pool"),
+            Seed::from(&seed_bytes),
+            Seed::from(&bump),
+        ];
+
+        update_metadata_account(
+            self.accounts.metadata,
+            self.accounts.pool_state,
+            &self.instruction_data.metadata,
+            &pool_seeds,
+        )
+    }
+}
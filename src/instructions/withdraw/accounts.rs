@@ -1,6 +1,6 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
 
-use crate::{AssociatedToken, Mint, ProgramAccount, STAKE_PROGRAM_ID, SignerAccount};
+use crate::{AssociatedToken, Mint, ProgramAccount, SignerAccount, Token, WithdrawLedger};
 
 pub struct WithdrawAccounts<'a> {
     pub user: &'a AccountInfo,
@@ -10,12 +10,19 @@ pub struct WithdrawAccounts<'a> {
     pub user_stake: &'a AccountInfo,
     pub lst_mint: &'a AccountInfo,
     pub user_lst_ata: &'a AccountInfo,
+    pub manager_fee_account: &'a AccountInfo,
     pub clock: &'a AccountInfo,
     pub rent: &'a AccountInfo,
     pub stake_history: &'a AccountInfo,
     pub system_program: &'a AccountInfo,
     pub stake_program: &'a AccountInfo,
     pub token_program: &'a AccountInfo,
+    /// Trailing `["ticket", user_stake]` PDA; when passed, the withdrawal is
+    /// issued as a transferable `WithdrawTicket`
+    pub ticket: Option<&'a AccountInfo>,
+    /// The user's `WithdrawLedger`, last among the trailing accounts; when
+    /// passed, it picks the nonce and records the withdrawal
+    pub ledger: Option<&'a AccountInfo>,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawAccounts<'a> {
@@ -30,27 +37,33 @@ impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawAccounts<'a> {
             user_stake,
             lst_mint,
             user_lst_ata,
+            manager_fee_account,
             clock,
             rent,
             stake_history,
             system_program,
             stake_program,
             token_program,
+            remaining @ ..
         ] = accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
+        let (ledger, remaining) = match remaining.split_last() {
+            Some((last, rest)) if WithdrawLedger::is_ledger(last) => (Some(last), rest),
+            _ => (None, remaining),
+        };
+
         SignerAccount::check(user)?;
         ProgramAccount::check(pool_state)?;
         ProgramAccount::check_token_program(token_program)?;
         ProgramAccount::check_system_program(system_program)?;
 
-        Mint::check(lst_mint)?;
+        Mint::check(lst_mint, token_program.key())?;
+        Token::check(manager_fee_account, token_program.key())?;
 
-        if stake_program.key() != &STAKE_PROGRAM_ID {
-            return Err(ProgramError::IncorrectProgramId);
-        }
+        ProgramAccount::check_stake_program(stake_program)?;
 
         AssociatedToken::check(
             user_lst_ata,
@@ -67,12 +80,15 @@ impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawAccounts<'a> {
             user_stake,
             lst_mint,
             user_lst_ata,
+            manager_fee_account,
             clock,
             rent,
             stake_history,
             system_program,
             stake_program,
             token_program,
+            ticket: remaining.first(),
+            ledger,
         })
     }
 }
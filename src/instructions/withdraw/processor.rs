@@ -1,21 +1,25 @@
 use pinocchio::{
+    ProgramResult,
     account_info::AccountInfo,
     instruction::Seed,
     program_error::ProgramError,
     pubkey::find_program_address,
-    sysvars::{Sysvar, rent::Rent},
+    sysvars::{Sysvar, clock::Clock, rent::Rent},
 };
-use pinocchio_token::instructions::Burn;
+use pinocchio_system::instructions::Transfer;
 
 use crate::{
-    MIN_STAKE_DELEGATION, PoolState, ProgramAccount, STAKE_ACCOUNT_SIZE, WithdrawAccounts,
-    WithdrawInstructionData, create_stake_account, deactivate_stake, split_stake,
+    MIN_STAKE_DELEGATION, PinocchioError, PoolState, ProgramAccount, STAKE_ACCOUNT_SIZE,
+    WithdrawAccounts, WithdrawInstructionData, WithdrawLedger, WithdrawTicket,
+    authorize_stake_signed, burn, calculate_fee, check_pool_stake, create_stake_account,
+    deactivate_stake, split_stake, transfer_checked,
 };
 
 pub struct Withdraw<'a> {
     pub accounts: WithdrawAccounts<'a>,
     pub instruction_data: WithdrawInstructionData,
     pub user_stake_bump: u8,
+    pub ticket_bump: Option<u8>,
 }
 
 impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for Withdraw<'a> {
@@ -23,13 +27,17 @@ impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for Withdraw<'a> {
 
     fn try_from((data, accounts): (&[u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
         let accounts = WithdrawAccounts::try_from(accounts)?;
-        let instruction_data = WithdrawInstructionData::try_from(data)?;
+        let mut instruction_data = WithdrawInstructionData::try_from(data)?;
 
         let pool_state_data = accounts.pool_state.try_borrow_data()?;
         let pool_state = PoolState::load(&pool_state_data)?;
 
         if pool_state.discriminator == 0 {
-            return Err(ProgramError::UninitializedAccount);
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        if pool_state.withdrawals_paused() {
+            return Err(PinocchioError::WithdrawalsPaused.into());
         }
 
         let seed_bytes = pool_state.seed.to_le_bytes();
@@ -42,6 +50,32 @@ impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for Withdraw<'a> {
             pool_state.bump,
         )?;
 
+        // The ledger hands out nonces: 0 takes its next one, and a later one
+        // skips nonces already used by withdrawals made without the ledger
+        match accounts.ledger {
+            Some(ledger) => {
+                let ledger_data = ledger.try_borrow_data()?;
+                let (ledger_state, _) = WithdrawLedger::load(&ledger_data)?;
+
+                if &ledger_state.pool_state != accounts.pool_state.key()
+                    || &ledger_state.owner != accounts.user.key()
+                {
+                    return Err(PinocchioError::InvalidWithdrawLedger.into());
+                }
+
+                if instruction_data.nonce == 0 {
+                    instruction_data.nonce = ledger_state.next_nonce;
+                } else if instruction_data.nonce < ledger_state.next_nonce {
+                    return Err(PinocchioError::InvalidNonce.into());
+                }
+            }
+            None => {
+                if instruction_data.nonce == 0 {
+                    return Err(PinocchioError::InvalidNonce.into());
+                }
+            }
+        }
+
         let nonce_bytes = instruction_data.nonce.to_le_bytes();
         let (user_stake_pda, user_stake_bump) = find_program_address(
             &[
@@ -53,40 +87,79 @@ impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for Withdraw<'a> {
             &crate::ID,
         );
         if accounts.user_stake.key() != &user_stake_pda {
-            return Err(ProgramError::InvalidSeeds);
+            return Err(PinocchioError::InvalidWithdrawStake.into());
         }
 
         if accounts.user_stake.data_len() != 0 || accounts.user_stake.lamports() != 0 {
-            return Err(ProgramError::AccountAlreadyInitialized);
+            return Err(PinocchioError::WithdrawStakeAlreadyExists.into());
         }
 
+        let ticket_bump = match accounts.ticket {
+            Some(ticket) => {
+                let (ticket_pda, ticket_bump) = find_program_address(
+                    &[b"ticket", accounts.user_stake.key().as_ref()],
+                    &crate::ID,
+                );
+                if ticket.key() != &ticket_pda {
+                    return Err(PinocchioError::InvalidWithdrawTicket.into());
+                }
+                Some(ticket_bump)
+            }
+            None => None,
+        };
+
         if accounts.pool_stake.owner() != accounts.stake_program.key() {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(PinocchioError::InvalidPoolStake.into());
         }
 
         if accounts.pool_stake.key() != &pool_state.stake_account {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(PinocchioError::InvalidPoolStake.into());
         }
 
         if accounts.reserve_stake.key() != &pool_state.reserve_stake {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(PinocchioError::InvalidReserveStake.into());
         }
 
         if accounts.lst_mint.key() != &pool_state.lst_mint {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        if accounts.token_program.key() != &pool_state.token_program() {
+            return Err(PinocchioError::InvalidProgram.into());
+        }
+
+        if accounts.manager_fee_account.key() != &pool_state.manager_fee_account {
+            return Err(PinocchioError::InvalidManagerFeeAccount.into());
+        }
+
+        if pool_state.is_redelegating() {
+            return Err(PinocchioError::RedelegationInProgress.into());
+        }
+
+        let clock = Clock::get()?;
+        check_pool_stake(
+            accounts.pool_stake,
+            accounts.pool_state.key(),
+            &pool_state.validator_vote,
+            &clock,
+        )?;
+
+        if pool_state.last_update_epoch != clock.epoch {
+            return Err(PinocchioError::StalePoolBalance.into());
         }
 
         let user_lst_data = accounts.user_lst_ata.try_borrow_data()?;
         let user_lst_balance = u64::from_le_bytes(user_lst_data[64..72].try_into().unwrap());
 
         if user_lst_balance < instruction_data.amount {
-            return Err(ProgramError::InsufficientFunds);
+            return Err(PinocchioError::InsufficientLstBalance.into());
         }
 
         Ok(Self {
             accounts,
             instruction_data,
             user_stake_bump,
+            ticket_bump,
         })
     }
 }
@@ -95,10 +168,19 @@ impl<'a> Withdraw<'a> {
     pub const DISCRIMINATOR: u8 = 4;
 
     pub fn process(&self) -> Result<(), ProgramError> {
-        let sol_amount = {
+        let (burn_amount, fee_amount, sol_amount) = {
             let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
             let pool_state = PoolState::load(&pool_state_data)?;
-            self.calculate_sol_amount(&pool_state)?
+
+            let fee_amount =
+                calculate_fee(self.instruction_data.amount, pool_state.withdrawal_fee_bps)?;
+            let burn_amount = self.instruction_data.amount - fee_amount;
+
+            (
+                burn_amount,
+                fee_amount,
+                self.calculate_sol_amount(pool_state, burn_amount)?,
+            )
         };
 
         let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
@@ -112,11 +194,15 @@ impl<'a> Withdraw<'a> {
             Seed::from(&pool_bump_binding),
         ];
 
+        if sol_amount < self.instruction_data.minimum_lamports_out {
+            return Err(PinocchioError::SlippageExceeded.into());
+        }
+
         let rent = Rent::get()?.minimum_balance(STAKE_ACCOUNT_SIZE as usize);
         let min_stake = rent + MIN_STAKE_DELEGATION;
 
         if sol_amount < min_stake {
-            return Err(ProgramError::InsufficientFunds);
+            return Err(PinocchioError::BelowMinimumWithdrawal.into());
         }
 
         let pool_after = self
@@ -124,10 +210,10 @@ impl<'a> Withdraw<'a> {
             .pool_stake
             .lamports()
             .checked_sub(sol_amount)
-            .ok_or(ProgramError::InsufficientFunds)?;
+            .ok_or(PinocchioError::PoolStakeBelowMinimum)?;
 
         if pool_after < min_stake {
-            return Err(ProgramError::InsufficientFunds);
+            return Err(PinocchioError::PoolStakeBelowMinimum.into());
         }
 
         let nonce_bytes = self.instruction_data.nonce.to_le_bytes();
@@ -162,45 +248,145 @@ impl<'a> Withdraw<'a> {
             &pool_seeds,
         )?;
 
-        Burn {
-            account: self.accounts.user_lst_ata,
-            mint: self.accounts.lst_mint,
-            authority: self.accounts.user,
-            amount: self.instruction_data.amount,
+        if let (Some(ticket), Some(ticket_bump)) = (self.accounts.ticket, self.ticket_bump) {
+            self.issue_ticket(ticket, ticket_bump, &pool_seeds)?;
         }
-        .invoke()?;
+
+        if let Some(ledger) = self.accounts.ledger {
+            self.record_in_ledger(ledger, sol_amount)?;
+        }
+
+        if fee_amount > 0 {
+            transfer_checked(
+                self.accounts.user_lst_ata,
+                self.accounts.lst_mint,
+                self.accounts.manager_fee_account,
+                self.accounts.user,
+                self.accounts.token_program,
+                fee_amount,
+            )?;
+        }
+
+        burn(
+            self.accounts.user_lst_ata,
+            self.accounts.lst_mint,
+            self.accounts.user,
+            self.accounts.token_program,
+            burn_amount,
+        )?;
 
         drop(pool_state_data);
         let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
         let pool_state = PoolState::load_mut(&mut pool_state_data)?;
         pool_state.lst_supply = pool_state
             .lst_supply
-            .checked_sub(self.instruction_data.amount)
+            .checked_sub(burn_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        pool_state.total_lamports = pool_state
+            .total_lamports
+            .checked_sub(sol_amount)
             .ok_or(ProgramError::ArithmeticOverflow)?;
 
         Ok(())
     }
 
-    fn calculate_sol_amount(&self, pool: &PoolState) -> Result<u64, ProgramError> {
-        let pool_stake_sol = self.accounts.pool_stake.lamports();
-        let reserve_stake_sol = self.accounts.reserve_stake.lamports();
+    /// Creates the ticket and hands it the stake's withdraw authority, so the
+    /// nonce-based claim no longer works and the ticket owner is paid instead.
+    fn issue_ticket(
+        &self,
+        ticket: &AccountInfo,
+        ticket_bump: u8,
+        pool_seeds: &[Seed],
+    ) -> ProgramResult {
+        let ticket_bump_binding = [ticket_bump];
+        let ticket_seeds = [
+            Seed::from(b"ticket"),
+            Seed::from(self.accounts.user_stake.key().as_ref()),
+            Seed::from(&ticket_bump_binding),
+        ];
 
-        let total_pool_value = pool_stake_sol
-            .checked_add(reserve_stake_sol)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+        ProgramAccount::init::<WithdrawTicket>(
+            self.accounts.user,
+            ticket,
+            &ticket_seeds,
+            WithdrawTicket::LEN,
+        )?;
+
+        {
+            let mut ticket_data = ticket.try_borrow_mut_data()?;
+            WithdrawTicket::load_mut(&mut ticket_data)?.set_inner(
+                ticket_bump,
+                *self.accounts.pool_state.key(),
+                *self.accounts.user_stake.key(),
+                *self.accounts.user.key(),
+            );
+        }
+
+        authorize_stake_signed(
+            self.accounts.user_stake,
+            self.accounts.clock,
+            self.accounts.pool_state,
+            ticket.key(),
+            1, // Withdrawer
+            pool_seeds,
+        )
+    }
+
+    /// Appends the withdraw stake to the ledger, growing it at the user's
+    /// expense, and advances its nonce past the one used.
+    fn record_in_ledger(&self, ledger: &AccountInfo, lamports: u64) -> ProgramResult {
+        let count = {
+            let ledger_data = ledger.try_borrow_data()?;
+            WithdrawLedger::load(&ledger_data)?.0.count
+        };
+
+        let new_space = WithdrawLedger::space(count as usize + 1);
+        let rent_due = Rent::get()?
+            .minimum_balance(new_space)
+            .saturating_sub(ledger.lamports());
+
+        if rent_due > 0 {
+            Transfer {
+                from: self.accounts.user,
+                to: ledger,
+                lamports: rent_due,
+            }
+            .invoke()?;
+        }
+
+        ledger.resize(new_space)?;
+
+        let mut ledger_data = ledger.try_borrow_mut_data()?;
+        WithdrawLedger::load_header_mut(&mut ledger_data)?.count = count + 1;
+        let (ledger_state, entries) = WithdrawLedger::load_mut(&mut ledger_data)?;
+
+        // Deactivation takes effect at the next epoch boundary
+        entries[count as usize].set_inner(
+            *self.accounts.user_stake.key(),
+            self.instruction_data.nonce,
+            lamports,
+            Clock::get()?.epoch + 1,
+        );
+        ledger_state.next_nonce = self.instruction_data.nonce + 1;
+
+        Ok(())
+    }
+
+    fn calculate_sol_amount(&self, pool: &PoolState, lst_amount: u64) -> Result<u64, ProgramError> {
+        let total_pool_value = pool.total_lamports;
 
         if pool.lst_supply == 0 {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(PinocchioError::EmptyPool.into());
         }
 
-        let sol_amount = (self.instruction_data.amount as u128)
+        let sol_amount = (lst_amount as u128)
             .checked_mul(total_pool_value as u128)
             .ok_or(ProgramError::ArithmeticOverflow)?
             .checked_div(pool.lst_supply as u128)
             .ok_or(ProgramError::ArithmeticOverflow)?;
 
         if sol_amount == 0 {
-            return Err(ProgramError::InvalidInstructionData);
+            return Err(PinocchioError::BelowMinimumWithdrawal.into());
         }
 
         Ok(sol_amount as u64)
@@ -1,6 +1,6 @@
 use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
 
-use crate::{ProgramAccount, STAKE_PROGRAM_ID, SignerAccount};
+use crate::{ProgramAccount, SignerAccount, WithdrawLedger};
 
 pub struct WithdrawCompleteAccounts<'a> {
     pub user: &'a AccountInfo,
@@ -9,6 +9,12 @@ pub struct WithdrawCompleteAccounts<'a> {
     pub clock: &'a AccountInfo,
     pub stake_history: &'a AccountInfo,
     pub stake_program: &'a AccountInfo,
+    /// Trailing `WithdrawTicket`; presented instead of a nonce to claim a
+    /// ticketed withdrawal, which is paid to the ticket owner
+    pub ticket: Option<&'a AccountInfo>,
+    /// A `WithdrawLedger` listing `user_stake`, last among the trailing
+    /// accounts; its entry is removed
+    pub ledger: Option<&'a AccountInfo>,
 }
 
 impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawCompleteAccounts<'a> {
@@ -22,17 +28,21 @@ impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawCompleteAccounts<'a> {
             clock,
             stake_history,
             stake_program,
+            remaining @ ..
         ] = accounts
         else {
             return Err(ProgramError::NotEnoughAccountKeys);
         };
 
+        let (ledger, remaining) = match remaining.split_last() {
+            Some((last, rest)) if WithdrawLedger::is_ledger(last) => (Some(last), rest),
+            _ => (None, remaining),
+        };
+
         SignerAccount::check(user)?;
         ProgramAccount::check(pool_state)?;
 
-        if stake_program.key() != &STAKE_PROGRAM_ID {
-            return Err(ProgramError::IncorrectProgramId);
-        }
+        ProgramAccount::check_stake_program(stake_program)?;
 
         Ok(Self {
             user,
@@ -41,6 +51,8 @@ impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawCompleteAccounts<'a> {
             clock,
             stake_history,
             stake_program,
+            ticket: remaining.first(),
+            ledger,
         })
     }
 }
@@ -1,7 +1,10 @@
 use pinocchio::program_error::ProgramError;
 
+use crate::PinocchioError;
+
 #[repr(C, packed)]
 pub struct WithdrawCompleteInstructionData {
+    /// 0 when a withdraw ticket is presented instead
     pub nonce: u64,
 }
 
@@ -9,14 +12,14 @@ impl<'a> TryFrom<&'a [u8]> for WithdrawCompleteInstructionData {
     type Error = ProgramError;
 
     fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
-        if data.len() != size_of::<u64>() {
-            return Err(ProgramError::InvalidInstructionData);
-        }
-
-        let nonce = u64::from_le_bytes(data[0..8].try_into().unwrap());
+        let nonce = match data.len() {
+            0 => 0,
+            8 => u64::from_le_bytes(data[0..8].try_into().unwrap()),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
 
-        if nonce == 0 {
-            return Err(ProgramError::InvalidInstructionData);
+        if data.len() == 8 && nonce == 0 {
+            return Err(PinocchioError::InvalidNonce.into());
         }
 
         Ok(Self { nonce })
@@ -1,11 +1,25 @@
-use pinocchio::{account_info::AccountInfo, instruction::Seed, program_error::ProgramError, pubkey::find_program_address};
-
-use crate::{PoolState, ProgramAccount, WithdrawCompleteAccounts, WithdrawCompleteInstructionData, withdraw_stake};
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::Seed,
+    program_error::ProgramError,
+    pubkey::find_program_address,
+    sysvars::{Sysvar, clock::Clock, rent::Rent},
+};
+
+use crate::{
+    PinocchioError, PoolState, ProgramAccount, StakeStateV2, WithdrawCompleteAccounts,
+    WithdrawCompleteInstructionData, WithdrawLedger, WithdrawTicket, check_stake_meta,
+    withdraw_stake,
+};
 
 pub struct WithdrawComplete<'a> {
     pub accounts: WithdrawCompleteAccounts<'a>,
     pub instruction_data: WithdrawCompleteInstructionData,
-    pub user_stake_bump: u8,
+    /// Bump of the presented ticket, which signs as withdraw authority
+    pub ticket_bump: Option<u8>,
+    /// Index of `user_stake` in the presented ledger
+    pub ledger_index: Option<usize>,
 }
 
 impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for WithdrawComplete<'a> {
@@ -19,53 +33,154 @@ impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for WithdrawComplete<'a> {
         let pool_state = PoolState::load(&pool_state_data)?;
 
         if pool_state.discriminator == 0 {
-            return Err(ProgramError::UninitializedAccount);
+            return Err(PinocchioError::PoolNotInitialized.into());
         }
 
         let seed_bytes = pool_state.seed.to_le_bytes();
         ProgramAccount::verify(
-            &[
-                Seed::from(b"lst_pool"),
-                Seed::from(&seed_bytes),
-            ],
+            &[Seed::from(b"lst_pool"), Seed::from(&seed_bytes)],
             accounts.pool_state,
             pool_state.bump,
         )?;
 
-        let nonce_bytes = instruction_data.nonce.to_le_bytes();
-        let (user_stake_pda, user_stake_bump) = find_program_address(
-            &[
-                b"withdraw",
-                accounts.pool_state.key().as_ref(),
-                accounts.user.key().as_ref(),
-                &nonce_bytes,
-            ],
-            &crate::ID,
-        );
-        if accounts.user_stake.key() != &user_stake_pda {
-            return Err(ProgramError::InvalidSeeds);
-        }
-
         if accounts.user_stake.owner() != accounts.stake_program.key() {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(PinocchioError::InvalidOwner.into());
         }
 
         if accounts.user_stake.data_len() == 0 || accounts.user_stake.lamports() == 0 {
-            return Err(ProgramError::UninitializedAccount);
+            return Err(PinocchioError::WithdrawStakeNotFound.into());
         }
 
+        let ticket_bump = match accounts.ticket {
+            None => {
+                if instruction_data.nonce == 0 {
+                    return Err(PinocchioError::InvalidNonce.into());
+                }
+
+                let nonce_bytes = instruction_data.nonce.to_le_bytes();
+                let (user_stake_pda, _) = find_program_address(
+                    &[
+                        b"withdraw",
+                        accounts.pool_state.key().as_ref(),
+                        accounts.user.key().as_ref(),
+                        &nonce_bytes,
+                    ],
+                    &crate::ID,
+                );
+                if accounts.user_stake.key() != &user_stake_pda {
+                    return Err(PinocchioError::InvalidWithdrawStake.into());
+                }
+
+                // A ticket took over the withdraw authority if it is not the pool
+                let stake_data = accounts.user_stake.try_borrow_data()?;
+                let withdrawer = StakeStateV2::load(&stake_data)?
+                    .meta()
+                    .map(|meta| meta.authorized.withdrawer);
+                if withdrawer.as_ref() != Some(accounts.pool_state.key()) {
+                    return Err(PinocchioError::WithdrawTicketIssued.into());
+                }
+
+                None
+            }
+            Some(ticket) => {
+                if instruction_data.nonce != 0 {
+                    return Err(ProgramError::InvalidInstructionData);
+                }
+
+                if !ticket.is_owned_by(&crate::ID) {
+                    return Err(PinocchioError::InvalidWithdrawTicket.into());
+                }
+
+                let ticket_data = ticket.try_borrow_data()?;
+                let ticket_state = WithdrawTicket::load(&ticket_data)?;
+
+                if ticket_state.discriminator != WithdrawTicket::DISCRIMINATOR
+                    || &ticket_state.pool_state != accounts.pool_state.key()
+                    || &ticket_state.withdraw_stake != accounts.user_stake.key()
+                {
+                    return Err(PinocchioError::InvalidWithdrawTicket.into());
+                }
+
+                if &ticket_state.owner != accounts.user.key() {
+                    return Err(PinocchioError::InvalidAuthority.into());
+                }
+
+                Some(ticket_state.bump)
+            }
+        };
+
+        // Split from the pool stake, so only the withdrawer may have moved.
+        // The voter is not checked: a redelegation may have replaced it since.
+        let withdrawer = accounts.ticket.unwrap_or(accounts.pool_state).key();
+        let stake_data = accounts.user_stake.try_borrow_data()?;
+        let meta = StakeStateV2::load(&stake_data)?
+            .meta()
+            .ok_or(PinocchioError::InvalidStakeState)?;
+        check_stake_meta(meta, accounts.pool_state.key(), withdrawer, &Clock::get()?)?;
+
+        // Anyone claiming the stake may clean up the ledger listing it
+        let ledger_index = match accounts.ledger {
+            Some(ledger) => {
+                let ledger_data = ledger.try_borrow_data()?;
+                let (ledger_state, entries) = WithdrawLedger::load(&ledger_data)?;
+
+                if &ledger_state.pool_state != accounts.pool_state.key() {
+                    return Err(PinocchioError::InvalidWithdrawLedger.into());
+                }
+
+                let index = entries
+                    .iter()
+                    .position(|entry| &entry.withdraw_stake == accounts.user_stake.key())
+                    .ok_or(PinocchioError::WithdrawLedgerEntryNotFound)?;
+                Some(index)
+            }
+            None => None,
+        };
+
         Ok(Self {
             accounts,
             instruction_data,
-            user_stake_bump,
+            ticket_bump,
+            ledger_index,
         })
     }
 }
 
 impl<'a> WithdrawComplete<'a> {
-    pub const DISCRIMINATOR: u8 = 5; 
+    pub const DISCRIMINATOR: u8 = 5;
 
     pub fn process(&self) -> Result<(), ProgramError> {
+        let withdraw_amount = self.accounts.user_stake.lamports();
+
+        if let (Some(ledger), Some(index)) = (self.accounts.ledger, self.ledger_index) {
+            self.remove_ledger_entry(ledger, index)?;
+        }
+
+        if let (Some(ticket), Some(ticket_bump)) = (self.accounts.ticket, self.ticket_bump) {
+            let ticket_bump_binding = [ticket_bump];
+            let ticket_seeds = [
+                Seed::from(b"ticket"),
+                Seed::from(self.accounts.user_stake.key().as_ref()),
+                Seed::from(&ticket_bump_binding),
+            ];
+
+            withdraw_stake(
+                self.accounts.user_stake,
+                self.accounts.user,
+                ticket,
+                self.accounts.clock,
+                self.accounts.stake_history,
+                &ticket_seeds,
+                withdraw_amount,
+            )?;
+
+            // The claim is spent; return the ticket's rent to its owner
+            let ticket_lamports = ticket.lamports();
+            *self.accounts.user.try_borrow_mut_lamports()? += ticket_lamports;
+            *ticket.try_borrow_mut_lamports()? = 0;
+            return ticket.close();
+        }
+
         let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
         let pool_state = PoolState::load(&pool_state_data)?;
 
@@ -77,8 +192,6 @@ impl<'a> WithdrawComplete<'a> {
             Seed::from(&pool_bump_binding),
         ];
 
-        let withdraw_amount = self.accounts.user_stake.lamports();
-
         withdraw_stake(
             self.accounts.user_stake,
             self.accounts.user,
@@ -91,4 +204,33 @@ impl<'a> WithdrawComplete<'a> {
 
         Ok(())
     }
+
+    /// Swap-removes the entry and shrinks the ledger. The freed rent goes to
+    /// the ledger owner when they are the claimant and stays in the ledger
+    /// for its next entry otherwise.
+    fn remove_ledger_entry(&self, ledger: &AccountInfo, index: usize) -> ProgramResult {
+        let (new_count, owner) = {
+            let mut ledger_data = ledger.try_borrow_mut_data()?;
+            let (ledger_state, entries) = WithdrawLedger::load_mut(&mut ledger_data)?;
+
+            let last = entries.len() - 1;
+            entries[index] = entries[last];
+            ledger_state.count -= 1;
+            (ledger_state.count as usize, ledger_state.owner)
+        };
+
+        let new_space = WithdrawLedger::space(new_count);
+        ledger.resize(new_space)?;
+
+        if &owner == self.accounts.user.key() {
+            let refund = ledger
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(new_space));
+
+            *ledger.try_borrow_mut_lamports()? -= refund;
+            *self.accounts.user.try_borrow_mut_lamports()? += refund;
+        }
+
+        Ok(())
+    }
 }
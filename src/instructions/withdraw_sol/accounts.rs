@@ -0,0 +1,68 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError};
+
+use crate::{AssociatedToken, Mint, ProgramAccount, SignerAccount, Token};
+
+pub struct WithdrawSolAccounts<'a> {
+    pub user: &'a AccountInfo,
+    pub pool_state: &'a AccountInfo,
+    pub reserve_stake: &'a AccountInfo,
+    pub lst_mint: &'a AccountInfo,
+    pub user_lst_ata: &'a AccountInfo,
+    pub manager_fee_account: &'a AccountInfo,
+    pub clock: &'a AccountInfo,
+    pub stake_history: &'a AccountInfo,
+    /// Programs
+    pub stake_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> TryFrom<&'a [AccountInfo]> for WithdrawSolAccounts<'a> {
+    type Error = ProgramError;
+
+    fn try_from(accounts: &'a [AccountInfo]) -> Result<Self, Self::Error> {
+        let [
+            user,
+            pool_state,
+            reserve_stake,
+            lst_mint,
+            user_lst_ata,
+            manager_fee_account,
+            clock,
+            stake_history,
+            stake_program,
+            token_program,
+        ] = accounts
+        else {
+            return Err(ProgramError::NotEnoughAccountKeys);
+        };
+
+        SignerAccount::check(user)?;
+        ProgramAccount::check(pool_state)?;
+        ProgramAccount::check_token_program(token_program)?;
+
+        Mint::check(lst_mint, token_program.key())?;
+        Token::check(manager_fee_account, token_program.key())?;
+
+        ProgramAccount::check_stake_program(stake_program)?;
+
+        AssociatedToken::check(
+            user_lst_ata,
+            *user.key(),
+            *lst_mint.key(),
+            *token_program.key(),
+        )?;
+
+        Ok(Self {
+            user,
+            pool_state,
+            reserve_stake,
+            lst_mint,
+            user_lst_ata,
+            manager_fee_account,
+            clock,
+            stake_history,
+            stake_program,
+            token_program,
+        })
+    }
+}
@@ -0,0 +1,33 @@
+use pinocchio::program_error::ProgramError;
+
+use crate::PinocchioError;
+
+#[repr(C, packed)]
+pub struct WithdrawSolInstructionData {
+    pub amount: u64,
+    /// Least lamports the user accepts; 0 when omitted
+    pub minimum_lamports_out: u64,
+}
+
+impl<'a> TryFrom<&'a [u8]> for WithdrawSolInstructionData {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let minimum_lamports_out = match data.len() {
+            8 => 0,
+            16 => u64::from_le_bytes(data[8..16].try_into().unwrap()),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        };
+
+        let amount = u64::from_le_bytes(data[0..8].try_into().unwrap());
+
+        if amount == 0 {
+            return Err(PinocchioError::ZeroAmount.into());
+        }
+
+        Ok(Self {
+            amount,
+            minimum_lamports_out,
+        })
+    }
+}
@@ -0,0 +1,218 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    msg,
+    program_error::ProgramError,
+    sysvars::{Sysvar, clock::Clock, rent::Rent},
+};
+
+use crate::{
+    PinocchioError, PoolState, ProgramAccount, STAKE_ACCOUNT_SIZE, STAKE_PROGRAM_ID, StakeStateV2,
+    WithdrawSolAccounts, WithdrawSolInstructionData, burn, calculate_fee, transfer_checked,
+    withdraw_stake,
+};
+
+pub struct WithdrawSol<'a> {
+    pub accounts: WithdrawSolAccounts<'a>,
+    pub instruction_data: WithdrawSolInstructionData,
+}
+
+impl<'a> TryFrom<(&[u8], &'a [AccountInfo])> for WithdrawSol<'a> {
+    type Error = ProgramError;
+
+    fn try_from((data, accounts): (&[u8], &'a [AccountInfo])) -> Result<Self, Self::Error> {
+        let accounts = WithdrawSolAccounts::try_from(accounts)?;
+        let instruction_data = WithdrawSolInstructionData::try_from(data)?;
+
+        let pool_state_data = accounts.pool_state.try_borrow_data()?;
+        let pool_state = PoolState::load(&pool_state_data)?;
+
+        if pool_state.discriminator == 0 {
+            return Err(PinocchioError::PoolNotInitialized.into());
+        }
+
+        if pool_state.withdrawals_paused() {
+            return Err(PinocchioError::WithdrawalsPaused.into());
+        }
+
+        let seed_bytes = pool_state.seed.to_le_bytes();
+        ProgramAccount::verify(
+            &[Seed::from(b"lst_pool"), Seed::from(&seed_bytes)],
+            accounts.pool_state,
+            pool_state.bump,
+        )?;
+
+        if accounts.reserve_stake.key() != &pool_state.reserve_stake {
+            return Err(PinocchioError::InvalidReserveStake.into());
+        }
+
+        if accounts.lst_mint.key() != &pool_state.lst_mint {
+            return Err(PinocchioError::InvalidLstMint.into());
+        }
+
+        if accounts.token_program.key() != &pool_state.token_program() {
+            return Err(PinocchioError::InvalidProgram.into());
+        }
+
+        if accounts.manager_fee_account.key() != &pool_state.manager_fee_account {
+            return Err(PinocchioError::InvalidManagerFeeAccount.into());
+        }
+
+        if pool_state.last_update_epoch != Clock::get()?.epoch {
+            return Err(PinocchioError::StalePoolBalance.into());
+        }
+
+        let user_lst_data = accounts.user_lst_ata.try_borrow_data()?;
+        let user_lst_balance = u64::from_le_bytes(user_lst_data[64..72].try_into().unwrap());
+
+        if user_lst_balance < instruction_data.amount {
+            return Err(PinocchioError::InsufficientLstBalance.into());
+        }
+
+        Ok(Self {
+            accounts,
+            instruction_data,
+        })
+    }
+}
+
+impl<'a> WithdrawSol<'a> {
+    pub const DISCRIMINATOR: u8 = 12;
+
+    pub fn process(&self) -> ProgramResult {
+        let (burn_amount, fee_amount, sol_amount, reserve_bump, reserve_lamports) = {
+            let pool_state_data = self.accounts.pool_state.try_borrow_data()?;
+            let pool_state = PoolState::load(&pool_state_data)?;
+
+            let fee_amount = calculate_fee(
+                self.instruction_data.amount,
+                pool_state.sol_withdrawal_fee_bps,
+            )?;
+            let burn_amount = self.instruction_data.amount - fee_amount;
+
+            (
+                burn_amount,
+                fee_amount,
+                self.calculate_sol_amount(pool_state, burn_amount)?,
+                pool_state.reserve_bump,
+                pool_state.reserve_lamports,
+            )
+        };
+
+        if sol_amount < self.instruction_data.minimum_lamports_out {
+            return Err(PinocchioError::SlippageExceeded.into());
+        }
+
+        self.check_reserve_after(reserve_lamports, sol_amount)?;
+
+        if fee_amount > 0 {
+            transfer_checked(
+                self.accounts.user_lst_ata,
+                self.accounts.lst_mint,
+                self.accounts.manager_fee_account,
+                self.accounts.user,
+                self.accounts.token_program,
+                fee_amount,
+            )?;
+        }
+
+        burn(
+            self.accounts.user_lst_ata,
+            self.accounts.lst_mint,
+            self.accounts.user,
+            self.accounts.token_program,
+            burn_amount,
+        )?;
+
+        let reserve_bump_binding = [reserve_bump];
+        let reserve_seeds = [
+            Seed::from(b"reserve_stake"),
+            Seed::from(self.accounts.pool_state.key().as_ref()),
+            Seed::from(&reserve_bump_binding),
+        ];
+
+        // The reserve is an uninitialized stake account, or a plain system
+        // account if the original MergeReserve closed it
+        if self.accounts.reserve_stake.is_owned_by(&STAKE_PROGRAM_ID) {
+            withdraw_stake(
+                self.accounts.reserve_stake,
+                self.accounts.user,
+                self.accounts.reserve_stake,
+                self.accounts.clock,
+                self.accounts.stake_history,
+                &reserve_seeds,
+                sol_amount,
+            )?;
+        } else {
+            pinocchio_system::instructions::Transfer {
+                from: self.accounts.reserve_stake,
+                to: self.accounts.user,
+                lamports: sol_amount,
+            }
+            .invoke_signed(&[Signer::from(&reserve_seeds)])?;
+        }
+
+        let mut pool_state_data = self.accounts.pool_state.try_borrow_mut_data()?;
+        let pool_state = PoolState::load_mut(&mut pool_state_data)?;
+        pool_state.lst_supply = pool_state
+            .lst_supply
+            .checked_sub(burn_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        pool_state.total_lamports = pool_state
+            .total_lamports
+            .checked_sub(sol_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        pool_state.debit_reserve(sol_amount)?;
+
+        msg!("SOL withdrawn from reserve");
+        Ok(())
+    }
+
+    /// Only undelegated reserve SOL can be paid out, and what is left must
+    /// stay rent-exempt as a stake account. Less than `InitializeReserve`
+    /// needs simply waits for further deposits. Lamports donated to the
+    /// reserve are not the pool's to pay out.
+    fn check_reserve_after(&self, reserve_lamports: u64, sol_amount: u64) -> ProgramResult {
+        let reserve = self.accounts.reserve_stake;
+
+        if reserve.is_owned_by(&STAKE_PROGRAM_ID) {
+            let reserve_data = reserve.try_borrow_data()?;
+            if !StakeStateV2::load(&reserve_data).is_ok_and(|reserve| reserve.is_uninitialized()) {
+                return Err(PinocchioError::ReserveNotAvailable.into());
+            }
+        } else if !reserve.is_owned_by(&pinocchio_system::ID) || reserve.data_len() != 0 {
+            return Err(PinocchioError::ReserveNotAvailable.into());
+        }
+
+        let floor = Rent::get()?.minimum_balance(STAKE_ACCOUNT_SIZE as usize);
+        let reserve_after = reserve_lamports
+            .checked_sub(sol_amount)
+            .ok_or(PinocchioError::InsufficientReserve)?;
+
+        if reserve_after < floor {
+            return Err(PinocchioError::InsufficientReserve.into());
+        }
+
+        Ok(())
+    }
+
+    fn calculate_sol_amount(
+        &self,
+        pool: &PoolState,
+        burn_amount: u64,
+    ) -> Result<u64, ProgramError> {
+        let sol_amount = (burn_amount as u128)
+            .checked_mul(pool.total_lamports as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            .checked_div(pool.lst_supply as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        // Rounds to nothing when the fee takes all of a dust amount
+        if sol_amount == 0 {
+            return Err(PinocchioError::BelowMinimumWithdrawal.into());
+        }
+
+        Ok(sol_amount as u64)
+    }
+}
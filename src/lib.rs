@@ -19,6 +19,9 @@ pub use constants::*;
 pub mod utils;
 pub use utils::*;
 
+#[cfg(feature = "client")]
+pub mod client;
+
 pub const ID: Pubkey = [
     0x0f, 0x1e, 0x6b, 0x14, 0x21, 0xc0, 0x4a, 0x07, 0x04, 0x31, 0x26, 0x5c, 0x19, 0xc5, 0xbb, 0xee,
     0x19, 0x92, 0xba, 0xe8, 0xaf, 0xd1, 0xcd, 0x07, 0x8e, 0xf8, 0xaf, 0x70, 0x47, 0xdc, 0x11, 0xf7,
@@ -42,6 +45,68 @@ fn process_instruction(
         Some((&WithdrawComplete::DISCRIMINATOR, data)) => {
             WithdrawComplete::try_from((data, accounts))?.process()
         }
+        Some((&UpdatePoolBalance::DISCRIMINATOR, _data)) => {
+            UpdatePoolBalance::try_from(accounts)?.process()
+        }
+        Some((&AddValidator::DISCRIMINATOR, _data)) => AddValidator::try_from(accounts)?.process(),
+        Some((&RemoveValidator::DISCRIMINATOR, _data)) => {
+            RemoveValidator::try_from(accounts)?.process()
+        }
+        Some((&Redelegate::DISCRIMINATOR, _data)) => Redelegate::try_from(accounts)?.process(),
+        Some((&CompleteRedelegate::DISCRIMINATOR, _data)) => {
+            CompleteRedelegate::try_from(accounts)?.process()
+        }
+        Some((&DepositStake::DISCRIMINATOR, data)) => {
+            DepositStake::try_from((data, accounts))?.process()
+        }
+        Some((&WithdrawSol::DISCRIMINATOR, data)) => {
+            WithdrawSol::try_from((data, accounts))?.process()
+        }
+        Some((&SetManager::DISCRIMINATOR, _data)) => SetManager::try_from(accounts)?.process(),
+        Some((&AcceptManager::DISCRIMINATOR, _data)) => {
+            AcceptManager::try_from(accounts)?.process()
+        }
+        Some((&SetStaker::DISCRIMINATOR, _data)) => SetStaker::try_from(accounts)?.process(),
+        Some((&SetStatus::DISCRIMINATOR, data)) => {
+            SetStatus::try_from((data, accounts))?.process()
+        }
+        Some((&MigratePool::DISCRIMINATOR, _data)) => MigratePool::try_from(accounts)?.process(),
+        Some((&UpdateTokenMetadata::DISCRIMINATOR, data)) => {
+            UpdateTokenMetadata::try_from((data, accounts))?.process()
+        }
+        Some((&UpdateConfig::DISCRIMINATOR, data)) => {
+            UpdateConfig::try_from((data, accounts))?.process()
+        }
+        Some((&SetDepositAuthority::DISCRIMINATOR, _data)) => {
+            SetDepositAuthority::try_from(accounts)?.process()
+        }
+        Some((&TransferWithdrawTicket::DISCRIMINATOR, _data)) => {
+            TransferWithdrawTicket::try_from(accounts)?.process()
+        }
+        Some((&InitializeWithdrawLedger::DISCRIMINATOR, _data)) => {
+            InitializeWithdrawLedger::try_from(accounts)?.process()
+        }
+        Some((&CloseWithdrawLedger::DISCRIMINATOR, _data)) => {
+            CloseWithdrawLedger::try_from(accounts)?.process()
+        }
+        Some((&InitializeRateHistory::DISCRIMINATOR, _data)) => {
+            InitializeRateHistory::try_from(accounts)?.process()
+        }
+        Some((&GetAverageRate::DISCRIMINATOR, data)) => {
+            GetAverageRate::try_from((data, accounts))?.process()
+        }
+        Some((&GetExchangeRate::DISCRIMINATOR, _data)) => {
+            GetExchangeRate::try_from(accounts)?.process()
+        }
+        Some((&SetFees::DISCRIMINATOR, data)) => {
+            SetFees::try_from((data, accounts))?.process()
+        }
+        Some((&PruneWithdrawLedger::DISCRIMINATOR, _data)) => {
+            PruneWithdrawLedger::try_from(accounts)?.process()
+        }
+        Some((&IncreaseValidatorStake::DISCRIMINATOR, data)) => {
+            IncreaseValidatorStake::try_from((data, accounts))?.process()
+        }
 
         _ => Err(ProgramError::InvalidInstructionData),
     }
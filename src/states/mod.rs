@@ -1,3 +1,11 @@
 pub mod pool;
+pub mod rate_history;
+pub mod validator_list;
+pub mod withdraw_ledger;
+pub mod withdraw_ticket;
 
-pub use pool::*;
\ No newline at end of file
+pub use pool::*;
+pub use rate_history::*;
+pub use validator_list::*;
+pub use withdraw_ledger::*;
+pub use withdraw_ticket::*;
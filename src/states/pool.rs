@@ -1,39 +1,96 @@
-use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+use core::mem::offset_of;
+
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{PAUSE_CRANKS, PAUSE_DEPOSITS, PAUSE_WITHDRAWALS, PinocchioError};
 
 #[repr(C)]
 pub struct PoolState {
     pub discriminator: u8,
     pub lst_mint: Pubkey,
-    pub authority: Pubkey,
+    pub manager: Pubkey,
     pub validator_vote: Pubkey,
     pub stake_account: Pubkey,
     pub reserve_stake: Pubkey,
-    _padding_1: [u8; 7],
+    pub version: u8,
+    _padding_1: [u8; 6],
     pub seed: u64,
     pub bump: u8,
     pub stake_bump: u8,
     pub reserve_bump: u8,
     _padding_2: [u8; 5],
     pub lst_supply: u64,
+    pub manager_fee_account: Pubkey,
+    pub deposit_fee_bps: u16,
+    pub withdrawal_fee_bps: u16,
+    pub reward_fee_bps: u16,
+    pub sol_withdrawal_fee_bps: u16,
+    pub total_lamports: u64,
+    pub last_update_epoch: u64,
+    /// Reserve SOL the pool has accounted for. Lamports sent to the reserve
+    /// without a deposit are not counted, so they cannot move the rate.
+    pub reserve_lamports: u64,
+    pub validator_list: Pubkey,
+    pub validator_list_bump: u8,
+    _padding_4: [u8; 7],
+    pub pending_validator_vote: Pubkey,
+    pub transient_lamports: u64,
+    pub staker: Pubkey,
+    pub pending_manager: Pubkey,
+    pub status: u8,
+    _padding_5: [u8; 7],
+    /// Owner of `lst_mint`; zero in version 1 pools, which all use SPL Token
+    pub token_program: Pubkey,
+    /// Deposit limits set with `UpdateConfig`; zero disables each
+    pub max_total_lamports: u64,
+    pub max_deposit_lamports: u64,
+    pub min_deposit_lamports: u64,
+    /// Must co-sign deposits when set; zero for a public pool
+    pub deposit_authority: Pubkey,
+    /// Appended to by `UpdatePoolBalance` when set; zero if never created
+    pub rate_history: Pubkey,
+    /// Room for new fields; zero means their default, so adding one only
+    /// needs a version bump, not a realloc.
+    _reserved: [u8; 8],
 }
 
 impl PoolState {
     pub const LEN: usize = size_of::<Self>();
 
+    pub const CURRENT_VERSION: u8 = 5;
+
+    /// Lengths of the unversioned layouts. Each is a prefix of the current
+    /// layout, so `MigratePool` upgrades them by zero-extending the account.
+    pub const LEGACY_LENS: [usize; 8] = [192, 232, 256, 296, 328, 336, 400, 408];
+
+    /// Accepts every version that shares the current length; fields taken
+    /// from the reserved space read as zero in older versions.
     #[inline(always)]
-    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+    pub fn check_layout(bytes: &[u8]) -> Result<(), ProgramError> {
         if bytes.len() != PoolState::LEN {
+            if PoolState::LEGACY_LENS.contains(&bytes.len()) {
+                return Err(PinocchioError::PoolMigrationRequired.into());
+            }
             return Err(ProgramError::InvalidAccountData);
         }
+
+        // Uninitialized accounts are version 0 until `Initialize` runs
+        if bytes[offset_of!(PoolState, version)] > PoolState::CURRENT_VERSION {
+            return Err(PinocchioError::UnsupportedPoolVersion.into());
+        }
+
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        PoolState::check_layout(bytes)?;
         Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
     }
 
     #[inline(always)]
     pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
-        if bytes.len() != PoolState::LEN {
-            return Err(ProgramError::InvalidAccountData);
-        }
-
+        PoolState::check_layout(bytes)?;
         Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
     }
 
@@ -47,9 +104,10 @@ impl PoolState {
         self.lst_mint
     }
 
+    /// Controls fees and pool configuration.
     #[inline(always)]
-    pub fn authority(&self) -> Pubkey {
-        self.authority
+    pub fn manager(&self) -> Pubkey {
+        self.manager
     }
 
     #[inline(always)]
@@ -67,6 +125,11 @@ impl PoolState {
         self.reserve_stake
     }
 
+    #[inline(always)]
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
     #[inline(always)]
     pub fn seed(&self) -> u64 {
         self.seed
@@ -91,33 +154,308 @@ impl PoolState {
         self.lst_supply
     }
 
+    #[inline(always)]
+    pub fn manager_fee_account(&self) -> Pubkey {
+        self.manager_fee_account
+    }
+
+    #[inline(always)]
+    pub fn deposit_fee_bps(&self) -> u16 {
+        self.deposit_fee_bps
+    }
+
+    #[inline(always)]
+    pub fn withdrawal_fee_bps(&self) -> u16 {
+        self.withdrawal_fee_bps
+    }
+
+    #[inline(always)]
+    pub fn reward_fee_bps(&self) -> u16 {
+        self.reward_fee_bps
+    }
+
+    #[inline(always)]
+    pub fn sol_withdrawal_fee_bps(&self) -> u16 {
+        self.sol_withdrawal_fee_bps
+    }
+
+    #[inline(always)]
+    pub fn total_lamports(&self) -> u64 {
+        self.total_lamports
+    }
+
+    #[inline(always)]
+    pub fn last_update_epoch(&self) -> u64 {
+        self.last_update_epoch
+    }
+
+    #[inline(always)]
+    pub fn reserve_lamports(&self) -> u64 {
+        self.reserve_lamports
+    }
+
+    /// Records reserve SOL the pool took in.
+    #[inline(always)]
+    pub fn credit_reserve(&mut self, lamports: u64) -> Result<(), ProgramError> {
+        self.reserve_lamports = self
+            .reserve_lamports
+            .checked_add(lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Records reserve SOL the pool paid out.
+    #[inline(always)]
+    pub fn debit_reserve(&mut self, lamports: u64) -> Result<(), ProgramError> {
+        self.reserve_lamports = self
+            .reserve_lamports
+            .checked_sub(lamports)
+            .ok_or(PinocchioError::InsufficientReserve)?;
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn validator_list(&self) -> Pubkey {
+        self.validator_list
+    }
+
+    #[inline(always)]
+    pub fn validator_list_bump(&self) -> u8 {
+        self.validator_list_bump
+    }
+
+    /// The validator list is created by the first `AddValidator`.
+    #[inline(always)]
+    pub fn has_validator_list(&self) -> bool {
+        self.validator_list != Pubkey::default()
+    }
+
+    #[inline(always)]
+    pub fn pending_validator_vote(&self) -> Pubkey {
+        self.pending_validator_vote
+    }
+
+    /// Set by `Redelegate` until `CompleteRedelegate` delegates to the new vote account.
+    #[inline(always)]
+    pub fn is_redelegating(&self) -> bool {
+        self.pending_validator_vote != Pubkey::default()
+    }
+
+    /// Lamports moved out of the reserve into transient stakes that have not
+    /// been merged yet, across all validators. Listed validators also track
+    /// their own in `ValidatorStakeInfo::transient_lamports`.
+    #[inline(always)]
+    pub fn transient_lamports(&self) -> u64 {
+        self.transient_lamports
+    }
+
+    /// Decides where the pool delegates: adds and removes validators and redelegates.
+    #[inline(always)]
+    pub fn staker(&self) -> Pubkey {
+        self.staker
+    }
+
+    #[inline(always)]
+    pub fn pending_manager(&self) -> Pubkey {
+        self.pending_manager
+    }
+
+    /// Set by `SetManager` until the proposed manager signs `AcceptManager`.
+    #[inline(always)]
+    pub fn has_pending_manager(&self) -> bool {
+        self.pending_manager != Pubkey::default()
+    }
+
+    /// `PAUSE_*` bits set by the manager with `SetStatus`.
+    #[inline(always)]
+    pub fn status(&self) -> u8 {
+        self.status
+    }
+
+    /// Blocks `Deposit` and `DepositStake`.
+    #[inline(always)]
+    pub fn deposits_paused(&self) -> bool {
+        self.status & PAUSE_DEPOSITS != 0
+    }
+
+    /// Blocks `Withdraw` and `WithdrawSol`; `WithdrawComplete` stays open so
+    /// stake that is already deactivating can always be claimed.
+    #[inline(always)]
+    pub fn withdrawals_paused(&self) -> bool {
+        self.status & PAUSE_WITHDRAWALS != 0
+    }
+
+    /// Blocks the permissionless cranks.
+    #[inline(always)]
+    pub fn cranks_paused(&self) -> bool {
+        self.status & PAUSE_CRANKS != 0
+    }
+
+    /// SPL Token or Token-2022, whichever owns `lst_mint`.
+    #[inline(always)]
+    pub fn token_program(&self) -> Pubkey {
+        if self.token_program == Pubkey::default() {
+            pinocchio_token::ID
+        } else {
+            self.token_program
+        }
+    }
+
+    /// Cap on `total_lamports` that deposits may not push past; 0 for none.
+    #[inline(always)]
+    pub fn max_total_lamports(&self) -> u64 {
+        self.max_total_lamports
+    }
+
+    /// Largest single deposit; 0 for none.
+    #[inline(always)]
+    pub fn max_deposit_lamports(&self) -> u64 {
+        self.max_deposit_lamports
+    }
+
+    /// Smallest single deposit on top of the protocol minimum; 0 for none.
+    #[inline(always)]
+    pub fn min_deposit_lamports(&self) -> u64 {
+        self.min_deposit_lamports
+    }
+
+    /// Checks a deposit adding `lamports` to the pool against the manager's
+    /// limits. Used by `Deposit` and `DepositStake`.
+    #[inline(always)]
+    pub fn check_deposit_limits(&self, lamports: u64) -> Result<(), ProgramError> {
+        if self.min_deposit_lamports != 0 && lamports < self.min_deposit_lamports {
+            return Err(PinocchioError::BelowMinimumDeposit.into());
+        }
+
+        if self.max_deposit_lamports != 0 && lamports > self.max_deposit_lamports {
+            return Err(PinocchioError::DepositAboveMaximum.into());
+        }
+
+        if self.max_total_lamports != 0
+            && self.total_lamports.saturating_add(lamports) > self.max_total_lamports
+        {
+            return Err(PinocchioError::PoolCapExceeded.into());
+        }
+
+        Ok(())
+    }
+
+    /// Set with `SetDepositAuthority` to make the pool permissioned.
+    #[inline(always)]
+    pub fn deposit_authority(&self) -> Pubkey {
+        self.deposit_authority
+    }
+
+    #[inline(always)]
+    pub fn has_deposit_authority(&self) -> bool {
+        self.deposit_authority != Pubkey::default()
+    }
+
+    /// Checks the deposit authority, if the pool has one, signed a deposit.
+    /// Used by `Deposit` and `DepositStake`.
+    #[inline(always)]
+    pub fn check_deposit_authority(
+        &self,
+        deposit_authority: Option<&AccountInfo>,
+    ) -> Result<(), ProgramError> {
+        if !self.has_deposit_authority() {
+            return Ok(());
+        }
+
+        match deposit_authority {
+            Some(authority) if authority.key() == &self.deposit_authority && authority.is_signer() => {
+                Ok(())
+            }
+            _ => Err(PinocchioError::InvalidDepositAuthority.into()),
+        }
+    }
+
+    /// Created with `InitializeRateHistory`.
+    #[inline(always)]
+    pub fn rate_history(&self) -> Pubkey {
+        self.rate_history
+    }
+
+    #[inline(always)]
+    pub fn has_rate_history(&self) -> bool {
+        self.rate_history != Pubkey::default()
+    }
+
+    #[allow(clippy::too_many_arguments)]
     #[inline(always)]
     pub fn set_inner(
         &mut self,
         discriminator: u8,
         lst_mint: Pubkey,
-        authority: Pubkey,
+        manager: Pubkey,
         validator_vote: Pubkey,
         stake_account: Pubkey,
         reserve_stake: Pubkey,
+        version: u8,
         seed: u64,
         bump: u8,
         stake_bump: u8,
         reserve_bump: u8,
         lst_supply: u64,
+        manager_fee_account: Pubkey,
+        deposit_fee_bps: u16,
+        withdrawal_fee_bps: u16,
+        reward_fee_bps: u16,
+        sol_withdrawal_fee_bps: u16,
+        total_lamports: u64,
+        last_update_epoch: u64,
+        reserve_lamports: u64,
+        validator_list: Pubkey,
+        validator_list_bump: u8,
+        pending_validator_vote: Pubkey,
+        transient_lamports: u64,
+        staker: Pubkey,
+        pending_manager: Pubkey,
+        status: u8,
+        token_program: Pubkey,
+        max_total_lamports: u64,
+        max_deposit_lamports: u64,
+        min_deposit_lamports: u64,
+        deposit_authority: Pubkey,
+        rate_history: Pubkey,
     ) {
         self.discriminator = discriminator;
         self.lst_mint = lst_mint;
-        self.authority = authority;
+        self.manager = manager;
         self.validator_vote = validator_vote;
         self.stake_account = stake_account;
         self.reserve_stake = reserve_stake;
-        self._padding_1 = [0u8; 7];
+        self.version = version;
+        self._padding_1 = [0u8; 6];
         self.seed = seed;
         self.bump = bump;
         self.stake_bump = stake_bump;
         self.reserve_bump = reserve_bump;
         self._padding_2 = [0u8; 5];
         self.lst_supply = lst_supply;
+        self.manager_fee_account = manager_fee_account;
+        self.deposit_fee_bps = deposit_fee_bps;
+        self.withdrawal_fee_bps = withdrawal_fee_bps;
+        self.reward_fee_bps = reward_fee_bps;
+        self.sol_withdrawal_fee_bps = sol_withdrawal_fee_bps;
+        self.total_lamports = total_lamports;
+        self.last_update_epoch = last_update_epoch;
+        self.reserve_lamports = reserve_lamports;
+        self.validator_list = validator_list;
+        self.validator_list_bump = validator_list_bump;
+        self._padding_4 = [0u8; 7];
+        self.pending_validator_vote = pending_validator_vote;
+        self.transient_lamports = transient_lamports;
+        self.staker = staker;
+        self.pending_manager = pending_manager;
+        self.status = status;
+        self._padding_5 = [0u8; 7];
+        self.token_program = token_program;
+        self.max_total_lamports = max_total_lamports;
+        self.max_deposit_lamports = max_deposit_lamports;
+        self.min_deposit_lamports = min_deposit_lamports;
+        self.deposit_authority = deposit_authority;
+        self.rate_history = rate_history;
+        self._reserved = [0u8; 8];
     }
 }
@@ -0,0 +1,107 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::calculate_exchange_rate;
+
+/// Pool balance recorded by `UpdatePoolBalance` for one epoch.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct RateHistoryEntry {
+    pub epoch: u64,
+    pub total_lamports: u64,
+    pub lst_supply: u64,
+}
+
+impl RateHistoryEntry {
+    pub const LEN: usize = size_of::<Self>();
+
+    /// Lamports per whole LST at this entry.
+    #[inline(always)]
+    pub fn rate(&self) -> Result<u64, ProgramError> {
+        calculate_exchange_rate(self.total_lamports, self.lst_supply)
+    }
+}
+
+/// Ring buffer of the pool's last `CAPACITY` epoch updates, stored at
+/// `["rate_history", pool_state]`. Once full, each update overwrites the
+/// oldest entry.
+#[repr(C)]
+pub struct RateHistory {
+    pub discriminator: u8,
+    pub bump: u8,
+    _padding: [u8; 2],
+    /// Entries written so far, at most `CAPACITY`
+    pub count: u32,
+    /// Slot the next entry is written to
+    pub head: u32,
+    _padding_2: [u8; 4],
+    pub pool_state: Pubkey,
+    entries: [RateHistoryEntry; RateHistory::CAPACITY],
+}
+
+impl RateHistory {
+    pub const LEN: usize = size_of::<Self>();
+    pub const DISCRIMINATOR: u8 = 5;
+    pub const CAPACITY: usize = 128;
+
+    /// Whether `account` holds a rate history of this program, which tells it
+    /// apart from validator stakes among trailing accounts.
+    #[inline(always)]
+    pub fn is_history(account: &AccountInfo) -> bool {
+        account.is_owned_by(&crate::ID)
+            && account
+                .try_borrow_data()
+                .is_ok_and(|data| data.first() == Some(&Self::DISCRIMINATOR))
+    }
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != RateHistory::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != RateHistory::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn push(&mut self, epoch: u64, total_lamports: u64, lst_supply: u64) {
+        self.entries[self.head as usize] = RateHistoryEntry {
+            epoch,
+            total_lamports,
+            lst_supply,
+        };
+        self.head = ((self.head as usize + 1) % Self::CAPACITY) as u32;
+        if (self.count as usize) < Self::CAPACITY {
+            self.count += 1;
+        }
+    }
+
+    /// Entries from the newest to the oldest.
+    #[inline(always)]
+    pub fn iter(&self) -> impl Iterator<Item = &RateHistoryEntry> {
+        (1..=self.count as usize)
+            .map(|age| &self.entries[(self.head as usize + Self::CAPACITY - age) % Self::CAPACITY])
+    }
+
+    #[inline(always)]
+    pub fn latest(&self) -> Option<&RateHistoryEntry> {
+        self.iter().next()
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, bump: u8, pool_state: Pubkey) {
+        self.discriminator = Self::DISCRIMINATOR;
+        self.bump = bump;
+        self._padding = [0u8; 2];
+        self.count = 0;
+        self.head = 0;
+        self._padding_2 = [0u8; 4];
+        self.pool_state = pool_state;
+    }
+}
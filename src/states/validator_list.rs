@@ -0,0 +1,155 @@
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValidatorStatus {
+    Active = 0,
+    Deactivating = 1,
+}
+
+impl TryFrom<u8> for ValidatorStatus {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(ValidatorStatus::Active),
+            1 => Ok(ValidatorStatus::Deactivating),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct ValidatorStakeInfo {
+    pub vote_account: Pubkey,
+    pub stake_account: Pubkey,
+    pub active_lamports: u64,
+    /// Reserve SOL in transient stakes towards this validator that have not
+    /// merged yet. Zero for entry 0; the primary validator's are the part of
+    /// `PoolState::transient_lamports` no other entry holds.
+    pub transient_lamports: u64,
+    pub last_update_epoch: u64,
+    pub status: u8,
+    pub stake_bump: u8,
+    _padding: [u8; 6],
+}
+
+impl ValidatorStakeInfo {
+    pub const LEN: usize = size_of::<Self>();
+
+    #[inline(always)]
+    pub fn status(&self) -> Result<ValidatorStatus, ProgramError> {
+        ValidatorStatus::try_from(self.status)
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        vote_account: Pubkey,
+        stake_account: Pubkey,
+        active_lamports: u64,
+        last_update_epoch: u64,
+        status: ValidatorStatus,
+        stake_bump: u8,
+    ) {
+        self.vote_account = vote_account;
+        self.stake_account = stake_account;
+        self.active_lamports = active_lamports;
+        self.transient_lamports = 0;
+        self.last_update_epoch = last_update_epoch;
+        self.status = status as u8;
+        self.stake_bump = stake_bump;
+        self._padding = [0u8; 6];
+    }
+}
+
+/// Header of the validator list account, followed by `count` entries.
+/// Entry 0 is always the pool's primary `stake_account`.
+#[repr(C)]
+pub struct ValidatorList {
+    pub discriminator: u8,
+    pub bump: u8,
+    _padding: [u8; 2],
+    pub count: u32,
+    pub pool_state: Pubkey,
+}
+
+impl ValidatorList {
+    pub const LEN: usize = size_of::<Self>();
+    pub const DISCRIMINATOR: u8 = 2;
+
+    #[inline(always)]
+    pub fn space(count: usize) -> usize {
+        Self::LEN + count * ValidatorStakeInfo::LEN
+    }
+
+    #[inline(always)]
+    pub fn load_mut(
+        bytes: &mut [u8],
+    ) -> Result<(&mut Self, &mut [ValidatorStakeInfo]), ProgramError> {
+        if bytes.len() < ValidatorList::LEN
+            || !(bytes.len() - ValidatorList::LEN).is_multiple_of(ValidatorStakeInfo::LEN)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (header, entries) = bytes.split_at_mut(ValidatorList::LEN);
+        let header =
+            unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(header.as_mut_ptr()) };
+        let entries = unsafe {
+            core::slice::from_raw_parts_mut(
+                entries.as_mut_ptr() as *mut ValidatorStakeInfo,
+                entries.len() / ValidatorStakeInfo::LEN,
+            )
+        };
+
+        if header.count as usize != entries.len() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok((header, entries))
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<(&Self, &[ValidatorStakeInfo]), ProgramError> {
+        if bytes.len() < ValidatorList::LEN
+            || !(bytes.len() - ValidatorList::LEN).is_multiple_of(ValidatorStakeInfo::LEN)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (header, entries) = bytes.split_at(ValidatorList::LEN);
+        let header = unsafe { &*core::mem::transmute::<*const u8, *const Self>(header.as_ptr()) };
+        let entries = unsafe {
+            core::slice::from_raw_parts(
+                entries.as_ptr() as *const ValidatorStakeInfo,
+                entries.len() / ValidatorStakeInfo::LEN,
+            )
+        };
+
+        if header.count as usize != entries.len() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok((header, entries))
+    }
+
+    /// Header only, for use while the entry count is being changed.
+    #[inline(always)]
+    pub fn load_header_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() < ValidatorList::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, bump: u8, count: u32, pool_state: Pubkey) {
+        self.discriminator = Self::DISCRIMINATOR;
+        self.bump = bump;
+        self._padding = [0u8; 2];
+        self.count = count;
+        self.pool_state = pool_state;
+    }
+}
@@ -0,0 +1,137 @@
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+/// An unbonding position recorded in a `WithdrawLedger`.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct WithdrawLedgerEntry {
+    pub withdraw_stake: Pubkey,
+    pub nonce: u64,
+    /// Lamports split off the pool stake by `Withdraw`
+    pub lamports: u64,
+    /// First epoch in which `WithdrawComplete` can succeed
+    pub claimable_epoch: u64,
+}
+
+impl WithdrawLedgerEntry {
+    pub const LEN: usize = size_of::<Self>();
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        withdraw_stake: Pubkey,
+        nonce: u64,
+        lamports: u64,
+        claimable_epoch: u64,
+    ) {
+        self.withdraw_stake = withdraw_stake;
+        self.nonce = nonce;
+        self.lamports = lamports;
+        self.claimable_epoch = claimable_epoch;
+    }
+}
+
+/// Header of a user's withdraw ledger at `["ledger", pool_state, owner]`,
+/// followed by `count` entries, one per outstanding withdraw stake.
+#[repr(C)]
+pub struct WithdrawLedger {
+    pub discriminator: u8,
+    pub bump: u8,
+    _padding: [u8; 2],
+    pub count: u32,
+    pub pool_state: Pubkey,
+    pub owner: Pubkey,
+    /// Lowest nonce `Withdraw` accepts with the ledger, and the one it uses
+    /// when passed nonce 0
+    pub next_nonce: u64,
+}
+
+impl WithdrawLedger {
+    pub const LEN: usize = size_of::<Self>();
+    pub const DISCRIMINATOR: u8 = 4;
+
+    #[inline(always)]
+    pub fn space(count: usize) -> usize {
+        Self::LEN + count * WithdrawLedgerEntry::LEN
+    }
+
+    /// Whether `account` holds a ledger of this program, which tells it apart
+    /// from a withdraw ticket among trailing accounts.
+    #[inline(always)]
+    pub fn is_ledger(account: &AccountInfo) -> bool {
+        account.is_owned_by(&crate::ID)
+            && account
+                .try_borrow_data()
+                .is_ok_and(|data| data.first() == Some(&Self::DISCRIMINATOR))
+    }
+
+    #[inline(always)]
+    pub fn load_mut(
+        bytes: &mut [u8],
+    ) -> Result<(&mut Self, &mut [WithdrawLedgerEntry]), ProgramError> {
+        if bytes.len() < WithdrawLedger::LEN
+            || !(bytes.len() - WithdrawLedger::LEN).is_multiple_of(WithdrawLedgerEntry::LEN)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (header, entries) = bytes.split_at_mut(WithdrawLedger::LEN);
+        let header =
+            unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(header.as_mut_ptr()) };
+        let entries = unsafe {
+            core::slice::from_raw_parts_mut(
+                entries.as_mut_ptr() as *mut WithdrawLedgerEntry,
+                entries.len() / WithdrawLedgerEntry::LEN,
+            )
+        };
+
+        if header.count as usize != entries.len() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok((header, entries))
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<(&Self, &[WithdrawLedgerEntry]), ProgramError> {
+        if bytes.len() < WithdrawLedger::LEN
+            || !(bytes.len() - WithdrawLedger::LEN).is_multiple_of(WithdrawLedgerEntry::LEN)
+        {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (header, entries) = bytes.split_at(WithdrawLedger::LEN);
+        let header = unsafe { &*core::mem::transmute::<*const u8, *const Self>(header.as_ptr()) };
+        let entries = unsafe {
+            core::slice::from_raw_parts(
+                entries.as_ptr() as *const WithdrawLedgerEntry,
+                entries.len() / WithdrawLedgerEntry::LEN,
+            )
+        };
+
+        if header.count as usize != entries.len() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok((header, entries))
+    }
+
+    /// Header only, for use while the entry count is being changed.
+    #[inline(always)]
+    pub fn load_header_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() < WithdrawLedger::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(&mut self, bump: u8, pool_state: Pubkey, owner: Pubkey) {
+        self.discriminator = Self::DISCRIMINATOR;
+        self.bump = bump;
+        self._padding = [0u8; 2];
+        self.count = 0;
+        self.pool_state = pool_state;
+        self.owner = owner;
+        self.next_nonce = 1;
+    }
+}
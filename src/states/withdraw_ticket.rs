@@ -0,0 +1,52 @@
+use pinocchio::{program_error::ProgramError, pubkey::Pubkey};
+
+/// Transferable claim on a deactivating withdraw stake account, stored at
+/// `["ticket", withdraw_stake]`. The PDA is the stake's withdraw authority,
+/// so only `WithdrawComplete` presented with the ticket can claim it.
+#[repr(C)]
+pub struct WithdrawTicket {
+    pub discriminator: u8,
+    pub bump: u8,
+    _padding: [u8; 6],
+    pub pool_state: Pubkey,
+    pub withdraw_stake: Pubkey,
+    /// Receives the stake's lamports; changed with `TransferWithdrawTicket`
+    pub owner: Pubkey,
+}
+
+impl WithdrawTicket {
+    pub const LEN: usize = size_of::<Self>();
+    pub const DISCRIMINATOR: u8 = 3;
+
+    #[inline(always)]
+    pub fn load_mut(bytes: &mut [u8]) -> Result<&mut Self, ProgramError> {
+        if bytes.len() != WithdrawTicket::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &mut *core::mem::transmute::<*mut u8, *mut Self>(bytes.as_mut_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() != WithdrawTicket::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn set_inner(
+        &mut self,
+        bump: u8,
+        pool_state: Pubkey,
+        withdraw_stake: Pubkey,
+        owner: Pubkey,
+    ) {
+        self.discriminator = Self::DISCRIMINATOR;
+        self.bump = bump;
+        self._padding = [0u8; 6];
+        self.pool_state = pool_state;
+        self.withdraw_stake = withdraw_stake;
+        self.owner = owner;
+    }
+}
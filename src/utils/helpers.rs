@@ -9,7 +9,10 @@ use pinocchio::{
 use pinocchio_associated_token_account::instructions::Create;
 use pinocchio_system::instructions::CreateAccount;
 
-use crate::{PinocchioError, PoolState};
+use crate::{
+    ACCOUNT_TYPE_ACCOUNT, ACCOUNT_TYPE_MINT, BPS_DENOMINATOR, EXCHANGE_RATE_SCALE, PinocchioError,
+    PoolState, STAKE_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, TOKEN_METADATA_PROGRAM_ID,
+};
 
 pub struct SignerAccount;
 
@@ -31,16 +34,18 @@ impl ProgramAccount {
         account: &pinocchio::account_info::AccountInfo,
     ) -> Result<(), pinocchio::program_error::ProgramError> {
         if account.key() != &pinocchio_system::ID {
-            return Err(PinocchioError::InvalidOwner.into());
+            return Err(PinocchioError::InvalidProgram.into());
         }
         Ok(())
     }
 
+    /// Either SPL Token or Token-2022; processors also match it against
+    /// `PoolState::token_program`.
     pub fn check_token_program(
         account: &pinocchio::account_info::AccountInfo,
     ) -> Result<(), pinocchio::program_error::ProgramError> {
-        if account.key() != &pinocchio_token::ID {
-            return Err(PinocchioError::InvalidOwner.into());
+        if account.key() != &pinocchio_token::ID && account.key() != &TOKEN_2022_PROGRAM_ID {
+            return Err(PinocchioError::InvalidProgram.into());
         }
         Ok(())
     }
@@ -49,7 +54,23 @@ impl ProgramAccount {
         account: &pinocchio::account_info::AccountInfo,
     ) -> Result<(), pinocchio::program_error::ProgramError> {
         if account.key() != &pinocchio_associated_token_account::ID {
-            return Err(PinocchioError::InvalidOwner.into());
+            return Err(PinocchioError::InvalidProgram.into());
+        }
+        Ok(())
+    }
+
+    pub fn check_stake_program(
+        account: &pinocchio::account_info::AccountInfo,
+    ) -> Result<(), pinocchio::program_error::ProgramError> {
+        if account.key() != &STAKE_PROGRAM_ID {
+            return Err(PinocchioError::InvalidProgram.into());
+        }
+        Ok(())
+    }
+
+    pub fn check_token_metadata_program(account: &AccountInfo) -> Result<(), ProgramError> {
+        if account.key() != &TOKEN_METADATA_PROGRAM_ID {
+            return Err(PinocchioError::InvalidProgram.into());
         }
         Ok(())
     }
@@ -62,6 +83,9 @@ impl ProgramAccount {
         }
 
         if account.data_len() != PoolState::LEN {
+            if PoolState::LEGACY_LENS.contains(&account.data_len()) {
+                return Err(PinocchioError::PoolMigrationRequired.into());
+            }
             return Err(PinocchioError::InvalidAccountData.into());
         }
 
@@ -74,11 +98,11 @@ impl ProgramAccount {
         let (expected_pubkey, expected_bump) = find_program_address(&seed_bytes, &crate::ID);
 
         if *account.key() != expected_pubkey {
-            return Err(ProgramError::InvalidAccountData);
+            return Err(PinocchioError::InvalidPoolAddress.into());
         }
 
         if bump != expected_bump {
-            return Err(ProgramError::InvalidSeeds);
+            return Err(PinocchioError::InvalidPoolBump.into());
         }
 
         Ok(())
@@ -113,12 +137,14 @@ impl ProgramAccount {
 pub struct Mint;
 
 impl Mint {
-    pub fn check(account: &AccountInfo) -> Result<(), ProgramError> {
-        if !account.is_owned_by(&pinocchio_token::ID) {
+    pub fn check(account: &AccountInfo, token_program: &Pubkey) -> Result<(), ProgramError> {
+        if !account.is_owned_by(token_program) {
             return Err(PinocchioError::InvalidOwner.into());
         }
 
-        if account.data_len().ne(&pinocchio_token::state::Mint::LEN) {
+        if account.data_len().ne(&pinocchio_token::state::Mint::LEN)
+            && !has_token_2022_account_type(account, token_program, ACCOUNT_TYPE_MINT)?
+        {
             return Err(PinocchioError::InvalidAccountData.into());
         }
 
@@ -128,14 +154,15 @@ impl Mint {
 
 pub struct Token;
 impl Token {
-    pub fn check(account: &AccountInfo) -> Result<(), ProgramError> {
-        if !account.is_owned_by(&pinocchio_token::ID) {
+    pub fn check(account: &AccountInfo, token_program: &Pubkey) -> Result<(), ProgramError> {
+        if !account.is_owned_by(token_program) {
             return Err(PinocchioError::InvalidOwner.into());
         }
 
         if account
             .data_len()
             .ne(&pinocchio_token::state::TokenAccount::LEN)
+            && !has_token_2022_account_type(account, token_program, ACCOUNT_TYPE_ACCOUNT)?
         {
             return Err(PinocchioError::InvalidAccountData.into());
         }
@@ -144,6 +171,22 @@ impl Token {
     }
 }
 
+/// Token-2022 accounts with extensions are longer than the base layout and
+/// carry their account type right after the base token account length.
+fn has_token_2022_account_type(
+    account: &AccountInfo,
+    token_program: &Pubkey,
+    account_type: u8,
+) -> Result<bool, ProgramError> {
+    const TYPE_OFFSET: usize = pinocchio_token::state::TokenAccount::LEN;
+
+    if token_program != &TOKEN_2022_PROGRAM_ID || account.data_len() <= TYPE_OFFSET {
+        return Ok(false);
+    }
+
+    Ok(account.try_borrow_data()?[TYPE_OFFSET] == account_type)
+}
+
 pub struct AssociatedToken;
 
 impl AssociatedToken {
@@ -153,7 +196,7 @@ impl AssociatedToken {
         mint: Pubkey,
         token_program: Pubkey,
     ) -> Result<(), ProgramError> {
-        Token::check(account)?;
+        Token::check(account, &token_program)?;
 
         let (expected_ata, _bump) = find_program_address(
             &[authority.as_ref(), token_program.as_ref(), mint.as_ref()],
@@ -161,7 +204,7 @@ impl AssociatedToken {
         );
 
         if *account.key() != expected_ata {
-            return Err(PinocchioError::InvalidAccountData.into());
+            return Err(PinocchioError::InvalidAssociatedTokenAccount.into());
         }
 
         Ok(())
@@ -186,3 +229,55 @@ impl AssociatedToken {
         .invoke()
     }
 }
+
+/// Fee owed on `amount` at `fee_bps` basis points, rounded down.
+pub fn calculate_fee(amount: u64, fee_bps: u16) -> Result<u64, ProgramError> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .checked_div(BPS_DENOMINATOR as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(fee as u64)
+}
+
+/// Lamports per whole LST for a pool holding `total_lamports` against
+/// `lst_supply`. An empty pool mints 1:1.
+pub fn calculate_exchange_rate(total_lamports: u64, lst_supply: u64) -> Result<u64, ProgramError> {
+    if lst_supply == 0 {
+        return Ok(EXCHANGE_RATE_SCALE);
+    }
+
+    let rate = (total_lamports as u128)
+        .checked_mul(EXCHANGE_RATE_SCALE as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .checked_div(lst_supply as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    u64::try_from(rate).map_err(|_| ProgramError::ArithmeticOverflow)
+}
+
+/// LST base units per SOL, the inverse of `calculate_exchange_rate`.
+///
+/// A pool with LST outstanding but no lamports has no price for SOL, and
+/// `Deposit` rejects it, so the rate is 0 rather than an error.
+pub fn calculate_inverse_exchange_rate(
+    total_lamports: u64,
+    lst_supply: u64,
+) -> Result<u64, ProgramError> {
+    if lst_supply == 0 {
+        return Ok(EXCHANGE_RATE_SCALE);
+    }
+
+    if total_lamports == 0 {
+        return Ok(0);
+    }
+
+    let rate = (lst_supply as u128)
+        .checked_mul(EXCHANGE_RATE_SCALE as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .checked_div(total_lamports as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    u64::try_from(rate).map_err(|_| ProgramError::ArithmeticOverflow)
+}
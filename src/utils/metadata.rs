@@ -0,0 +1,244 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Seed, Signer},
+    msg,
+    program_error::ProgramError,
+    pubkey::{Pubkey, find_program_address},
+};
+
+use crate::{MAX_NAME_LEN, MAX_SYMBOL_LEN, MAX_URI_LEN, PinocchioError, TOKEN_METADATA_PROGRAM_ID};
+
+/// Metaplex `CreateMetadataAccountV3` / `UpdateMetadataAccountV2` discriminators
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+const UPDATE_METADATA_ACCOUNT_V2: u8 = 15;
+
+/// Borsh `DataV2` with no royalties, creators, collection or uses
+const DATA_V2_MAX_LEN: usize = 4 + MAX_NAME_LEN + 4 + MAX_SYMBOL_LEN + 4 + MAX_URI_LEN + 2 + 3;
+
+/// LST name, symbol and URI as passed in instruction data:
+/// `name_len: u8 | name | symbol_len: u8 | symbol | uri_len: u8 | uri`.
+pub struct TokenMetadata {
+    name: [u8; MAX_NAME_LEN],
+    name_len: u8,
+    symbol: [u8; MAX_SYMBOL_LEN],
+    symbol_len: u8,
+    uri: [u8; MAX_URI_LEN],
+    uri_len: u8,
+}
+
+impl TokenMetadata {
+    pub fn name(&self) -> &[u8] {
+        &self.name[..self.name_len as usize]
+    }
+
+    pub fn symbol(&self) -> &[u8] {
+        &self.symbol[..self.symbol_len as usize]
+    }
+
+    pub fn uri(&self) -> &[u8] {
+        &self.uri[..self.uri_len as usize]
+    }
+
+    /// Splits one length-prefixed UTF-8 field off the front of `data`.
+    fn take_field(data: &[u8], max_len: usize) -> Result<(&[u8], &[u8]), ProgramError> {
+        let (&len, rest) = data
+            .split_first()
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        let len = len as usize;
+
+        if len > max_len {
+            return Err(PinocchioError::InvalidTokenMetadata.into());
+        }
+        if rest.len() < len {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let (field, rest) = rest.split_at(len);
+        if core::str::from_utf8(field).is_err() {
+            return Err(PinocchioError::InvalidTokenMetadata.into());
+        }
+
+        Ok((field, rest))
+    }
+
+    /// Writes the Borsh `DataV2` for this metadata into `buf`, returning the
+    /// number of bytes written.
+    fn write_data_v2(&self, buf: &mut [u8]) -> usize {
+        let mut offset = 0;
+
+        for field in [self.name(), self.symbol(), self.uri()] {
+            buf[offset..offset + 4].copy_from_slice(&(field.len() as u32).to_le_bytes());
+            offset += 4;
+            buf[offset..offset + field.len()].copy_from_slice(field);
+            offset += field.len();
+        }
+
+        // seller_fee_basis_points = 0, creators/collection/uses = None
+        buf[offset..offset + 5].copy_from_slice(&[0; 5]);
+        offset + 5
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for TokenMetadata {
+    type Error = ProgramError;
+
+    fn try_from(data: &'a [u8]) -> Result<Self, Self::Error> {
+        let (name, rest) = Self::take_field(data, MAX_NAME_LEN)?;
+        let (symbol, rest) = Self::take_field(rest, MAX_SYMBOL_LEN)?;
+        let (uri, rest) = Self::take_field(rest, MAX_URI_LEN)?;
+
+        if !rest.is_empty() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        if name.is_empty() || symbol.is_empty() {
+            return Err(PinocchioError::InvalidTokenMetadata.into());
+        }
+
+        let mut metadata = Self {
+            name: [0; MAX_NAME_LEN],
+            name_len: name.len() as u8,
+            symbol: [0; MAX_SYMBOL_LEN],
+            symbol_len: symbol.len() as u8,
+            uri: [0; MAX_URI_LEN],
+            uri_len: uri.len() as u8,
+        };
+        metadata.name[..name.len()].copy_from_slice(name);
+        metadata.symbol[..symbol.len()].copy_from_slice(symbol);
+        metadata.uri[..uri.len()].copy_from_slice(uri);
+
+        Ok(metadata)
+    }
+}
+
+/// Checks `metadata` is the Metaplex metadata PDA for `mint`.
+pub fn verify_metadata_account(metadata: &AccountInfo, mint: &Pubkey) -> Result<(), ProgramError> {
+    let (expected, _) = find_program_address(
+        &[b"metadata", &TOKEN_METADATA_PROGRAM_ID, mint.as_ref()],
+        &TOKEN_METADATA_PROGRAM_ID,
+    );
+
+    if metadata.key() != &expected {
+        return Err(PinocchioError::InvalidMetadataAccount.into());
+    }
+    Ok(())
+}
+
+/// Create the Metaplex metadata account for the LST mint. The pool PDA is
+/// both mint authority and update authority, so it signs twice.
+pub fn create_metadata_account<'a>(
+    metadata_account: &'a AccountInfo,
+    mint: &'a AccountInfo,
+    pool_state: &'a AccountInfo,
+    payer: &'a AccountInfo,
+    system_program: &'a AccountInfo,
+    metadata: &TokenMetadata,
+    signer_seeds: &[Seed],
+) -> ProgramResult {
+    let signer = [Signer::from(signer_seeds)];
+
+    let mut data = [0u8; 1 + DATA_V2_MAX_LEN + 2];
+    data[0] = CREATE_METADATA_ACCOUNT_V3;
+    let mut len = 1 + metadata.write_data_v2(&mut data[1..]);
+
+    // is_mutable = true, collection_details = None
+    data[len] = 1;
+    data[len + 1] = 0;
+    len += 2;
+
+    let ix = Instruction {
+        program_id: &TOKEN_METADATA_PROGRAM_ID,
+        accounts: &[
+            AccountMeta {
+                pubkey: metadata_account.key(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: mint.key(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: pool_state.key(),
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: payer.key(),
+                is_signer: true,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: pool_state.key(),
+                is_signer: true,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: system_program.key(),
+                is_signer: false,
+                is_writable: false,
+            },
+        ],
+        data: &data[..len],
+    };
+
+    pinocchio::program::invoke_signed(
+        &ix,
+        &[
+            metadata_account,
+            mint,
+            pool_state,
+            payer,
+            pool_state,
+            system_program,
+        ],
+        &signer,
+    )?;
+
+    msg!("LST metadata created");
+    Ok(())
+}
+
+/// Replace the name, symbol and URI on the LST metadata account, signed by
+/// the pool PDA as update authority.
+pub fn update_metadata_account<'a>(
+    metadata_account: &'a AccountInfo,
+    pool_state: &'a AccountInfo,
+    metadata: &TokenMetadata,
+    signer_seeds: &[Seed],
+) -> ProgramResult {
+    let signer = [Signer::from(signer_seeds)];
+
+    let mut data = [0u8; 2 + DATA_V2_MAX_LEN + 3];
+    data[0] = UPDATE_METADATA_ACCOUNT_V2;
+    data[1] = 1; // Some(data)
+    let mut len = 2 + metadata.write_data_v2(&mut data[2..]);
+
+    // new_update_authority, primary_sale_happened, is_mutable = None
+    data[len..len + 3].copy_from_slice(&[0; 3]);
+    len += 3;
+
+    let ix = Instruction {
+        program_id: &TOKEN_METADATA_PROGRAM_ID,
+        accounts: &[
+            AccountMeta {
+                pubkey: metadata_account.key(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: pool_state.key(),
+                is_signer: true,
+                is_writable: false,
+            },
+        ],
+        data: &data[..len],
+    };
+
+    pinocchio::program::invoke_signed(&ix, &[metadata_account, pool_state], &signer)?;
+
+    msg!("LST metadata updated");
+    Ok(())
+}
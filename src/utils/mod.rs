@@ -1,5 +1,9 @@
 pub mod helpers;
+pub mod metadata;
 pub mod stake;
+pub mod token;
 
 pub use helpers::*;
+pub use metadata::*;
 pub use stake::*;
+pub use token::*;
@@ -1,15 +1,15 @@
-// src/helpers/stake.rs (or src/utils/stake.rs)
-
 use pinocchio::{
     ProgramResult,
     account_info::AccountInfo,
     instruction::{AccountMeta, Instruction, Seed, Signer},
     msg,
-    sysvars::{Sysvar, rent::Rent},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{Sysvar, clock::Clock, rent::Rent},
 };
 use pinocchio_system::instructions::{Allocate, Assign, CreateAccount};
 
-use crate::constants::*;
+use crate::{PinocchioError, constants::*};
 
 /// Create a stake account with PDA signing
 pub fn create_stake_account<'a>(
@@ -34,7 +34,7 @@ pub fn create_stake_account<'a>(
     Ok(())
 }
 
-pub fn reinit_stake_account<'a>(stake_account: &'a AccountInfo, seeds: &[Seed]) -> ProgramResult {
+pub fn reinit_stake_account(stake_account: &AccountInfo, seeds: &[Seed]) -> ProgramResult {
     let signer = [Signer::from(seeds)];
 
     // Allocate 200 bytes
@@ -351,3 +351,502 @@ pub fn withdraw_stake<'a>(
     msg!("Stake withdrawn");
     Ok(())
 }
+
+/// Move the staker (`stake_authorize` = 0) or withdrawer (1) authority of a
+/// stake account to `new_authority`. `authority` must sign the transaction.
+pub fn authorize_stake<'a>(
+    stake_account: &'a AccountInfo,
+    clock: &'a AccountInfo,
+    authority: &'a AccountInfo,
+    new_authority: &Pubkey,
+    stake_authorize: u32,
+) -> ProgramResult {
+    let mut data = [0u8; 40];
+    data[0..4].copy_from_slice(&1u32.to_le_bytes());
+    data[4..36].copy_from_slice(new_authority.as_ref());
+    data[36..40].copy_from_slice(&stake_authorize.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: &STAKE_PROGRAM_ID,
+        accounts: &[
+            AccountMeta {
+                pubkey: stake_account.key(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: clock.key(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: authority.key(),
+                is_signer: true,
+                is_writable: false,
+            },
+        ],
+        data: &data,
+    };
+
+    pinocchio::program::invoke(&ix, &[stake_account, clock, authority])?;
+
+    msg!("Stake authorized");
+    Ok(())
+}
+
+/// `authorize_stake` with a PDA as the current authority.
+pub fn authorize_stake_signed<'a>(
+    stake_account: &'a AccountInfo,
+    clock: &'a AccountInfo,
+    authority: &'a AccountInfo,
+    new_authority: &Pubkey,
+    stake_authorize: u32,
+    signer_seeds: &[Seed],
+) -> ProgramResult {
+    let signer = [Signer::from(signer_seeds)];
+    let mut data = [0u8; 40];
+    data[0..4].copy_from_slice(&1u32.to_le_bytes());
+    data[4..36].copy_from_slice(new_authority.as_ref());
+    data[36..40].copy_from_slice(&stake_authorize.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: &STAKE_PROGRAM_ID,
+        accounts: &[
+            AccountMeta {
+                pubkey: stake_account.key(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: clock.key(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: authority.key(),
+                is_signer: true,
+                is_writable: false,
+            },
+        ],
+        data: &data,
+    };
+
+    pinocchio::program::invoke_signed(&ix, &[stake_account, clock, authority], &signer)?;
+
+    msg!("Stake authorized");
+    Ok(())
+}
+
+// Stake account state. Fields are byte arrays so the view has alignment 1
+// and can be laid over account data directly; getters decode little-endian.
+
+/// Warmup/cooldown rate in force since `reduce_stake_warmup_cooldown`.
+const WARMUP_COOLDOWN_RATE: f64 = 0.09;
+
+#[repr(C)]
+pub struct Authorized {
+    pub staker: Pubkey,
+    pub withdrawer: Pubkey,
+}
+
+#[repr(C)]
+pub struct Lockup {
+    unix_timestamp: [u8; 8],
+    epoch: [u8; 8],
+    pub custodian: Pubkey,
+}
+
+impl Lockup {
+    #[inline(always)]
+    pub fn unix_timestamp(&self) -> i64 {
+        i64::from_le_bytes(self.unix_timestamp)
+    }
+
+    #[inline(always)]
+    pub fn epoch(&self) -> u64 {
+        u64::from_le_bytes(self.epoch)
+    }
+
+    /// Ignores the custodian, which this program never acts as.
+    #[inline(always)]
+    pub fn is_in_force(&self, unix_timestamp: i64, epoch: u64) -> bool {
+        self.unix_timestamp() > unix_timestamp || self.epoch() > epoch
+    }
+}
+
+#[repr(C)]
+pub struct Meta {
+    rent_exempt_reserve: [u8; 8],
+    pub authorized: Authorized,
+    pub lockup: Lockup,
+}
+
+impl Meta {
+    #[inline(always)]
+    pub fn rent_exempt_reserve(&self) -> u64 {
+        u64::from_le_bytes(self.rent_exempt_reserve)
+    }
+}
+
+#[repr(C)]
+pub struct Delegation {
+    pub voter_pubkey: Pubkey,
+    stake: [u8; 8],
+    activation_epoch: [u8; 8],
+    deactivation_epoch: [u8; 8],
+    /// Deprecated by the stake program, kept for the layout
+    _warmup_cooldown_rate: [u8; 8],
+}
+
+/// How much of a delegation is effective, activating and deactivating in
+/// a given epoch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StakeActivation {
+    pub effective: u64,
+    pub activating: u64,
+    pub deactivating: u64,
+}
+
+impl StakeActivation {
+    /// Neither warming up nor cooling down.
+    #[inline(always)]
+    pub fn is_settled(&self) -> bool {
+        self.activating == 0 && self.deactivating == 0
+    }
+}
+
+impl Delegation {
+    #[inline(always)]
+    pub fn stake(&self) -> u64 {
+        u64::from_le_bytes(self.stake)
+    }
+
+    #[inline(always)]
+    pub fn activation_epoch(&self) -> u64 {
+        u64::from_le_bytes(self.activation_epoch)
+    }
+
+    /// `u64::MAX` until the stake is deactivated.
+    #[inline(always)]
+    pub fn deactivation_epoch(&self) -> u64 {
+        u64::from_le_bytes(self.deactivation_epoch)
+    }
+
+    #[inline(always)]
+    pub fn is_deactivating(&self) -> bool {
+        self.deactivation_epoch() != u64::MAX
+    }
+
+    /// Mirrors the stake program's warmup and cooldown, so the result matches
+    /// what it uses for merges and withdrawals in `epoch`.
+    pub fn activation(&self, epoch: u64, history: &StakeHistory) -> StakeActivation {
+        let (effective, activating) = self.effective_and_activating(epoch, history);
+        let deactivation_epoch = self.deactivation_epoch();
+
+        if epoch < deactivation_epoch {
+            return StakeActivation {
+                effective,
+                activating,
+                deactivating: 0,
+            };
+        }
+
+        if epoch == deactivation_epoch {
+            return StakeActivation {
+                effective,
+                activating: 0,
+                deactivating: effective,
+            };
+        }
+
+        let Some(mut cluster) = history.get(deactivation_epoch) else {
+            return StakeActivation::default();
+        };
+
+        let mut prev_epoch = deactivation_epoch;
+        let mut current_effective = effective;
+        loop {
+            let current_epoch = prev_epoch + 1;
+            if cluster.deactivating == 0 {
+                break;
+            }
+
+            // This delegation's share of the stake the cluster let go of
+            let weight = current_effective as f64 / cluster.deactivating as f64;
+            let newly_not_effective = cluster.effective as f64 * WARMUP_COOLDOWN_RATE;
+            current_effective =
+                current_effective.saturating_sub(((weight * newly_not_effective) as u64).max(1));
+
+            if current_effective == 0 || current_epoch >= epoch {
+                break;
+            }
+            match history.get(current_epoch) {
+                Some(entry) => {
+                    prev_epoch = current_epoch;
+                    cluster = entry;
+                }
+                None => break,
+            }
+        }
+
+        StakeActivation {
+            effective: current_effective,
+            activating: 0,
+            deactivating: current_effective,
+        }
+    }
+
+    fn effective_and_activating(&self, epoch: u64, history: &StakeHistory) -> (u64, u64) {
+        let delegated = self.stake();
+        let activation_epoch = self.activation_epoch();
+
+        // Bootstrap stakes are effective from genesis
+        if activation_epoch == u64::MAX {
+            return (delegated, 0);
+        }
+        if activation_epoch == self.deactivation_epoch() || epoch < activation_epoch {
+            return (0, 0);
+        }
+        if epoch == activation_epoch {
+            return (0, delegated);
+        }
+
+        let Some(mut cluster) = history.get(activation_epoch) else {
+            // Older than the history the sysvar keeps
+            return (delegated, 0);
+        };
+
+        let mut prev_epoch = activation_epoch;
+        let mut current_effective = 0u64;
+        loop {
+            let current_epoch = prev_epoch + 1;
+            if cluster.activating == 0 {
+                break;
+            }
+
+            let remaining = delegated - current_effective;
+            let weight = remaining as f64 / cluster.activating as f64;
+            let newly_effective = cluster.effective as f64 * WARMUP_COOLDOWN_RATE;
+            current_effective += ((weight * newly_effective) as u64).max(1);
+
+            if current_effective >= delegated {
+                current_effective = delegated;
+                break;
+            }
+            if current_epoch >= epoch || current_epoch >= self.deactivation_epoch() {
+                break;
+            }
+            match history.get(current_epoch) {
+                Some(entry) => {
+                    prev_epoch = current_epoch;
+                    cluster = entry;
+                }
+                None => break,
+            }
+        }
+
+        (current_effective, delegated - current_effective)
+    }
+}
+
+#[repr(C)]
+pub struct Stake {
+    pub delegation: Delegation,
+    credits_observed: [u8; 8],
+}
+
+impl Stake {
+    #[inline(always)]
+    pub fn credits_observed(&self) -> u64 {
+        u64::from_le_bytes(self.credits_observed)
+    }
+}
+
+/// Zero-copy view of a stake account. `meta` is only meaningful once the
+/// account is initialized and `stake` once it is delegated.
+#[repr(C)]
+pub struct StakeStateV2 {
+    tag: [u8; 4],
+    meta: Meta,
+    stake: Stake,
+    _stake_flags: u8,
+}
+
+impl StakeStateV2 {
+    pub const LEN: usize = size_of::<Self>();
+
+    pub const UNINITIALIZED: u32 = 0;
+    pub const INITIALIZED: u32 = 1;
+    pub const STAKE: u32 = 2;
+
+    #[inline(always)]
+    pub fn load(bytes: &[u8]) -> Result<&Self, ProgramError> {
+        if bytes.len() < StakeStateV2::LEN {
+            return Err(PinocchioError::InvalidStakeState.into());
+        }
+        Ok(unsafe { &*core::mem::transmute::<*const u8, *const Self>(bytes.as_ptr()) })
+    }
+
+    #[inline(always)]
+    pub fn state(&self) -> u32 {
+        u32::from_le_bytes(self.tag)
+    }
+
+    #[inline(always)]
+    pub fn is_uninitialized(&self) -> bool {
+        self.state() == Self::UNINITIALIZED
+    }
+
+    /// Authorities and lockup of an initialized or delegated account.
+    #[inline(always)]
+    pub fn meta(&self) -> Option<&Meta> {
+        matches!(self.state(), Self::INITIALIZED | Self::STAKE).then_some(&self.meta)
+    }
+
+    #[inline(always)]
+    pub fn stake(&self) -> Option<&Stake> {
+        (self.state() == Self::STAKE).then_some(&self.stake)
+    }
+
+    #[inline(always)]
+    pub fn delegation(&self) -> Option<&Delegation> {
+        self.stake().map(|stake| &stake.delegation)
+    }
+
+    /// The delegated stake plus the rent reserve. Rewards are added to the
+    /// delegation but lamports sent to the account are not, so unlike the
+    /// balance this only grows when the stake earns.
+    #[inline(always)]
+    pub fn delegated_lamports(&self) -> Option<u64> {
+        let meta = self.meta()?;
+        self.delegation()?
+            .stake()
+            .checked_add(meta.rent_exempt_reserve())
+    }
+}
+
+/// Cluster-wide stake in one epoch, as recorded in the stake history sysvar.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StakeHistoryEntry {
+    pub effective: u64,
+    pub activating: u64,
+    pub deactivating: u64,
+}
+
+/// Zero-copy view of the stake history sysvar: a `u64` count followed by
+/// `(epoch, effective, activating, deactivating)` entries, newest first.
+pub struct StakeHistory<'a> {
+    entries: &'a [u8],
+}
+
+impl<'a> StakeHistory<'a> {
+    const ENTRY_LEN: usize = 32;
+
+    pub fn new(data: &'a [u8]) -> Result<Self, ProgramError> {
+        let count = data
+            .get(0..8)
+            .map(|count| u64::from_le_bytes(count.try_into().unwrap()) as usize)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        let entries = count
+            .checked_mul(Self::ENTRY_LEN)
+            .and_then(|len| data.get(8..8 + len))
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        Ok(Self { entries })
+    }
+
+    /// Checks `account` is the stake history sysvar before viewing `data`,
+    /// its borrowed contents.
+    pub fn from_account(account: &AccountInfo, data: &'a [u8]) -> Result<Self, ProgramError> {
+        if account.key() != &STAKE_HISTORY_ID {
+            return Err(ProgramError::UnsupportedSysvar);
+        }
+        Self::new(data)
+    }
+
+    fn entry_epoch(&self, index: usize) -> u64 {
+        let offset = index * Self::ENTRY_LEN;
+        u64::from_le_bytes(self.entries[offset..offset + 8].try_into().unwrap())
+    }
+
+    pub fn get(&self, epoch: u64) -> Option<StakeHistoryEntry> {
+        let count = self.entries.len() / Self::ENTRY_LEN;
+
+        // Sorted by descending epoch
+        let (mut low, mut high) = (0, count);
+        while low < high {
+            let mid = (low + high) / 2;
+            match self.entry_epoch(mid).cmp(&epoch) {
+                core::cmp::Ordering::Equal => {
+                    let entry = &self.entries[mid * Self::ENTRY_LEN..(mid + 1) * Self::ENTRY_LEN];
+                    let field =
+                        |i: usize| u64::from_le_bytes(entry[i * 8..i * 8 + 8].try_into().unwrap());
+                    return Some(StakeHistoryEntry {
+                        effective: field(1),
+                        activating: field(2),
+                        deactivating: field(3),
+                    });
+                }
+                core::cmp::Ordering::Greater => low = mid + 1,
+                core::cmp::Ordering::Less => high = mid,
+            }
+        }
+
+        None
+    }
+}
+
+/// Checks that `staker` and `withdrawer` alone control a stake account: no
+/// lockup may be in force and no custodian may be able to lift one.
+pub fn check_stake_meta(
+    meta: &Meta,
+    staker: &Pubkey,
+    withdrawer: &Pubkey,
+    clock: &Clock,
+) -> ProgramResult {
+    if &meta.authorized.staker != staker || &meta.authorized.withdrawer != withdrawer {
+        return Err(PinocchioError::StakeAuthorityMismatch.into());
+    }
+
+    if meta.lockup.is_in_force(clock.unix_timestamp, clock.epoch)
+        || meta.lockup.custodian != Pubkey::default()
+    {
+        return Err(PinocchioError::StakeLockupInForce.into());
+    }
+
+    Ok(())
+}
+
+/// Checks a stake account the pool holds, such as the pool or a transient
+/// stake: the pool PDA is both authorities and it is delegated to
+/// `validator_vote`.
+pub fn check_pool_stake(
+    stake_account: &AccountInfo,
+    pool_state: &Pubkey,
+    validator_vote: &Pubkey,
+    clock: &Clock,
+) -> ProgramResult {
+    let stake_data = stake_account.try_borrow_data()?;
+    let stake_state = StakeStateV2::load(&stake_data)?;
+    let (Some(meta), Some(delegation)) = (stake_state.meta(), stake_state.delegation()) else {
+        return Err(PinocchioError::InvalidStakeState.into());
+    };
+
+    check_stake_meta(meta, pool_state, pool_state, clock)?;
+
+    if &delegation.voter_pubkey != validator_vote {
+        return Err(PinocchioError::StakeValidatorMismatch.into());
+    }
+
+    Ok(())
+}
+
+/// What a delegated stake account of the pool counts for in its value; see
+/// `StakeStateV2::delegated_lamports`.
+pub fn stake_value(stake_account: &AccountInfo) -> Result<u64, ProgramError> {
+    let stake_data = stake_account.try_borrow_data()?;
+    StakeStateV2::load(&stake_data)?
+        .delegated_lamports()
+        .ok_or(PinocchioError::InvalidStakeState.into())
+}
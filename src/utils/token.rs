@@ -0,0 +1,201 @@
+use pinocchio::{
+    ProgramResult,
+    account_info::AccountInfo,
+    instruction::{AccountMeta, Instruction, Seed, Signer},
+    pubkey::Pubkey,
+};
+
+use crate::LST_DECIMALS;
+
+// Token instructions shared by SPL Token and Token-2022. `pinocchio_token`
+// always targets SPL Token, so these take the program to invoke.
+
+const INITIALIZE_MINT_2: u8 = 20;
+const MINT_TO: u8 = 7;
+const BURN: u8 = 8;
+const TRANSFER_CHECKED: u8 = 12;
+const METADATA_POINTER_EXTENSION: u8 = 39;
+
+/// Token-2022 account type byte, stored right after the base account
+pub const ACCOUNT_TYPE_MINT: u8 = 1;
+pub const ACCOUNT_TYPE_ACCOUNT: u8 = 2;
+
+/// Token-2022 mint carrying only a `MetadataPointer` extension: the base
+/// mint padded to the token account length, the account type, then one TLV
+/// entry (type, length, authority, metadata address).
+pub const MINT_WITH_METADATA_POINTER_LEN: usize = 165 + 1 + 4 + 64;
+
+/// Initialize a mint with no freeze authority
+pub fn initialize_mint(
+    mint: &AccountInfo,
+    mint_authority: &Pubkey,
+    token_program: &AccountInfo,
+) -> ProgramResult {
+    let mut data = [0u8; 35];
+    data[0] = INITIALIZE_MINT_2;
+    data[1] = LST_DECIMALS;
+    data[2..34].copy_from_slice(mint_authority.as_ref());
+    // freeze_authority = None
+
+    let ix = Instruction {
+        program_id: token_program.key(),
+        accounts: &[AccountMeta {
+            pubkey: mint.key(),
+            is_signer: false,
+            is_writable: true,
+        }],
+        data: &data,
+    };
+
+    pinocchio::program::invoke(&ix, &[mint])
+}
+
+/// Point a Token-2022 mint at its metadata account. Must run before
+/// `initialize_mint`.
+pub fn initialize_metadata_pointer(
+    mint: &AccountInfo,
+    authority: &Pubkey,
+    metadata_address: &Pubkey,
+    token_program: &AccountInfo,
+) -> ProgramResult {
+    let mut data = [0u8; 66];
+    data[0] = METADATA_POINTER_EXTENSION;
+    data[1] = 0; // Initialize
+    data[2..34].copy_from_slice(authority.as_ref());
+    data[34..66].copy_from_slice(metadata_address.as_ref());
+
+    let ix = Instruction {
+        program_id: token_program.key(),
+        accounts: &[AccountMeta {
+            pubkey: mint.key(),
+            is_signer: false,
+            is_writable: true,
+        }],
+        data: &data,
+    };
+
+    pinocchio::program::invoke(&ix, &[mint])
+}
+
+/// Mint `amount` LST, signed by the pool PDA as mint authority
+pub fn mint_to<'a>(
+    mint: &'a AccountInfo,
+    account: &'a AccountInfo,
+    mint_authority: &'a AccountInfo,
+    token_program: &'a AccountInfo,
+    amount: u64,
+    signer_seeds: &[Seed],
+) -> ProgramResult {
+    let signer = [Signer::from(signer_seeds)];
+
+    let mut data = [0u8; 9];
+    data[0] = MINT_TO;
+    data[1..9].copy_from_slice(&amount.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: token_program.key(),
+        accounts: &[
+            AccountMeta {
+                pubkey: mint.key(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: account.key(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: mint_authority.key(),
+                is_signer: true,
+                is_writable: false,
+            },
+        ],
+        data: &data,
+    };
+
+    pinocchio::program::invoke_signed(&ix, &[mint, account, mint_authority], &signer)
+}
+
+/// Burn `amount` from `account`; `authority` signs the outer transaction
+pub fn burn<'a>(
+    account: &'a AccountInfo,
+    mint: &'a AccountInfo,
+    authority: &'a AccountInfo,
+    token_program: &'a AccountInfo,
+    amount: u64,
+) -> ProgramResult {
+    let mut data = [0u8; 9];
+    data[0] = BURN;
+    data[1..9].copy_from_slice(&amount.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: token_program.key(),
+        accounts: &[
+            AccountMeta {
+                pubkey: account.key(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: mint.key(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: authority.key(),
+                is_signer: true,
+                is_writable: false,
+            },
+        ],
+        data: &data,
+    };
+
+    pinocchio::program::invoke(&ix, &[account, mint, authority])
+}
+
+/// Transfer LST between token accounts; `authority` signs the outer
+/// transaction. Checked, since Token-2022 rejects the unchecked transfer for
+/// some extensions.
+pub fn transfer_checked<'a>(
+    from: &'a AccountInfo,
+    mint: &'a AccountInfo,
+    to: &'a AccountInfo,
+    authority: &'a AccountInfo,
+    token_program: &'a AccountInfo,
+    amount: u64,
+) -> ProgramResult {
+    let mut data = [0u8; 10];
+    data[0] = TRANSFER_CHECKED;
+    data[1..9].copy_from_slice(&amount.to_le_bytes());
+    data[9] = LST_DECIMALS;
+
+    let ix = Instruction {
+        program_id: token_program.key(),
+        accounts: &[
+            AccountMeta {
+                pubkey: from.key(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: mint.key(),
+                is_signer: false,
+                is_writable: false,
+            },
+            AccountMeta {
+                pubkey: to.key(),
+                is_signer: false,
+                is_writable: true,
+            },
+            AccountMeta {
+                pubkey: authority.key(),
+                is_signer: true,
+                is_writable: false,
+            },
+        ],
+        data: &data,
+    };
+
+    pinocchio::program::invoke(&ix, &[from, mint, to, authority])
+}
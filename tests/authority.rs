@@ -0,0 +1,227 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use liquid_staking_token::{
+        PinocchioError,
+        client::{self, PoolFees},
+    };
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    /// Creates a pool whose manager and staker are the returned keypair
+    fn initialize_pool(svm: &mut LiteSVM) -> (Keypair, Pubkey) {
+        let initializer = Keypair::new();
+        let lst_mint = Keypair::new();
+        svm.airdrop(&initializer.pubkey(), 10_000_000_000).unwrap();
+        let validator_vote = create_vote_account(svm, &Keypair::new().pubkey());
+
+        let seed = 77u64;
+        let ix = client::initialize(
+            &initializer.pubkey(),
+            &lst_mint.pubkey(),
+            &validator_vote,
+            seed,
+            PoolFees::default(),
+        );
+        send(svm, &initializer, ix, &[&lst_mint]).expect("Initialize should succeed");
+
+        (initializer, client::find_pool_state_address(seed).0)
+    }
+
+    fn funded_keypair(svm: &mut LiteSVM) -> Keypair {
+        let keypair = Keypair::new();
+        svm.airdrop(&keypair.pubkey(), 1_000_000_000).unwrap();
+        keypair
+    }
+
+    #[test]
+    fn test_initializer_holds_both_roles() {
+        let mut svm = setup_svm();
+        let (initializer, pool_address) = initialize_pool(&mut svm);
+
+        let pool = load_pool(&svm, &pool_address);
+        assert_eq!(pool.manager, initializer.pubkey());
+        assert_eq!(pool.staker, initializer.pubkey());
+        assert_eq!(pool.pending_manager, None);
+    }
+
+    #[test]
+    fn test_manager_transfer_takes_two_steps() {
+        let mut svm = setup_svm();
+        let (manager, pool_address) = initialize_pool(&mut svm);
+        let new_manager = funded_keypair(&mut svm);
+
+        let pool = load_pool(&svm, &pool_address);
+        send(
+            &mut svm,
+            &manager,
+            client::set_manager(&pool, &new_manager.pubkey()),
+            &[],
+        )
+        .expect("SetManager should succeed");
+
+        // Proposing does not hand over the role yet
+        let pool = load_pool(&svm, &pool_address);
+        assert_eq!(pool.manager, manager.pubkey());
+        assert_eq!(pool.pending_manager, Some(new_manager.pubkey()));
+
+        send(
+            &mut svm,
+            &new_manager,
+            client::accept_manager(&pool, &new_manager.pubkey()),
+            &[],
+        )
+        .expect("AcceptManager should succeed");
+
+        let pool = load_pool(&svm, &pool_address);
+        assert_eq!(pool.manager, new_manager.pubkey());
+        assert_eq!(pool.pending_manager, None);
+        assert_eq!(
+            pool.staker,
+            manager.pubkey(),
+            "The staker role is not transferred with the manager"
+        );
+
+        // The old manager lost its rights
+        let result = send(
+            &mut svm,
+            &manager,
+            client::set_manager(&pool, &manager.pubkey()),
+            &[],
+        );
+        assert_eq!(result, Err(custom_error(PinocchioError::InvalidAuthority)));
+    }
+
+    #[test]
+    fn test_accept_manager_requires_the_proposed_key() {
+        let mut svm = setup_svm();
+        let (manager, pool_address) = initialize_pool(&mut svm);
+        let new_manager = funded_keypair(&mut svm);
+        let attacker = funded_keypair(&mut svm);
+
+        let pool = load_pool(&svm, &pool_address);
+        let result = send(
+            &mut svm,
+            &new_manager,
+            client::accept_manager(&pool, &new_manager.pubkey()),
+            &[],
+        );
+        assert_eq!(result, Err(custom_error(PinocchioError::NoPendingManager)));
+
+        send(
+            &mut svm,
+            &manager,
+            client::set_manager(&pool, &new_manager.pubkey()),
+            &[],
+        )
+        .expect("SetManager should succeed");
+
+        let result = send(
+            &mut svm,
+            &attacker,
+            client::accept_manager(&pool, &attacker.pubkey()),
+            &[],
+        );
+        assert_eq!(
+            result,
+            Err(custom_error(PinocchioError::InvalidPendingManager))
+        );
+
+        // Proposing the current manager cancels the transfer
+        send(
+            &mut svm,
+            &manager,
+            client::set_manager(&pool, &manager.pubkey()),
+            &[],
+        )
+        .expect("Cancelling should succeed");
+        assert_eq!(load_pool(&svm, &pool_address).pending_manager, None);
+    }
+
+    #[test]
+    fn test_only_manager_proposes_manager() {
+        let mut svm = setup_svm();
+        let (_, pool_address) = initialize_pool(&mut svm);
+        let attacker = funded_keypair(&mut svm);
+
+        let mut pool = load_pool(&svm, &pool_address);
+        pool.manager = attacker.pubkey();
+        let result = send(
+            &mut svm,
+            &attacker,
+            client::set_manager(&pool, &attacker.pubkey()),
+            &[],
+        );
+        assert_eq!(result, Err(custom_error(PinocchioError::InvalidAuthority)));
+    }
+
+    #[test]
+    fn test_set_staker() {
+        let mut svm = setup_svm();
+        let (manager, pool_address) = initialize_pool(&mut svm);
+        let staker = funded_keypair(&mut svm);
+        let next_staker = Keypair::new().pubkey();
+        let attacker = funded_keypair(&mut svm);
+
+        let pool = load_pool(&svm, &pool_address);
+        send(
+            &mut svm,
+            &manager,
+            client::set_staker(&pool, &manager.pubkey(), &staker.pubkey()),
+            &[],
+        )
+        .expect("Manager should set the staker");
+        assert_eq!(load_pool(&svm, &pool_address).staker, staker.pubkey());
+
+        // The staker can hand its role on
+        send(
+            &mut svm,
+            &staker,
+            client::set_staker(&pool, &staker.pubkey(), &next_staker),
+            &[],
+        )
+        .expect("Staker should set the staker");
+        assert_eq!(load_pool(&svm, &pool_address).staker, next_staker);
+
+        let result = send(
+            &mut svm,
+            &attacker,
+            client::set_staker(&pool, &attacker.pubkey(), &attacker.pubkey()),
+            &[],
+        );
+        assert_eq!(result, Err(custom_error(PinocchioError::InvalidAuthority)));
+    }
+
+    #[test]
+    fn test_manager_cannot_redelegate_after_staker_change() {
+        let mut svm = setup_svm();
+        let (manager, pool_address) = initialize_pool(&mut svm);
+        let staker = Keypair::new().pubkey();
+        let new_vote = create_vote_account(&mut svm, &Keypair::new().pubkey());
+
+        let pool = load_pool(&svm, &pool_address);
+        send(
+            &mut svm,
+            &manager,
+            client::set_staker(&pool, &manager.pubkey(), &staker),
+            &[],
+        )
+        .expect("SetStaker should succeed");
+
+        // Delegation decisions belong to the staker alone
+        let mut pool = load_pool(&svm, &pool_address);
+        pool.staker = manager.pubkey();
+        let result = send(
+            &mut svm,
+            &manager,
+            client::redelegate(&pool, &new_vote),
+            &[],
+        );
+        assert_eq!(result, Err(custom_error(PinocchioError::InvalidAuthority)));
+    }
+}
@@ -0,0 +1,614 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use liquid_staking_token::{
+        DepositInstructionData, IncreaseValidatorStakeInstructionData, InitializeData,
+        PAUSE_CRANKS, PinocchioError, PoolState, RateHistory, SetFeesInstructionData,
+        WithdrawInstructionData,
+        client::{self, AverageRate, Crank, KeeperView, Pool, PoolFees, TransientStake},
+    };
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    fn sample_pool() -> Pool {
+        let (address, _) = client::find_pool_state_address(7);
+        Pool {
+            address,
+            lst_mint: Keypair::new().pubkey(),
+            token_program: client::TOKEN_PROGRAM_ID,
+            manager: Keypair::new().pubkey(),
+            staker: Keypair::new().pubkey(),
+            validator_vote: Keypair::new().pubkey(),
+            pool_stake: client::find_pool_stake_address(&address).0,
+            reserve_stake: client::find_reserve_stake_address(&address).0,
+            manager_fee_account: Keypair::new().pubkey(),
+            validator_list: None,
+            pending_validator_vote: None,
+            pending_manager: None,
+            deposit_authority: None,
+            rate_history: None,
+        }
+    }
+
+    #[test]
+    fn test_instruction_data_matches_program_parsers() {
+        let fees = PoolFees {
+            deposit_fee_bps: 10,
+            withdrawal_fee_bps: 20,
+            reward_fee_bps: 30,
+            sol_withdrawal_fee_bps: 40,
+        };
+        let ix = client::initialize(
+            &Keypair::new().pubkey(),
+            &Keypair::new().pubkey(),
+            &Keypair::new().pubkey(),
+            7,
+            fees,
+        );
+        let data = InitializeData::try_from(&ix.data[1..]).expect("Initialize data should parse");
+        assert_eq!({ data.seed }, 7);
+        assert_eq!({ data.sol_withdrawal_fee_bps }, 40);
+        assert_eq!(ix.accounts[2].pubkey, sample_pool().address);
+
+        let pool = sample_pool();
+        let user = Keypair::new().pubkey();
+
+        let ix = client::set_fees(&pool, fees, &user);
+        let data =
+            SetFeesInstructionData::try_from(&ix.data[1..]).expect("SetFees data should parse");
+        assert_eq!({ data.reward_fee_bps }, 30);
+        assert_eq!({ data.sol_withdrawal_fee_bps }, 40);
+
+        let ix = client::deposit(&pool, &user, 5_000_000_000, 4_900_000_000);
+        let data =
+            DepositInstructionData::try_from(&ix.data[1..]).expect("Deposit data should parse");
+        assert_eq!({ data.amount }, 5_000_000_000);
+        assert_eq!({ data.minimum_lst_out }, 4_900_000_000);
+
+        let ix = client::increase_validator_stake(&pool, &user, 3_000_000_000, 12);
+        let data = IncreaseValidatorStakeInstructionData::try_from(&ix.data[1..])
+            .expect("IncreaseValidatorStake data should parse");
+        assert_eq!({ data.amount }, 3_000_000_000);
+        assert_eq!(
+            ix.accounts[4].pubkey,
+            client::find_transient_stake_address(&pool.address, &user, 12).0
+        );
+
+        let ix = client::withdraw(&pool, &user, 2_000_000_000, 3, 0);
+        let data =
+            WithdrawInstructionData::try_from(&ix.data[1..]).expect("Withdraw data should parse");
+        assert_eq!({ data.nonce }, 3);
+        assert_eq!(
+            ix.accounts[4].pubkey,
+            client::find_withdraw_stake_address(&pool.address, &user, 3).0
+        );
+    }
+
+    #[test]
+    fn test_optional_accounts_follow_pool_state() {
+        let mut pool = sample_pool();
+        let stakes = [Keypair::new().pubkey()];
+
+        assert_eq!(
+            client::update_pool_balance(&pool, &stakes).accounts.len(),
+            6
+        );
+        assert!(client::complete_redelegate(&pool).is_none());
+
+        pool.validator_list = Some(client::find_validator_list_address(&pool.address).0);
+        pool.pending_validator_vote = Some(Keypair::new().pubkey());
+
+        let ix = client::update_pool_balance(&pool, &stakes);
+        assert_eq!(ix.accounts.len(), 8);
+        assert_eq!(ix.accounts[6].pubkey, pool.validator_list.unwrap());
+        assert_eq!(ix.accounts[7].pubkey, stakes[0]);
+
+        pool.rate_history = Some(client::find_rate_history_address(&pool.address).0);
+        let ix = client::update_pool_balance(&pool, &stakes);
+        assert_eq!(ix.accounts.len(), 9);
+        assert_eq!(ix.accounts[8].pubkey, pool.rate_history.unwrap());
+
+        let ix = client::complete_redelegate(&pool).expect("Redelegation is pending");
+        assert_eq!(ix.accounts[3].pubkey, pool.pending_validator_vote.unwrap());
+        assert_eq!(
+            ix.accounts.last().unwrap().pubkey,
+            pool.validator_list.unwrap()
+        );
+    }
+
+    #[test]
+    fn test_token_2022_pool_accounts() {
+        let pool = Pool {
+            token_program: client::TOKEN_2022_PROGRAM_ID,
+            ..sample_pool()
+        };
+        let user = Keypair::new().pubkey();
+        let user_lst = client::get_associated_token_address_with_program_id(
+            &user,
+            &pool.lst_mint,
+            &client::TOKEN_2022_PROGRAM_ID,
+        );
+        assert_ne!(
+            user_lst,
+            client::get_associated_token_address(&user, &pool.lst_mint)
+        );
+
+        for ix in [
+            client::deposit(&pool, &user, 1, 0),
+            client::withdraw(&pool, &user, 1, 0, 0),
+            client::withdraw_sol(&pool, &user, 1, 0),
+        ] {
+            assert!(ix.accounts.iter().any(|meta| meta.pubkey == user_lst));
+            assert!(
+                ix.accounts
+                    .iter()
+                    .any(|meta| meta.pubkey == client::TOKEN_2022_PROGRAM_ID)
+            );
+            assert!(
+                !ix.accounts
+                    .iter()
+                    .any(|meta| meta.pubkey == client::TOKEN_PROGRAM_ID)
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_pool_state() {
+        let pool = sample_pool();
+
+        let mut data = vec![0u8; PoolState::LEN];
+        assert!(
+            client::decode_pool_state(&data).is_err(),
+            "Zeroed pool is uninitialized"
+        );
+
+        data[0] = 1;
+        data[1..33].copy_from_slice(pool.lst_mint.as_ref());
+        data[97..129].copy_from_slice(pool.pool_stake.as_ref());
+        data[192..224].copy_from_slice(pool.manager_fee_account.as_ref());
+        data[232..240].copy_from_slice(&42u64.to_le_bytes());
+
+        // Misaligned on purpose: decoding must not depend on buffer alignment
+        let mut shifted = vec![0u8];
+        shifted.extend_from_slice(&data);
+
+        let pool_state = client::decode_pool_state(&shifted[1..]).expect("Pool should decode");
+        assert_eq!({ pool_state.total_lamports }, 42);
+
+        let decoded = Pool::new(pool.address, &pool_state);
+        assert_eq!(decoded.lst_mint, pool.lst_mint);
+        assert_eq!(decoded.pool_stake, pool.pool_stake);
+        assert_eq!(decoded.manager_fee_account, pool.manager_fee_account);
+        assert_eq!(decoded.validator_list, None);
+        // Version 1 pools leave the token program zeroed and use SPL Token
+        assert_eq!(decoded.token_program, client::TOKEN_PROGRAM_ID);
+
+        data[408..440].copy_from_slice(client::TOKEN_2022_PROGRAM_ID.as_ref());
+        let pool_state = client::decode_pool_state(&data).expect("Pool should decode");
+        assert_eq!(
+            Pool::new(pool.address, &pool_state).token_program,
+            client::TOKEN_2022_PROGRAM_ID
+        );
+
+        assert!(
+            client::decode_pool_state(&data[1..]).is_err(),
+            "Short data should fail"
+        );
+
+        // Unversioned layouts must be migrated first, newer versions are unknown
+        assert_eq!(
+            client::decode_pool_state(&data[..328]).err(),
+            Some(PinocchioError::PoolMigrationRequired.into())
+        );
+        data[161] = PoolState::CURRENT_VERSION + 1;
+        assert_eq!(
+            client::decode_pool_state(&data).err(),
+            Some(PinocchioError::UnsupportedPoolVersion.into())
+        );
+    }
+
+    #[test]
+    fn test_decode_rate_history() {
+        // Full history whose oldest entry, at the head, is about to be overwritten
+        let head = 3usize;
+        let mut data = vec![0u8; RateHistory::LEN];
+        data[0] = RateHistory::DISCRIMINATOR;
+        data[4..8].copy_from_slice(&(RateHistory::CAPACITY as u32).to_le_bytes());
+        data[8..12].copy_from_slice(&(head as u32).to_le_bytes());
+        for slot in 0..RateHistory::CAPACITY {
+            let epoch = if slot < head {
+                RateHistory::CAPACITY + slot
+            } else {
+                slot
+            } as u64;
+            let offset = RateHistory::LEN - (RateHistory::CAPACITY - slot) * 24;
+            data[offset..offset + 8].copy_from_slice(&epoch.to_le_bytes());
+            data[offset + 8..offset + 16].copy_from_slice(&(2 * epoch).to_le_bytes());
+            data[offset + 16..offset + 24].copy_from_slice(&epoch.to_le_bytes());
+        }
+
+        let entries = client::decode_rate_history(&data).expect("History should decode");
+        let epochs: Vec<u64> = entries.iter().map(|entry| entry.epoch).collect();
+        let newest = (RateHistory::CAPACITY + head - 1) as u64;
+        assert_eq!(epochs.len(), RateHistory::CAPACITY);
+        assert_eq!(epochs[0], newest);
+        assert_eq!(*epochs.last().unwrap(), head as u64);
+        assert!(epochs.windows(2).all(|pair| pair[0] == pair[1] + 1));
+        assert_eq!(entries[0].rate().unwrap(), 2_000_000_000);
+
+        data[0] = 0;
+        assert_eq!(
+            client::decode_rate_history(&data).err(),
+            Some(PinocchioError::InvalidRateHistory.into())
+        );
+
+        let mut return_data = Vec::new();
+        for field in [1_100_000_000u64, 1_050_000_000, 10, 12] {
+            return_data.extend_from_slice(&field.to_le_bytes());
+        }
+        assert_eq!(
+            AverageRate::from_return_data(&return_data),
+            Some(AverageRate {
+                current_rate: 1_100_000_000,
+                average_rate: 1_050_000_000,
+                first_epoch: 10,
+                last_epoch: 12,
+            })
+        );
+        assert_eq!(AverageRate::from_return_data(&return_data[1..]), None);
+    }
+
+    #[test]
+    fn test_exchange_rates() {
+        use liquid_staking_token::{
+            EXCHANGE_RATE_SCALE, calculate_exchange_rate, calculate_inverse_exchange_rate,
+        };
+
+        assert_eq!(
+            calculate_exchange_rate(3_000, 2_000).unwrap(),
+            1_500_000_000
+        );
+        assert_eq!(
+            calculate_inverse_exchange_rate(3_000, 2_000).unwrap(),
+            666_666_666
+        );
+
+        // An empty pool mints 1:1
+        assert_eq!(
+            calculate_exchange_rate(5_000, 0).unwrap(),
+            EXCHANGE_RATE_SCALE
+        );
+        assert_eq!(
+            calculate_inverse_exchange_rate(5_000, 0).unwrap(),
+            EXCHANGE_RATE_SCALE
+        );
+
+        // LST outstanding against no lamports has no price for SOL
+        assert_eq!(calculate_exchange_rate(0, 2_000).unwrap(), 0);
+        assert_eq!(calculate_inverse_exchange_rate(0, 2_000).unwrap(), 0);
+    }
+
+    fn pool_state_data(last_update_epoch: u64, redelegating: bool) -> Vec<u8> {
+        let mut data = vec![0u8; PoolState::LEN];
+        data[0] = 1;
+        data[240..248].copy_from_slice(&last_update_epoch.to_le_bytes());
+        if redelegating {
+            data[296..328].copy_from_slice(Keypair::new().pubkey().as_ref());
+        }
+        data
+    }
+
+    fn stake_data(activation_epoch: u64, deactivation_epoch: u64) -> Vec<u8> {
+        let mut data = vec![0u8; 200];
+        data[0..4].copy_from_slice(&2u32.to_le_bytes());
+        data[164..172].copy_from_slice(&activation_epoch.to_le_bytes());
+        data[172..180].copy_from_slice(&deactivation_epoch.to_le_bytes());
+        data
+    }
+
+    const STAKE_RENT: u64 = 2_282_880;
+
+    #[test]
+    fn test_keeper_cycles_reserve() {
+        let funded = STAKE_RENT * 2 + 1_000_000_000;
+        let mut data = pool_state_data(10, false);
+        data[248..256].copy_from_slice(&funded.to_le_bytes());
+        let pool_state = client::decode_pool_state(&data).unwrap();
+        let pool_stake = stake_data(0, u64::MAX);
+
+        // Deposits below the delegation minimum stay in the reserve
+        let cranks = client::due_cranks(&KeeperView {
+            pool_state: &pool_state,
+            pool_stake_data: &pool_stake,
+            reserve_lamports: Some(funded - 1),
+            reserve_data: &[0u8; 200],
+            transient_stakes: &[],
+            stake_rent: STAKE_RENT,
+            epoch: 10,
+        });
+        assert!(cranks.is_empty());
+
+        // A funded reserve is activated, after the balance update
+        let cranks = client::due_cranks(&KeeperView {
+            pool_state: &pool_state,
+            pool_stake_data: &pool_stake,
+            reserve_lamports: Some(funded),
+            reserve_data: &[0u8; 200],
+            transient_stakes: &[],
+            stake_rent: STAKE_RENT,
+            epoch: 11,
+        });
+        assert_eq!(
+            cranks,
+            vec![
+                Crank::UpdatePoolBalance,
+                Crank::InitializeReserve { epoch: 11 }
+            ]
+        );
+
+        // Once per epoch; last epoch's transient merges while the reserve,
+        // refilled by new deposits, activates again
+        let transient = stake_data(11, u64::MAX);
+        let transient_stakes = [TransientStake {
+            validator_vote: Pubkey::default(),
+            epoch: 11,
+            data: &transient,
+        }];
+        let view = |epoch| KeeperView {
+            pool_state: &pool_state,
+            pool_stake_data: &pool_stake,
+            reserve_lamports: Some(funded),
+            reserve_data: &[0u8; 200],
+            transient_stakes: &transient_stakes,
+            stake_rent: STAKE_RENT,
+            epoch,
+        };
+        assert_eq!(
+            client::due_cranks(&view(11)),
+            vec![Crank::UpdatePoolBalance]
+        );
+        assert_eq!(
+            client::due_cranks(&view(12)),
+            vec![
+                Crank::UpdatePoolBalance,
+                Crank::MergeReserve {
+                    transient_epoch: 11,
+                    validator_vote: Pubkey::default(),
+                },
+                Crank::InitializeReserve { epoch: 12 },
+            ]
+        );
+
+        // Transient stakes of other validators do not hold up the reserve
+        let listed = stake_data(12, u64::MAX);
+        let transient_stakes = [TransientStake {
+            validator_vote: Keypair::new().pubkey(),
+            epoch: 12,
+            data: &listed,
+        }];
+        let cranks = client::due_cranks(&KeeperView {
+            transient_stakes: &transient_stakes,
+            ..view(12)
+        });
+        assert_eq!(
+            cranks,
+            vec![
+                Crank::UpdatePoolBalance,
+                Crank::InitializeReserve { epoch: 12 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keeper_merges_validator_transient() {
+        let mut data = pool_state_data(12, false);
+        let primary_vote = Keypair::new().pubkey();
+        data[65..97].copy_from_slice(primary_vote.as_ref());
+        let pool_state = client::decode_pool_state(&data).unwrap();
+        let pool = Pool::new(sample_pool().address, &pool_state);
+        let pool_stake = stake_data(0, u64::MAX);
+
+        // Activated by `IncreaseValidatorStake` towards a listed validator
+        let listed_vote = Keypair::new().pubkey();
+        let mut transient = stake_data(11, u64::MAX);
+        transient[124..156].copy_from_slice(listed_vote.as_ref());
+        let transient_stakes = [TransientStake {
+            validator_vote: listed_vote,
+            epoch: 11,
+            data: &transient,
+        }];
+
+        let cranks = client::due_cranks(&KeeperView {
+            pool_state: &pool_state,
+            pool_stake_data: &pool_stake,
+            reserve_lamports: Some(STAKE_RENT),
+            reserve_data: &[0u8; 200],
+            transient_stakes: &transient_stakes,
+            stake_rent: STAKE_RENT,
+            epoch: 12,
+        });
+        let merge = Crank::MergeReserve {
+            transient_epoch: 11,
+            validator_vote: listed_vote,
+        };
+        assert_eq!(cranks, vec![merge]);
+
+        // It merges into the validator's stake rather than the pool stake
+        let ix = client::crank_instruction(&pool, merge, &[]).unwrap();
+        assert_eq!(
+            ix.accounts[1].pubkey,
+            client::find_validator_stake_address(&pool.address, &listed_vote).0
+        );
+        assert_eq!(
+            ix.accounts.last().unwrap().pubkey,
+            client::find_validator_list_address(&pool.address).0
+        );
+
+        let primary_merge = Crank::MergeReserve {
+            transient_epoch: 11,
+            validator_vote: primary_vote,
+        };
+        let ix = client::crank_instruction(&pool, primary_merge, &[]).unwrap();
+        assert_eq!(ix.accounts[1].pubkey, pool.pool_stake);
+    }
+
+    #[test]
+    fn test_keeper_merges_legacy_reserve() {
+        let pool_state = client::decode_pool_state(&pool_state_data(12, false)).unwrap();
+        let pool = Pool::new(sample_pool().address, &pool_state);
+        let pool_stake = stake_data(0, u64::MAX);
+
+        // Delegated by the original InitializeReserve
+        let reserve = stake_data(11, u64::MAX);
+        let view = |epoch| KeeperView {
+            pool_state: &pool_state,
+            pool_stake_data: &pool_stake,
+            reserve_lamports: Some(STAKE_RENT * 2 + 1_000_000_000),
+            reserve_data: &reserve,
+            transient_stakes: &[],
+            stake_rent: STAKE_RENT,
+            epoch,
+        };
+        assert_eq!(
+            client::due_cranks(&view(11)),
+            vec![Crank::UpdatePoolBalance]
+        );
+        assert_eq!(
+            client::due_cranks(&view(12)),
+            vec![Crank::MergeLegacyReserve]
+        );
+
+        // The reserve is both the source and the account paid back
+        let ix = client::crank_instruction(&pool, Crank::MergeLegacyReserve, &[]).unwrap();
+        assert_eq!(ix.accounts[1].pubkey, pool.pool_stake);
+        assert_eq!(ix.accounts[2].pubkey, pool.reserve_stake);
+        assert_eq!(ix.accounts[3].pubkey, pool.reserve_stake);
+    }
+
+    #[test]
+    fn test_keeper_ignores_reserve_donations() {
+        let funded = STAKE_RENT * 2 + 1_000_000_000;
+        let mut data = pool_state_data(10, false);
+        data[248..256].copy_from_slice(&(funded - 1).to_le_bytes());
+        let pool_state = client::decode_pool_state(&data).unwrap();
+        let pool_stake = stake_data(0, u64::MAX);
+
+        // Only the SOL the pool tracks can be activated
+        let cranks = client::due_cranks(&KeeperView {
+            pool_state: &pool_state,
+            pool_stake_data: &pool_stake,
+            reserve_lamports: Some(funded * 2),
+            reserve_data: &[0u8; 200],
+            transient_stakes: &[],
+            stake_rent: STAKE_RENT,
+            epoch: 10,
+        });
+        assert!(cranks.is_empty());
+    }
+
+    #[test]
+    fn test_keeper_waits_for_redelegation_cooldown() {
+        let pool_state = client::decode_pool_state(&pool_state_data(12, true)).unwrap();
+        let pool_stake = stake_data(0, 12);
+
+        let view = |epoch| KeeperView {
+            pool_state: &pool_state,
+            pool_stake_data: &pool_stake,
+            reserve_lamports: Some(STAKE_RENT * 2 + 1_000_000_000),
+            reserve_data: &[0u8; 200],
+            transient_stakes: &[],
+            stake_rent: STAKE_RENT,
+            epoch,
+        };
+
+        // The reserve is left alone while the pool stake cools down
+        assert!(client::due_cranks(&view(12)).is_empty());
+        assert_eq!(
+            client::due_cranks(&view(13)),
+            vec![Crank::UpdatePoolBalance, Crank::CompleteRedelegate]
+        );
+    }
+
+    #[test]
+    fn test_keeper_idles_while_cranks_paused() {
+        let mut data = pool_state_data(10, false);
+        data[400] = PAUSE_CRANKS;
+        let pool_state = client::decode_pool_state(&data).unwrap();
+        let pool_stake = stake_data(0, u64::MAX);
+
+        let cranks = client::due_cranks(&KeeperView {
+            pool_state: &pool_state,
+            pool_stake_data: &pool_stake,
+            reserve_lamports: Some(STAKE_RENT * 2 + 1_000_000_000),
+            reserve_data: &[0u8; 200],
+            transient_stakes: &[],
+            stake_rent: STAKE_RENT,
+            epoch: 11,
+        });
+        assert!(cranks.is_empty());
+    }
+
+    #[test]
+    fn test_initialize_and_deposit_with_client() {
+        let mut svm = setup_svm();
+
+        let initializer = Keypair::new();
+        let lst_mint = Keypair::new();
+        svm.airdrop(&initializer.pubkey(), 10_000_000_000).unwrap();
+        let validator_vote = create_vote_account(&mut svm, &Keypair::new().pubkey());
+
+        let seed = 42u64;
+        let ix = client::initialize(
+            &initializer.pubkey(),
+            &lst_mint.pubkey(),
+            &validator_vote,
+            seed,
+            PoolFees::default(),
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&initializer.pubkey()),
+            &[&initializer, &lst_mint],
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx).expect("Initialize should succeed");
+
+        let (pool_address, _) = client::find_pool_state_address(seed);
+        let pool =
+            Pool::from_account_data(pool_address, &svm.get_account(&pool_address).unwrap().data)
+                .expect("Pool state should decode");
+        assert_eq!(pool.lst_mint, lst_mint.pubkey());
+        assert_eq!(pool.validator_vote, validator_vote);
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 5_000_000_000).unwrap();
+
+        let create_ata_ix =
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &depositor.pubkey(),
+                &depositor.pubkey(),
+                &pool.lst_mint,
+                &client::TOKEN_PROGRAM_ID,
+            );
+        let deposit_ix = client::deposit(&pool, &depositor.pubkey(), 2_000_000_000, 0);
+        let tx = Transaction::new_signed_with_payer(
+            &[create_ata_ix, deposit_ix],
+            Some(&depositor.pubkey()),
+            &[&depositor],
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx).expect("Deposit should succeed");
+
+        let pool_state = client::decode_pool_state(&svm.get_account(&pool_address).unwrap().data)
+            .expect("Pool state should decode");
+        assert!(
+            { pool_state.lst_supply } > 1_000_000_000,
+            "Deposit should mint LST on top of the initial supply"
+        );
+    }
+}
@@ -0,0 +1,473 @@
+//! Pool and stake fixtures shared by the integration tests
+#![allow(dead_code)]
+
+use liquid_staking_token::{
+    PinocchioError,
+    client::{self, Pool},
+};
+use litesvm::{
+    LiteSVM,
+    types::{FailedTransactionMetadata, TransactionMetadata},
+};
+use pinocchio::sysvars::{clock::CLOCK_ID, rent::RENT_ID};
+use solana_sdk::{
+    account::Account,
+    clock::Clock,
+    instruction::{AccountMeta, Instruction, InstructionError},
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::{ID as ATA_PROGRAM_ID, get_associated_token_address};
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    0x0f, 0x1e, 0x6b, 0x14, 0x21, 0xc0, 0x4a, 0x07, 0x04, 0x31, 0x26, 0x5c, 0x19, 0xc5, 0xbb, 0xee,
+    0x19, 0x92, 0xba, 0xe8, 0xaf, 0xd1, 0xcd, 0x07, 0x8e, 0xf8, 0xaf, 0x70, 0x47, 0xdc, 0x11, 0xf7,
+]);
+
+pub const STAKE_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    6, 161, 216, 23, 145, 55, 84, 42, 152, 52, 55, 189, 254, 42, 122, 178, 85, 127, 83, 92, 138,
+    120, 114, 43, 104, 164, 157, 192, 0, 0, 0, 0,
+]);
+
+pub const VOTE_PROGRAM_ID: Pubkey = Pubkey::new_from_array([
+    7, 97, 72, 29, 53, 116, 116, 187, 124, 77, 118, 36, 235, 211, 189, 179, 216, 53, 94, 115, 209,
+    16, 67, 252, 13, 163, 83, 128, 0, 0, 0, 0,
+]);
+
+pub const STAKE_HISTORY_SYSVAR: Pubkey = Pubkey::new_from_array([
+    6, 167, 213, 23, 25, 53, 132, 43, 117, 36, 142, 142, 69, 167, 74, 9, 0, 69, 35, 53, 181, 203,
+    213, 234, 92, 199, 0, 0, 0, 0, 0, 0,
+]);
+
+pub const STAKE_CONFIG: Pubkey = Pubkey::new_from_array([
+    6, 161, 216, 23, 165, 2, 5, 11, 104, 7, 145, 230, 206, 95, 249, 248, 36, 45, 178, 171, 63, 252,
+    207, 199, 82, 86, 83, 0, 0, 99, 1, 1,
+]);
+
+pub const SYSTEM_PROGRAM_ID: Pubkey = Pubkey::new_from_array([0; 32]);
+
+pub const CLOCK_SYSVAR: Pubkey = Pubkey::new_from_array(CLOCK_ID);
+
+pub const RENT_SYSVAR: Pubkey = Pubkey::new_from_array(RENT_ID);
+
+/// The failure is boxed so helpers returning it stay small
+pub type TransactionResult = Result<TransactionMetadata, Box<FailedTransactionMetadata>>;
+
+pub fn derive_pool_state_pda(seed: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"lst_pool", &seed.to_le_bytes()], &PROGRAM_ID)
+}
+
+pub fn derive_stake_account_pda(pool_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"stake", pool_state.as_ref()], &PROGRAM_ID)
+}
+
+pub fn derive_reserve_stake_account_pda(pool_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"reserve_stake", pool_state.as_ref()], &PROGRAM_ID)
+}
+
+pub fn derive_validator_list_pda(pool_state: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"validator_list", pool_state.as_ref()], &PROGRAM_ID)
+}
+
+pub fn derive_validator_stake_pda(pool_state: &Pubkey, validator_vote: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"validator_stake",
+            pool_state.as_ref(),
+            validator_vote.as_ref(),
+        ],
+        &PROGRAM_ID,
+    )
+}
+
+pub fn derive_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &ATA_PROGRAM_ID,
+    )
+    .0
+}
+
+pub fn create_initialize_instruction_data(
+    seed: u64,
+    deposit_fee_bps: u16,
+    withdrawal_fee_bps: u16,
+    reward_fee_bps: u16,
+    sol_withdrawal_fee_bps: u16,
+) -> Vec<u8> {
+    let mut data = vec![0u8]; // Discriminator for Initialize
+    data.extend_from_slice(&seed.to_le_bytes());
+    data.extend_from_slice(&deposit_fee_bps.to_le_bytes());
+    data.extend_from_slice(&withdrawal_fee_bps.to_le_bytes());
+    data.extend_from_slice(&reward_fee_bps.to_le_bytes());
+    data.extend_from_slice(&sol_withdrawal_fee_bps.to_le_bytes());
+    data
+}
+
+pub fn create_deposit_instruction_data(amount: u64) -> Vec<u8> {
+    let mut data = vec![1u8]; // Discriminator for Deposit
+    data.extend_from_slice(&amount.to_le_bytes());
+    data
+}
+
+pub fn setup_svm() -> LiteSVM {
+    let mut svm = LiteSVM::new().with_builtins().with_sigverify(false);
+
+    svm.add_program_from_file(PROGRAM_ID, "target/deploy/liquid_staking_token.so")
+        .expect("Failed to load program");
+
+    svm
+}
+
+pub fn create_vote_account(svm: &mut LiteSVM, validator_identity: &Pubkey) -> Pubkey {
+    let vote_pubkey = Keypair::new().pubkey();
+
+    let mut data = vec![0u8; 3762];
+    data[0..4].copy_from_slice(&1u32.to_le_bytes());
+    data[4..36].copy_from_slice(validator_identity.as_ref());
+    data[36..68].copy_from_slice(validator_identity.as_ref());
+
+    svm.set_account(
+        vote_pubkey,
+        Account {
+            lamports: 10_000_000_000,
+            data,
+            owner: VOTE_PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    )
+    .unwrap();
+
+    vote_pubkey
+}
+
+pub fn send_transaction(svm: &mut LiteSVM, transaction: Transaction) -> TransactionResult {
+    svm.send_transaction(transaction).map_err(Box::new)
+}
+
+/// Sends `ix` paid for and signed by `payer`, plus `extra_signers`
+pub fn send(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    ix: Instruction,
+    extra_signers: &[&Keypair],
+) -> Result<(), TransactionError> {
+    let mut signers = vec![payer];
+    signers.extend_from_slice(extra_signers);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &signers,
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).map(|_| ()).map_err(|e| e.err)
+}
+
+/// The error a failed first instruction reports for `error`
+pub fn custom_error(error: PinocchioError) -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(error as u32))
+}
+
+pub fn load_pool(svm: &LiteSVM, address: &Pubkey) -> Pool {
+    Pool::from_account_data(*address, &svm.get_account(address).unwrap().data)
+        .expect("Pool state should decode")
+}
+
+/// Funds a user and deposits `amount` lamports for LST
+pub fn deposit(svm: &mut LiteSVM, pool: &Pool, amount: u64) -> Keypair {
+    let user = Keypair::new();
+    svm.airdrop(&user.pubkey(), amount + 1_000_000_000).unwrap();
+
+    let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+        &user.pubkey(),
+        &user.pubkey(),
+        &pool.lst_mint,
+        &client::TOKEN_PROGRAM_ID,
+    );
+    send(svm, &user, create_ata_ix, &[]).expect("ATA creation should succeed");
+    send(
+        svm,
+        &user,
+        client::deposit(pool, &user.pubkey(), amount, 0),
+        &[],
+    )
+    .expect("Deposit should succeed");
+
+    user
+}
+
+pub fn print_transaction_logs(result: &TransactionResult) {
+    match result {
+        Ok(meta) => {
+            eprintln!("\n=== Transaction Succeeded ===");
+            for log in &meta.logs {
+                eprintln!("  {}", log);
+            }
+        }
+        Err(err) => {
+            eprintln!("\n=== Transaction Failed ===");
+            eprintln!("Error: {:?}", err.err);
+            for log in &err.meta.logs {
+                eprintln!("  {}", log);
+            }
+        }
+    }
+}
+
+/// Reads the manager fee account recorded in pool state
+pub fn get_manager_fee_account(svm: &LiteSVM, pool_state_pda: &Pubkey) -> Pubkey {
+    let data = svm.get_account(pool_state_pda).unwrap().data;
+    Pubkey::new_from_array(data[192..224].try_into().unwrap())
+}
+
+/// Helper to initialize a pool with the given fees and return all the PDAs
+pub fn initialize_pool_with_fees(
+    svm: &mut LiteSVM,
+    deposit_fee_bps: u16,
+    withdrawal_fee_bps: u16,
+    reward_fee_bps: u16,
+    sol_withdrawal_fee_bps: u16,
+) -> (Keypair, Pubkey, Pubkey, Pubkey, Pubkey, Pubkey, u64) {
+    let initializer = Keypair::new();
+    svm.airdrop(&initializer.pubkey(), 2_000_000_000).unwrap();
+
+    let validator_identity = Keypair::new();
+    let validator_vote = create_vote_account(svm, &validator_identity.pubkey());
+
+    let seed = 12345u64;
+
+    let (pool_state_pda, _) = derive_pool_state_pda(seed);
+    let lst_mint = Keypair::new();
+    let (stake_account_pda, _) = derive_stake_account_pda(&pool_state_pda);
+    let (reserve_stake_pda, _) = derive_reserve_stake_account_pda(&pool_state_pda);
+    let initializer_lst_ata =
+        get_associated_token_address(&initializer.pubkey(), &lst_mint.pubkey());
+
+    let instruction_data = create_initialize_instruction_data(
+        seed,
+        deposit_fee_bps,
+        withdrawal_fee_bps,
+        reward_fee_bps,
+        sol_withdrawal_fee_bps,
+    );
+
+    let instruction = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(initializer.pubkey(), true), // initializer
+            AccountMeta::new(initializer_lst_ata, false), // initializer_lst_ata
+            AccountMeta::new(pool_state_pda, false),      // pool_state
+            AccountMeta::new(lst_mint.pubkey(), true),    // lst_mint
+            AccountMeta::new(stake_account_pda, false),   // stake_account
+            AccountMeta::new(reserve_stake_pda, false),   // reserve_stake
+            AccountMeta::new_readonly(validator_vote, false), // validator_vote
+            AccountMeta::new_readonly(CLOCK_SYSVAR, false), // clock
+            AccountMeta::new_readonly(RENT_SYSVAR, false), // rent
+            AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false), // stake_history
+            AccountMeta::new_readonly(STAKE_CONFIG, false), // stake_config
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false), // token_program
+            AccountMeta::new_readonly(STAKE_PROGRAM_ID, false), // stake_program
+            AccountMeta::new_readonly(ATA_PROGRAM_ID, false), // ata_program
+        ],
+        data: instruction_data,
+    };
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&initializer.pubkey()),
+        &[&initializer, &lst_mint],
+        svm.latest_blockhash(),
+    );
+
+    let result = send_transaction(svm, transaction);
+    print_transaction_logs(&result);
+    assert!(result.is_ok(), "Initialize should succeed");
+    let reserve_after = svm.get_account(&reserve_stake_pda).unwrap();
+    println!(
+        "Reserve stake lamports after init: {}",
+        reserve_after.lamports
+    );
+    println!("=== Pool Initialized Successfully ===");
+
+    (
+        initializer,
+        pool_state_pda,
+        lst_mint.pubkey(),
+        stake_account_pda,
+        reserve_stake_pda,
+        validator_vote,
+        seed,
+    )
+}
+
+/// Helper to get token account balance from account data
+pub fn get_token_balance(account_data: &[u8]) -> u64 {
+    u64::from_le_bytes(account_data[64..72].try_into().unwrap())
+}
+
+/// Helper to get mint total supply
+pub fn get_mint_supply(mint_data: &[u8]) -> u64 {
+    u64::from_le_bytes(mint_data[36..44].try_into().unwrap())
+}
+
+/// Helper to create depositor ATA and return the address
+pub fn create_depositor_ata(svm: &mut LiteSVM, depositor: &Keypair, lst_mint: &Pubkey) -> Pubkey {
+    let depositor_lst_ata = derive_ata(&depositor.pubkey(), lst_mint);
+
+    let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+        &depositor.pubkey(),
+        &depositor.pubkey(),
+        lst_mint,
+        &TOKEN_PROGRAM_ID,
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ata_ix],
+        Some(&depositor.pubkey()),
+        &[&depositor],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).expect("Should create ATA");
+
+    depositor_lst_ata
+}
+
+/// Helper to execute deposit into the pool's reserve
+pub fn execute_deposit(
+    svm: &mut LiteSVM,
+    depositor: &Keypair,
+    pool_state_pda: &Pubkey,
+    lst_mint_pda: &Pubkey,
+    depositor_lst_ata: &Pubkey,
+    amount: u64,
+) -> TransactionResult {
+    let (pool_stake_pda, _) = derive_stake_account_pda(pool_state_pda);
+    let (reserve_stake_pda, _) = derive_reserve_stake_account_pda(pool_state_pda);
+
+    let instruction = Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(depositor.pubkey(), true),
+            AccountMeta::new(*pool_state_pda, false),
+            AccountMeta::new_readonly(pool_stake_pda, false),
+            AccountMeta::new(reserve_stake_pda, false),
+            AccountMeta::new(*lst_mint_pda, false),
+            AccountMeta::new(*depositor_lst_ata, false),
+            AccountMeta::new(get_manager_fee_account(svm, pool_state_pda), false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+        ],
+        data: create_deposit_instruction_data(amount),
+    };
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&depositor.pubkey()),
+        &[&depositor],
+        svm.latest_blockhash(),
+    );
+
+    send_transaction(svm, transaction)
+}
+
+/// Moves the clock into the next epoch
+pub fn advance_epoch(svm: &mut LiteSVM) {
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.epoch += 1;
+    svm.set_sysvar(&clock);
+}
+
+/// Helper to execute the UpdatePoolBalance crank for a pool without a validator list
+pub fn execute_update_pool_balance(
+    svm: &mut LiteSVM,
+    pool_state_pda: &Pubkey,
+    pool_stake_pda: &Pubkey,
+    reserve_stake_pda: &Pubkey,
+    lst_mint_pda: &Pubkey,
+) -> TransactionResult {
+    execute_update_pool_balance_with_validators(
+        svm,
+        pool_state_pda,
+        pool_stake_pda,
+        reserve_stake_pda,
+        lst_mint_pda,
+        None,
+        &[],
+    )
+}
+
+/// Helper to execute the UpdatePoolBalance crank, passing the list and the
+/// given validator stake accounts when `validator_list` is set
+pub fn execute_update_pool_balance_with_validators(
+    svm: &mut LiteSVM,
+    pool_state_pda: &Pubkey,
+    pool_stake_pda: &Pubkey,
+    reserve_stake_pda: &Pubkey,
+    lst_mint_pda: &Pubkey,
+    validator_list: Option<&Pubkey>,
+    validator_stakes: &[Pubkey],
+) -> TransactionResult {
+    let crank = Keypair::new();
+    svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
+
+    let mut accounts = vec![
+        AccountMeta::new(*pool_state_pda, false),
+        AccountMeta::new_readonly(*pool_stake_pda, false),
+        AccountMeta::new_readonly(*reserve_stake_pda, false),
+        AccountMeta::new(*lst_mint_pda, false),
+        AccountMeta::new(get_manager_fee_account(svm, pool_state_pda), false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ];
+    if let Some(validator_list) = validator_list {
+        accounts.push(AccountMeta::new(*validator_list, false));
+    }
+    for stake in validator_stakes {
+        accounts.push(AccountMeta::new_readonly(*stake, false));
+    }
+
+    let instruction = Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: vec![6u8], // UpdatePoolBalance discriminator
+    };
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[instruction],
+        Some(&crank.pubkey()),
+        &[&crank],
+        svm.latest_blockhash(),
+    );
+
+    send_transaction(svm, transaction)
+}
+
+/// Simulates a donation by adding lamports to an account
+pub fn add_lamports(svm: &mut LiteSVM, address: &Pubkey, lamports: u64) {
+    let mut account = svm.get_account(address).unwrap();
+    account.lamports += lamports;
+    svm.set_account(*address, account).unwrap();
+}
+
+/// Simulates staking rewards, which raise both the balance and the delegation
+pub fn add_rewards(svm: &mut LiteSVM, address: &Pubkey, lamports: u64) {
+    let mut account = svm.get_account(address).unwrap();
+    account.lamports += lamports;
+    let stake = u64::from_le_bytes(account.data[156..164].try_into().unwrap());
+    account.data[156..164].copy_from_slice(&(stake + lamports).to_le_bytes());
+    svm.set_account(*address, account).unwrap();
+}
+
+/// Helper to read (total_lamports, last_update_epoch) from pool state
+pub fn get_cached_balance(svm: &LiteSVM, pool_state_pda: &Pubkey) -> (u64, u64) {
+    let data = svm.get_account(pool_state_pda).unwrap().data;
+    (
+        u64::from_le_bytes(data[232..240].try_into().unwrap()),
+        u64::from_le_bytes(data[240..248].try_into().unwrap()),
+    )
+}
@@ -0,0 +1,170 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use liquid_staking_token::{
+        PinocchioError,
+        client::{self, Pool, PoolFees},
+    };
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    };
+
+    /// Creates a pool managed by the returned keypair
+    fn initialize_pool(svm: &mut LiteSVM) -> (Keypair, Pool) {
+        let manager = Keypair::new();
+        let lst_mint = Keypair::new();
+        svm.airdrop(&manager.pubkey(), 10_000_000_000).unwrap();
+        let validator_vote = create_vote_account(svm, &Keypair::new().pubkey());
+
+        let seed = 19u64;
+        let ix = client::initialize(
+            &manager.pubkey(),
+            &lst_mint.pubkey(),
+            &validator_vote,
+            seed,
+            PoolFees::default(),
+        );
+        send(svm, &manager, ix, &[&lst_mint]).expect("Initialize should succeed");
+
+        let pool = load_pool(svm, &client::find_pool_state_address(seed).0);
+        (manager, pool)
+    }
+
+    /// Funds a user with an LST account, ready to deposit
+    fn create_user(svm: &mut LiteSVM, pool: &Pool) -> Keypair {
+        let user = Keypair::new();
+        svm.airdrop(&user.pubkey(), 10_000_000_000).unwrap();
+
+        let create_ata_ix =
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &user.pubkey(),
+                &user.pubkey(),
+                &pool.lst_mint,
+                &client::TOKEN_PROGRAM_ID,
+            );
+        send(svm, &user, create_ata_ix, &[]).expect("ATA creation should succeed");
+
+        user
+    }
+
+    fn deposit(
+        svm: &mut LiteSVM,
+        pool: &Pool,
+        user: &Keypair,
+        extra_signers: &[&Keypair],
+    ) -> Result<(), TransactionError> {
+        svm.expire_blockhash();
+        send(
+            svm,
+            user,
+            client::deposit(pool, &user.pubkey(), 2_000_000_000, 0),
+            extra_signers,
+        )
+    }
+
+    /// Makes the pool permissioned, returning the reloaded pool
+    fn set_deposit_authority(
+        svm: &mut LiteSVM,
+        manager: &Keypair,
+        pool: &Pool,
+        deposit_authority: Option<&Pubkey>,
+    ) -> Pool {
+        send(
+            svm,
+            manager,
+            client::set_deposit_authority(pool, deposit_authority),
+            &[],
+        )
+        .expect("Manager should set the deposit authority");
+
+        load_pool(svm, &pool.address)
+    }
+
+    #[test]
+    fn test_set_deposit_authority() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+        assert_eq!(pool.deposit_authority, None);
+
+        let deposit_authority = Keypair::new().pubkey();
+        let pool = set_deposit_authority(&mut svm, &manager, &pool, Some(&deposit_authority));
+        assert_eq!(pool.deposit_authority, Some(deposit_authority));
+
+        let pool = set_deposit_authority(&mut svm, &manager, &pool, None);
+        assert_eq!(pool.deposit_authority, None);
+    }
+
+    #[test]
+    fn test_set_deposit_authority_requires_manager() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+
+        let impostor = Keypair::new();
+        svm.airdrop(&impostor.pubkey(), 1_000_000_000).unwrap();
+        let ix = client::set_deposit_authority(
+            &Pool {
+                manager: impostor.pubkey(),
+                ..pool
+            },
+            Some(&impostor.pubkey()),
+        );
+
+        assert_eq!(
+            send(&mut svm, &impostor, ix, &[]),
+            Err(custom_error(PinocchioError::InvalidAuthority))
+        );
+    }
+
+    #[test]
+    fn test_permissioned_deposit() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+        let user = create_user(&mut svm, &pool);
+
+        let deposit_authority = Keypair::new();
+        let pool =
+            set_deposit_authority(&mut svm, &manager, &pool, Some(&deposit_authority.pubkey()));
+
+        // Without the authority account
+        let public_pool = Pool {
+            deposit_authority: None,
+            ..pool
+        };
+        assert_eq!(
+            deposit(&mut svm, &public_pool, &user, &[]),
+            Err(custom_error(PinocchioError::InvalidDepositAuthority))
+        );
+
+        // Signed by someone else
+        let impostor = Keypair::new();
+        let impostor_pool = Pool {
+            deposit_authority: Some(impostor.pubkey()),
+            ..pool
+        };
+        assert_eq!(
+            deposit(&mut svm, &impostor_pool, &user, &[&impostor]),
+            Err(custom_error(PinocchioError::InvalidDepositAuthority))
+        );
+
+        deposit(&mut svm, &pool, &user, &[&deposit_authority])
+            .expect("Deposit co-signed by the deposit authority");
+    }
+
+    #[test]
+    fn test_clearing_deposit_authority_reopens_pool() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+        let user = create_user(&mut svm, &pool);
+
+        let deposit_authority = Keypair::new().pubkey();
+        let pool = set_deposit_authority(&mut svm, &manager, &pool, Some(&deposit_authority));
+        let pool = set_deposit_authority(&mut svm, &manager, &pool, None);
+
+        deposit(&mut svm, &pool, &user, &[]).expect("Public pools take any deposit");
+    }
+}
@@ -0,0 +1,221 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use liquid_staking_token::{
+        PinocchioError,
+        client::{self, DepositLimits, Pool, PoolFees},
+    };
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    };
+
+    /// Creates a pool managed by the returned keypair
+    fn initialize_pool(svm: &mut LiteSVM) -> (Keypair, Pool) {
+        let manager = Keypair::new();
+        let lst_mint = Keypair::new();
+        svm.airdrop(&manager.pubkey(), 10_000_000_000).unwrap();
+        let validator_vote = create_vote_account(svm, &Keypair::new().pubkey());
+
+        let seed = 18u64;
+        let ix = client::initialize(
+            &manager.pubkey(),
+            &lst_mint.pubkey(),
+            &validator_vote,
+            seed,
+            PoolFees::default(),
+        );
+        send(svm, &manager, ix, &[&lst_mint]).expect("Initialize should succeed");
+
+        let pool = load_pool(svm, &client::find_pool_state_address(seed).0);
+        (manager, pool)
+    }
+
+    /// Funds a user with an LST account, ready to deposit
+    fn create_user(svm: &mut LiteSVM, pool: &Pool) -> Keypair {
+        let user = Keypair::new();
+        svm.airdrop(&user.pubkey(), 10_000_000_000).unwrap();
+
+        let create_ata_ix =
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &user.pubkey(),
+                &user.pubkey(),
+                &pool.lst_mint,
+                &client::TOKEN_PROGRAM_ID,
+            );
+        send(svm, &user, create_ata_ix, &[]).expect("ATA creation should succeed");
+
+        user
+    }
+
+    fn deposit(
+        svm: &mut LiteSVM,
+        pool: &Pool,
+        user: &Keypair,
+        amount: u64,
+    ) -> Result<(), TransactionError> {
+        svm.expire_blockhash();
+        send(
+            svm,
+            user,
+            client::deposit(pool, &user.pubkey(), amount, 0),
+            &[],
+        )
+    }
+
+    fn total_lamports(svm: &LiteSVM, pool: &Pool) -> u64 {
+        client::decode_pool_state(&svm.get_account(&pool.address).unwrap().data)
+            .expect("Pool state should decode")
+            .total_lamports()
+    }
+
+    #[test]
+    fn test_update_config_sets_limits() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+
+        let limits = DepositLimits {
+            max_total_lamports: 100_000_000_000,
+            max_deposit_lamports: 10_000_000_000,
+            min_deposit_lamports: 2_000_000_000,
+        };
+        send(
+            &mut svm,
+            &manager,
+            client::update_config(&pool, limits),
+            &[],
+        )
+        .expect("Manager should update the config");
+
+        let pool_state =
+            client::decode_pool_state(&svm.get_account(&pool.address).unwrap().data).unwrap();
+        assert_eq!(pool_state.max_total_lamports(), limits.max_total_lamports);
+        assert_eq!(
+            pool_state.max_deposit_lamports(),
+            limits.max_deposit_lamports
+        );
+        assert_eq!(
+            pool_state.min_deposit_lamports(),
+            limits.min_deposit_lamports
+        );
+    }
+
+    #[test]
+    fn test_update_config_requires_manager() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+
+        let impostor = Keypair::new();
+        svm.airdrop(&impostor.pubkey(), 1_000_000_000).unwrap();
+        let ix = client::update_config(
+            &Pool {
+                manager: impostor.pubkey(),
+                ..pool
+            },
+            DepositLimits {
+                max_total_lamports: 1,
+                ..DepositLimits::default()
+            },
+        );
+
+        assert_eq!(
+            send(&mut svm, &impostor, ix, &[]),
+            Err(custom_error(PinocchioError::InvalidAuthority))
+        );
+    }
+
+    #[test]
+    fn test_update_config_rejects_min_above_max() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+
+        let ix = client::update_config(
+            &pool,
+            DepositLimits {
+                max_total_lamports: 0,
+                max_deposit_lamports: 2_000_000_000,
+                min_deposit_lamports: 3_000_000_000,
+            },
+        );
+
+        assert_eq!(
+            send(&mut svm, &manager, ix, &[]),
+            Err(custom_error(PinocchioError::InvalidDepositLimits))
+        );
+    }
+
+    #[test]
+    fn test_per_deposit_limits() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+        let user = create_user(&mut svm, &pool);
+
+        send(
+            &mut svm,
+            &manager,
+            client::update_config(
+                &pool,
+                DepositLimits {
+                    max_total_lamports: 0,
+                    max_deposit_lamports: 3_000_000_000,
+                    min_deposit_lamports: 2_000_000_000,
+                },
+            ),
+            &[],
+        )
+        .expect("Manager should update the config");
+
+        assert_eq!(
+            deposit(&mut svm, &pool, &user, 4_000_000_000),
+            Err(custom_error(PinocchioError::DepositAboveMaximum))
+        );
+        assert_eq!(
+            deposit(&mut svm, &pool, &user, 1_500_000_000),
+            Err(custom_error(PinocchioError::BelowMinimumDeposit))
+        );
+        deposit(&mut svm, &pool, &user, 3_000_000_000).expect("Deposit within limits");
+
+        // Clearing the limits lets any deposit above the protocol minimum through
+        send(
+            &mut svm,
+            &manager,
+            client::update_config(&pool, DepositLimits::default()),
+            &[],
+        )
+        .expect("Manager should clear the limits");
+        deposit(&mut svm, &pool, &user, 4_000_000_000).expect("Limits are cleared");
+    }
+
+    #[test]
+    fn test_pool_cap() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+        let user = create_user(&mut svm, &pool);
+
+        let cap = total_lamports(&svm, &pool) + 3_000_000_000;
+        send(
+            &mut svm,
+            &manager,
+            client::update_config(
+                &pool,
+                DepositLimits {
+                    max_total_lamports: cap,
+                    ..DepositLimits::default()
+                },
+            ),
+            &[],
+        )
+        .expect("Manager should update the config");
+
+        deposit(&mut svm, &pool, &user, 2_000_000_000).expect("Deposit under the cap");
+        assert_eq!(
+            deposit(&mut svm, &pool, &user, 1_500_000_000),
+            Err(custom_error(PinocchioError::PoolCapExceeded))
+        );
+        deposit(&mut svm, &pool, &user, 1_000_000_000).expect("Deposit up to the cap");
+        assert_eq!(total_lamports(&svm, &pool), cap);
+    }
+}
@@ -0,0 +1,274 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        account::Account,
+        clock::Clock,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+    use spl_token::ID as TOKEN_PROGRAM_ID;
+
+    /// Writes a delegated stake account owned by `owner` straight into the SVM
+    fn create_delegated_stake_account(
+        svm: &mut LiteSVM,
+        owner: &Pubkey,
+        validator_vote: &Pubkey,
+        stake: u64,
+        deactivation_epoch: u64,
+    ) -> Pubkey {
+        let stake_pubkey = Keypair::new().pubkey();
+        let rent_exempt_reserve = svm.minimum_balance_for_rent_exemption(200);
+
+        let mut data = vec![0u8; 200];
+        data[0..4].copy_from_slice(&2u32.to_le_bytes()); // StakeStateV2::Stake
+        data[4..12].copy_from_slice(&rent_exempt_reserve.to_le_bytes());
+        data[12..44].copy_from_slice(owner.as_ref()); // staker
+        data[44..76].copy_from_slice(owner.as_ref()); // withdrawer
+        data[124..156].copy_from_slice(validator_vote.as_ref()); // voter
+        data[156..164].copy_from_slice(&stake.to_le_bytes());
+        data[164..172].copy_from_slice(&0u64.to_le_bytes()); // activation_epoch
+        data[172..180].copy_from_slice(&deactivation_epoch.to_le_bytes());
+        data[180..188].copy_from_slice(&0.25f64.to_le_bytes()); // warmup_cooldown_rate
+
+        svm.set_account(
+            stake_pubkey,
+            Account {
+                lamports: rent_exempt_reserve + stake,
+                data,
+                owner: STAKE_PROGRAM_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+        stake_pubkey
+    }
+
+    /// Helper to execute DepositStake
+    fn execute_deposit_stake(
+        svm: &mut LiteSVM,
+        depositor: &Keypair,
+        pool_state_pda: &Pubkey,
+        pool_stake_pda: &Pubkey,
+        deposit_stake: &Pubkey,
+        lst_mint_pda: &Pubkey,
+        depositor_lst_ata: &Pubkey,
+    ) -> TransactionResult {
+        let (reserve_stake_pda, _) = derive_reserve_stake_account_pda(pool_state_pda);
+        let instruction = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(depositor.pubkey(), true),
+                AccountMeta::new(*pool_state_pda, false),
+                AccountMeta::new(*pool_stake_pda, false),
+                AccountMeta::new(reserve_stake_pda, false),
+                AccountMeta::new(*deposit_stake, false),
+                AccountMeta::new(*lst_mint_pda, false),
+                AccountMeta::new(*depositor_lst_ata, false),
+                AccountMeta::new(get_manager_fee_account(svm, pool_state_pda), false),
+                AccountMeta::new_readonly(CLOCK_SYSVAR, false),
+                AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+                AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+            ],
+            data: vec![11u8], // DepositStake discriminator
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&depositor.pubkey()),
+            &[depositor],
+            svm.latest_blockhash(),
+        );
+
+        send_transaction(svm, transaction)
+    }
+
+    /// Initializes a pool and moves it into a fresh epoch so existing stake is active
+    fn setup_pool_for_deposit_stake(
+        svm: &mut LiteSVM,
+    ) -> (Pubkey, Pubkey, Pubkey, Pubkey, Pubkey) {
+        let (_, pool_state_pda, lst_mint_pda, pool_stake_pda, reserve_stake_pda, validator_vote, _) =
+            initialize_pool_with_fees(svm, 0, 0, 0, 0);
+
+        advance_epoch(svm);
+        execute_update_pool_balance(
+            svm,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+        )
+        .expect("UpdatePoolBalance should succeed");
+
+        (
+            pool_state_pda,
+            lst_mint_pda,
+            pool_stake_pda,
+            reserve_stake_pda,
+            validator_vote,
+        )
+    }
+
+    #[test]
+    fn test_deposit_stake_success() {
+        let mut svm = setup_svm();
+        let (pool_state_pda, lst_mint_pda, pool_stake_pda, reserve_stake_pda, validator_vote) =
+            setup_pool_for_deposit_stake(&mut svm);
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 1_000_000_000).unwrap();
+        let depositor_lst_ata = create_depositor_ata(&mut svm, &depositor, &lst_mint_pda);
+
+        let stake_amount = 2_000_000_000u64;
+        let deposit_stake = create_delegated_stake_account(
+            &mut svm,
+            &depositor.pubkey(),
+            &validator_vote,
+            stake_amount,
+            u64::MAX,
+        );
+        let deposit_lamports = svm.get_account(&deposit_stake).unwrap().lamports;
+        let pool_stake_before = svm.get_account(&pool_stake_pda).unwrap().lamports;
+        let reserve_before = svm.get_account(&reserve_stake_pda).unwrap().lamports;
+
+        let result = execute_deposit_stake(
+            &mut svm,
+            &depositor,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &deposit_stake,
+            &lst_mint_pda,
+            &depositor_lst_ata,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "DepositStake should succeed");
+
+        let pool_stake_after = svm.get_account(&pool_stake_pda).unwrap().lamports;
+        assert_eq!(
+            pool_stake_after,
+            pool_stake_before + stake_amount,
+            "Deposited delegation should be merged into pool stake"
+        );
+
+        // The deposit's rent reserve is moved on to the reserve
+        let reserve_after = svm.get_account(&reserve_stake_pda).unwrap().lamports;
+        assert_eq!(reserve_after, reserve_before + deposit_lamports - stake_amount);
+
+        let lst_balance =
+            get_token_balance(&svm.get_account(&depositor_lst_ata).unwrap().data);
+        assert!(lst_balance > 0, "Depositor should receive LST");
+        assert!(
+            lst_balance <= stake_amount,
+            "LST is minted for the active stake only"
+        );
+
+        println!("\n=== Test Passed: DepositStake Success ===");
+    }
+
+    #[test]
+    fn test_deposit_stake_wrong_validator_fails() {
+        let mut svm = setup_svm();
+        let (pool_state_pda, lst_mint_pda, pool_stake_pda, _, _) =
+            setup_pool_for_deposit_stake(&mut svm);
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 1_000_000_000).unwrap();
+        let depositor_lst_ata = create_depositor_ata(&mut svm, &depositor, &lst_mint_pda);
+
+        let other_vote = create_vote_account(&mut svm, &Keypair::new().pubkey());
+        let deposit_stake = create_delegated_stake_account(
+            &mut svm,
+            &depositor.pubkey(),
+            &other_vote,
+            2_000_000_000,
+            u64::MAX,
+        );
+
+        let result = execute_deposit_stake(
+            &mut svm,
+            &depositor,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &deposit_stake,
+            &lst_mint_pda,
+            &depositor_lst_ata,
+        );
+        assert!(result.is_err(), "Stake delegated elsewhere should be rejected");
+
+        println!("\n=== Test Passed: DepositStake Wrong Validator Fails ===");
+    }
+
+    #[test]
+    fn test_deposit_stake_deactivating_fails() {
+        let mut svm = setup_svm();
+        let (pool_state_pda, lst_mint_pda, pool_stake_pda, _, validator_vote) =
+            setup_pool_for_deposit_stake(&mut svm);
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 1_000_000_000).unwrap();
+        let depositor_lst_ata = create_depositor_ata(&mut svm, &depositor, &lst_mint_pda);
+
+        let current_epoch = svm.get_sysvar::<Clock>().epoch;
+        let deposit_stake = create_delegated_stake_account(
+            &mut svm,
+            &depositor.pubkey(),
+            &validator_vote,
+            2_000_000_000,
+            current_epoch,
+        );
+
+        let result = execute_deposit_stake(
+            &mut svm,
+            &depositor,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &deposit_stake,
+            &lst_mint_pda,
+            &depositor_lst_ata,
+        );
+        assert!(result.is_err(), "Deactivating stake should be rejected");
+
+        println!("\n=== Test Passed: DepositStake Deactivating Fails ===");
+    }
+
+    #[test]
+    fn test_deposit_stake_not_owner_fails() {
+        let mut svm = setup_svm();
+        let (pool_state_pda, lst_mint_pda, pool_stake_pda, _, validator_vote) =
+            setup_pool_for_deposit_stake(&mut svm);
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 1_000_000_000).unwrap();
+        let depositor_lst_ata = create_depositor_ata(&mut svm, &depositor, &lst_mint_pda);
+
+        let victim = Keypair::new();
+        let deposit_stake = create_delegated_stake_account(
+            &mut svm,
+            &victim.pubkey(),
+            &validator_vote,
+            2_000_000_000,
+            u64::MAX,
+        );
+
+        let result = execute_deposit_stake(
+            &mut svm,
+            &depositor,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &deposit_stake,
+            &lst_mint_pda,
+            &depositor_lst_ata,
+        );
+        assert!(result.is_err(), "Only the stake authority can deposit it");
+
+        println!("\n=== Test Passed: DepositStake Not Owner Fails ===");
+    }
+}
@@ -1,13 +1,15 @@
 #[cfg(test)]
 mod tests {
+    use liquid_staking_token::PinocchioError;
     use litesvm::LiteSVM;
     use pinocchio::sysvars::{clock::CLOCK_ID as CLOCK_SYSVAR, rent::RENT_ID as RENT_SYSVAR};
     use solana_sdk::{
         account::Account,
-        instruction::{AccountMeta, Instruction},
+        clock::Clock,
+        instruction::{AccountMeta, Instruction, InstructionError},
         pubkey::Pubkey,
         signature::{Keypair, Signer},
-        transaction::Transaction,
+        transaction::{Transaction, TransactionError},
     };
     use spl_associated_token_account::{ID as ATA_PROGRAM_ID, get_associated_token_address};
     use spl_token::ID as TOKEN_PROGRAM_ID;
@@ -70,9 +72,18 @@ mod tests {
         .0
     }
 
-    fn create_initialize_instruction_data(seed: u64) -> Vec<u8> {
+    fn create_initialize_instruction_data(
+        seed: u64,
+        deposit_fee_bps: u16,
+        withdrawal_fee_bps: u16,
+        reward_fee_bps: u16,
+    ) -> Vec<u8> {
         let mut data = vec![0u8]; // Discriminator for Initialize
         data.extend_from_slice(&seed.to_le_bytes());
+        data.extend_from_slice(&deposit_fee_bps.to_le_bytes());
+        data.extend_from_slice(&withdrawal_fee_bps.to_le_bytes());
+        data.extend_from_slice(&reward_fee_bps.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes()); // sol_withdrawal_fee_bps
         data
     }
 
@@ -82,6 +93,26 @@ mod tests {
         data
     }
 
+    fn create_deposit_instruction_data_with_min(amount: u64, minimum_lst_out: u64) -> Vec<u8> {
+        let mut data = create_deposit_instruction_data(amount);
+        data.extend_from_slice(&minimum_lst_out.to_le_bytes());
+        data
+    }
+
+    fn assert_program_error(
+        result: &Result<
+            litesvm::types::TransactionMetadata,
+            litesvm::types::FailedTransactionMetadata,
+        >,
+        expected: PinocchioError,
+    ) {
+        let err = result.as_ref().expect_err("Transaction should fail");
+        assert_eq!(
+            err.err,
+            TransactionError::InstructionError(0, InstructionError::Custom(expected as u32)),
+        );
+    }
+
     fn setup_svm() -> LiteSVM {
         let mut svm = LiteSVM::new().with_builtins().with_sigverify(false);
 
@@ -138,9 +169,64 @@ mod tests {
         }
     }
 
+    /// Reads the manager fee account recorded in pool state
+    fn get_manager_fee_account(svm: &LiteSVM, pool_state_pda: &Pubkey) -> Pubkey {
+        let data = svm.get_account(pool_state_pda).unwrap().data;
+        Pubkey::new_from_array(data[192..224].try_into().unwrap())
+    }
+
+    /// Moves the clock into the next epoch and runs the UpdatePoolBalance crank
+    fn advance_epoch_and_update_pool_balance(
+        svm: &mut LiteSVM,
+        pool_state_pda: &Pubkey,
+        pool_stake_pda: &Pubkey,
+        reserve_stake_pda: &Pubkey,
+        lst_mint_pda: &Pubkey,
+    ) {
+        let mut clock = svm.get_sysvar::<Clock>();
+        clock.epoch += 1;
+        svm.set_sysvar(&clock);
+
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+
+        let update_ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(*pool_state_pda, false),
+                AccountMeta::new_readonly(*pool_stake_pda, false),
+                AccountMeta::new_readonly(*reserve_stake_pda, false),
+                AccountMeta::new(*lst_mint_pda, false),
+                AccountMeta::new(get_manager_fee_account(svm, pool_state_pda), false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ],
+            data: vec![6u8], // UpdatePoolBalance discriminator
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "UpdatePoolBalance should succeed");
+    }
+
     /// Helper to initialize a pool and return all the PDAs
     fn initialize_pool(
         svm: &mut LiteSVM,
+    ) -> (Keypair, Pubkey, Pubkey, Pubkey, Pubkey, Pubkey, u64) {
+        initialize_pool_with_fees(svm, 0, 0, 0)
+    }
+
+    /// Helper to initialize a pool with the given fees and return all the PDAs
+    fn initialize_pool_with_fees(
+        svm: &mut LiteSVM,
+        deposit_fee_bps: u16,
+        withdrawal_fee_bps: u16,
+        reward_fee_bps: u16,
     ) -> (Keypair, Pubkey, Pubkey, Pubkey, Pubkey, Pubkey, u64) {
         let initializer = Keypair::new();
         svm.airdrop(&initializer.pubkey(), 2_000_000_000).unwrap();
@@ -157,7 +243,12 @@ mod tests {
         let initializer_lst_ata =
             get_associated_token_address(&initializer.pubkey(), &lst_mint.pubkey());
 
-        let instruction_data = create_initialize_instruction_data(seed);
+        let instruction_data = create_initialize_instruction_data(
+            seed,
+            deposit_fee_bps,
+            withdrawal_fee_bps,
+            reward_fee_bps,
+        );
 
         let instruction = Instruction {
             program_id: PROGRAM_ID,
@@ -262,6 +353,7 @@ mod tests {
                 AccountMeta::new(reserve_stake_pda, false), // ✅ Changed to writable
                 AccountMeta::new(lst_mint_pda, false),
                 AccountMeta::new(depositor_lst_ata, false),
+                AccountMeta::new(get_manager_fee_account(&svm, &pool_state_pda), false),
                 AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
                 AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
                 AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
@@ -343,7 +435,30 @@ mod tests {
         amount: u64,
     ) -> Result<litesvm::types::TransactionMetadata, litesvm::types::FailedTransactionMetadata>
     {
-        let instruction_data = create_deposit_instruction_data(amount);
+        execute_deposit_with_data(
+            svm,
+            depositor,
+            pool_state_pda,
+            pool_stake_pda,
+            reserve_stake_pda,
+            lst_mint_pda,
+            depositor_lst_ata,
+            create_deposit_instruction_data(amount),
+        )
+    }
+
+    /// Helper to execute deposit with raw instruction data
+    fn execute_deposit_with_data(
+        svm: &mut LiteSVM,
+        depositor: &Keypair,
+        pool_state_pda: &Pubkey,
+        pool_stake_pda: &Pubkey,
+        reserve_stake_pda: &Pubkey,
+        lst_mint_pda: &Pubkey,
+        depositor_lst_ata: &Pubkey,
+        instruction_data: Vec<u8>,
+    ) -> Result<litesvm::types::TransactionMetadata, litesvm::types::FailedTransactionMetadata>
+    {
 
         let instruction = Instruction {
             program_id: PROGRAM_ID,
@@ -354,6 +469,7 @@ mod tests {
                 AccountMeta::new(*reserve_stake_pda, false),
                 AccountMeta::new(*lst_mint_pda, false),
                 AccountMeta::new(*depositor_lst_ata, false),
+                AccountMeta::new(get_manager_fee_account(svm, pool_state_pda), false),
                 AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
                 AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
                 AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
@@ -489,20 +605,20 @@ mod tests {
             total_before as f64 / mint_supply_before as f64
         );
 
-        // Simulate staking rewards by adding SOL to reserve
-        // In real life, this would come from staking rewards on the stake account
+        // Simulate staking rewards, which raise the pool stake's balance and delegation
         let reward_amount = 500_000_000u64; // 0.5 SOL rewards
-        let reserve_account = svm.get_account(&reserve_stake_pda).unwrap();
-        svm.set_account(
-            reserve_stake_pda,
-            Account {
-                lamports: reserve_account.lamports + reward_amount,
-                data: reserve_account.data.clone(),
-                owner: reserve_account.owner,
-                executable: false,
-                rent_epoch: 0,
-            }
-            .into(),
+        let mut pool_stake_account = svm.get_account(&pool_stake_pda).unwrap();
+        pool_stake_account.lamports += reward_amount;
+        let stake = u64::from_le_bytes(pool_stake_account.data[156..164].try_into().unwrap());
+        pool_stake_account.data[156..164].copy_from_slice(&(stake + reward_amount).to_le_bytes());
+        svm.set_account(pool_stake_pda, pool_stake_account).unwrap();
+
+        advance_epoch_and_update_pool_balance(
+            &mut svm,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
         );
 
         // Verify rewards applied
@@ -723,6 +839,7 @@ mod tests {
                 AccountMeta::new(reserve_stake_pda, false),
                 AccountMeta::new(lst_mint_pda, false),
                 AccountMeta::new(depositor_lst_ata, false),
+                AccountMeta::new(derive_ata(&fake_initializer.pubkey(), &lst_mint_pda), false),
                 AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
                 AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
                 AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
@@ -745,4 +862,119 @@ mod tests {
 
         println!("\n=== Test Passed: Uninitialized Pool Rejected ===");
     }
+
+    #[test]
+    fn test_deposit_fee_minted_to_manager() {
+        let mut svm = setup_svm();
+
+        // 1% deposit fee
+        let deposit_fee_bps = 100u16;
+        let (initializer, pool_state_pda, lst_mint_pda, pool_stake_pda, reserve_stake_pda, _, _) =
+            initialize_pool_with_fees(&mut svm, deposit_fee_bps, 0, 0);
+
+        let manager_fee_account = get_manager_fee_account(&svm, &pool_state_pda);
+        assert_eq!(
+            manager_fee_account,
+            get_associated_token_address(&initializer.pubkey(), &lst_mint_pda),
+            "Initializer ATA should be recorded as the manager fee account"
+        );
+
+        let manager_before =
+            get_token_balance(&svm.get_account(&manager_fee_account).unwrap().data);
+        let supply_before = get_mint_supply(&svm.get_account(&lst_mint_pda).unwrap().data);
+
+        let depositor = Keypair::new();
+        let deposit_amount = 1_200_000_000u64;
+        svm.airdrop(&depositor.pubkey(), 2_000_000_000).unwrap();
+        let depositor_lst_ata = create_depositor_ata(&mut svm, &depositor, &lst_mint_pda);
+
+        let result = execute_deposit(
+            &mut svm,
+            &depositor,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+            &depositor_lst_ata,
+            deposit_amount,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Deposit with fee should succeed");
+
+        let minted =
+            get_mint_supply(&svm.get_account(&lst_mint_pda).unwrap().data) - supply_before;
+        let expected_fee = minted * deposit_fee_bps as u64 / 10_000;
+
+        let depositor_balance =
+            get_token_balance(&svm.get_account(&depositor_lst_ata).unwrap().data);
+        let manager_after =
+            get_token_balance(&svm.get_account(&manager_fee_account).unwrap().data);
+
+        assert_eq!(
+            manager_after - manager_before,
+            expected_fee,
+            "Manager should receive the deposit fee in LST"
+        );
+        assert_eq!(
+            depositor_balance,
+            minted - expected_fee,
+            "Depositor should receive the minted amount minus the fee"
+        );
+
+        println!("\n=== Test Passed: Deposit Fee Minted To Manager ===");
+    }
+
+    #[test]
+    fn test_deposit_minimum_lst_out() {
+        let mut svm = setup_svm();
+
+        // 1% deposit fee so the minimum has to account for it
+        let (_, pool_state_pda, lst_mint_pda, pool_stake_pda, reserve_stake_pda, _, _) =
+            initialize_pool_with_fees(&mut svm, 100, 0, 0);
+
+        let depositor = Keypair::new();
+        let deposit_amount = 1_000_000_000u64;
+        svm.airdrop(&depositor.pubkey(), 3_000_000_000).unwrap();
+        let depositor_lst_ata = create_depositor_ata(&mut svm, &depositor, &lst_mint_pda);
+
+        // The gross amount is unreachable once the fee is taken
+        let result = execute_deposit_with_data(
+            &mut svm,
+            &depositor,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+            &depositor_lst_ata,
+            create_deposit_instruction_data_with_min(deposit_amount, deposit_amount),
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_err(), "Deposit below minimum_lst_out should fail");
+        assert_program_error(&result, PinocchioError::SlippageExceeded);
+        assert_eq!(
+            get_token_balance(&svm.get_account(&depositor_lst_ata).unwrap().data),
+            0,
+            "Nothing should be minted on a slippage failure"
+        );
+
+        let expected_out = deposit_amount - deposit_amount / 100;
+        let result = execute_deposit_with_data(
+            &mut svm,
+            &depositor,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+            &depositor_lst_ata,
+            create_deposit_instruction_data_with_min(deposit_amount, expected_out),
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Deposit meeting minimum_lst_out should succeed");
+        assert_eq!(
+            get_token_balance(&svm.get_account(&depositor_lst_ata).unwrap().data),
+            expected_out
+        );
+
+        println!("\n=== Test Passed: Deposit Minimum LST Out Enforced ===");
+    }
 }
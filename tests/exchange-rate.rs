@@ -0,0 +1,173 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use liquid_staking_token::{
+        PinocchioError,
+        client::{self, ExchangeRate, Pool, PoolFees},
+    };
+    use litesvm::{LiteSVM, types::TransactionMetadata};
+    use solana_sdk::{
+        clock::Clock,
+        instruction::Instruction,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    };
+
+    /// Creates a pool managed by the returned keypair
+    fn initialize_pool(svm: &mut LiteSVM) -> (Keypair, Pool) {
+        let manager = Keypair::new();
+        let lst_mint = Keypair::new();
+        svm.airdrop(&manager.pubkey(), 10_000_000_000).unwrap();
+        let validator_vote = create_vote_account(svm, &Keypair::new().pubkey());
+
+        let seed = 25u64;
+        let ix = client::initialize(
+            &manager.pubkey(),
+            &lst_mint.pubkey(),
+            &validator_vote,
+            seed,
+            PoolFees::default(),
+        );
+        send(svm, &manager, ix, &[&lst_mint]).expect("Initialize should succeed");
+
+        let pool = load_pool(svm, &client::find_pool_state_address(seed).0);
+        (manager, pool)
+    }
+
+    fn send_with_metadata(
+        svm: &mut LiteSVM,
+        payer: &Keypair,
+        ix: Instruction,
+        extra_signers: &[&Keypair],
+    ) -> Result<TransactionMetadata, TransactionError> {
+        let mut signers = vec![payer];
+        signers.extend_from_slice(extra_signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &signers,
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx).map_err(|e| e.err)
+    }
+
+    fn get_exchange_rate(
+        svm: &mut LiteSVM,
+        payer: &Keypair,
+        pool: &Pool,
+    ) -> Result<ExchangeRate, TransactionError> {
+        let metadata = send_with_metadata(svm, payer, client::get_exchange_rate(pool), &[])?;
+        svm.expire_blockhash();
+        assert_eq!(metadata.return_data.program_id, client::PROGRAM_ID);
+        Ok(ExchangeRate::from_return_data(&metadata.return_data.data)
+            .expect("Return data should decode"))
+    }
+
+    #[test]
+    fn test_get_exchange_rate_matches_pool_state() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+        deposit(&mut svm, &pool, 5_000_000_000);
+
+        // Rewards credited by the epoch update raise the rate above 1:1
+        let mut account = svm.get_account(&pool.pool_stake).unwrap();
+        account.lamports += 600_000_000;
+        let stake = u64::from_le_bytes(account.data[156..164].try_into().unwrap());
+        account.data[156..164].copy_from_slice(&(stake + 600_000_000).to_le_bytes());
+        svm.set_account(pool.pool_stake, account).unwrap();
+        let mut clock = svm.get_sysvar::<Clock>();
+        clock.epoch += 1;
+        svm.set_sysvar(&clock);
+        send(
+            &mut svm,
+            &manager,
+            client::update_pool_balance(&pool, &[]),
+            &[],
+        )
+        .expect("UpdatePoolBalance should succeed");
+
+        let rate =
+            get_exchange_rate(&mut svm, &manager, &pool).expect("GetExchangeRate should succeed");
+
+        let data = svm.get_account(&pool.address).unwrap().data;
+        let pool_state = client::decode_pool_state(&data).unwrap();
+        assert_eq!(rate.total_lamports, { pool_state.total_lamports });
+        assert_eq!(rate.lst_supply, { pool_state.lst_supply });
+        assert_eq!(
+            rate.lamports_per_lst as u128,
+            rate.total_lamports as u128 * 1_000_000_000 / rate.lst_supply as u128
+        );
+        assert_eq!(
+            rate.lst_per_sol as u128,
+            rate.lst_supply as u128 * 1_000_000_000 / rate.total_lamports as u128
+        );
+        assert!(rate.lamports_per_lst > 1_000_000_000);
+        assert!(rate.lst_per_sol < 1_000_000_000);
+    }
+
+    #[test]
+    fn test_get_exchange_rate_requires_fresh_balance() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+
+        let mut clock = svm.get_sysvar::<Clock>();
+        clock.epoch += 1;
+        svm.set_sysvar(&clock);
+
+        assert_eq!(
+            get_exchange_rate(&mut svm, &manager, &pool).unwrap_err(),
+            custom_error(PinocchioError::StalePoolBalance)
+        );
+
+        send(
+            &mut svm,
+            &manager,
+            client::update_pool_balance(&pool, &[]),
+            &[],
+        )
+        .expect("UpdatePoolBalance should succeed");
+        get_exchange_rate(&mut svm, &manager, &pool)
+            .expect("GetExchangeRate should succeed after the update");
+    }
+
+    #[test]
+    fn test_get_exchange_rate_rejects_foreign_accounts() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+        let stranger = Keypair::new().pubkey();
+
+        let cases = [
+            (
+                Pool {
+                    pool_stake: stranger,
+                    ..pool
+                },
+                PinocchioError::InvalidPoolStake,
+            ),
+            (
+                Pool {
+                    reserve_stake: stranger,
+                    ..pool
+                },
+                PinocchioError::InvalidReserveStake,
+            ),
+            (
+                Pool {
+                    lst_mint: stranger,
+                    ..pool
+                },
+                PinocchioError::InvalidLstMint,
+            ),
+        ];
+
+        for (spoofed, error) in cases {
+            assert_eq!(
+                get_exchange_rate(&mut svm, &manager, &spoofed).unwrap_err(),
+                custom_error(error)
+            );
+        }
+    }
+}
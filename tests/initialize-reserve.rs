@@ -1,9 +1,11 @@
 #[cfg(test)]
 mod tests {
+    use liquid_staking_token::PoolState;
     use litesvm::LiteSVM;
     use pinocchio::sysvars::{clock::CLOCK_ID as CLOCK_SYSVAR, rent::RENT_ID as RENT_SYSVAR};
     use solana_sdk::{
         account::Account,
+        clock::Clock,
         instruction::{AccountMeta, Instruction},
         pubkey::Pubkey,
         signature::{Keypair, Signer},
@@ -66,6 +68,23 @@ mod tests {
         Pubkey::find_program_address(&[b"reserve_stake", pool_state.as_ref()], &PROGRAM_ID)
     }
 
+    fn derive_transient_stake_pda(
+        pool_state: &Pubkey,
+        validator_vote: &Pubkey,
+        epoch: u64,
+    ) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"transient_stake",
+                pool_state.as_ref(),
+                validator_vote.as_ref(),
+                &epoch.to_le_bytes(),
+            ],
+            &PROGRAM_ID,
+        )
+        .0
+    }
+
     fn derive_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
         Pubkey::find_program_address(
             &[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
@@ -79,6 +98,10 @@ mod tests {
     ) -> Vec<u8> {
         let mut data = vec![0u8]; // Discriminator for Initialize
         data.extend_from_slice(&seed.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes()); // deposit_fee_bps
+        data.extend_from_slice(&0u16.to_le_bytes()); // withdrawal_fee_bps
+        data.extend_from_slice(&0u16.to_le_bytes()); // reward_fee_bps
+        data.extend_from_slice(&0u16.to_le_bytes()); // sol_withdrawal_fee_bps
         data
     }
 
@@ -88,6 +111,23 @@ mod tests {
         data
     }
 
+    /// Stands in for SOL deposits: funds the reserve and records the
+    /// lamports as reserve SOL the pool tracks.
+    fn fund_reserve(
+        svm: &mut LiteSVM,
+        pool_state: &Pubkey,
+        reserve_stake: &Pubkey,
+        lamports: u64,
+    ) {
+        svm.airdrop(reserve_stake, lamports).unwrap();
+        let mut account = svm.get_account(pool_state).unwrap();
+        PoolState::load_mut(&mut account.data)
+            .unwrap()
+            .credit_reserve(lamports)
+            .unwrap();
+        svm.set_account(*pool_state, account).unwrap();
+    }
+
     fn setup_svm() -> LiteSVM {
         let mut svm = LiteSVM::new().with_builtins().with_sigverify(false);
 
@@ -224,13 +264,18 @@ mod tests {
             initialize_pool(&mut svm);
 
         // Create InitializeReserve instruction
+        let transient_stake_pda = derive_transient_stake_pda(
+            &pool_state_pda,
+            &validator_vote,
+            svm.get_sysvar::<Clock>().epoch,
+        );
         let instruction_data = vec![2u8]; // Discriminator for InitializeReserve
 
         // Anyone can call this (permissionless crank)
         let crank = Keypair::new();
         svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
 
-        svm.airdrop(&reserve_stake_pda, 1_000_000_000).unwrap();
+        fund_reserve(&mut svm, &pool_state_pda, &reserve_stake_pda, 2_000_000_000);
 
         let instruction = Instruction {
             program_id: PROGRAM_ID,
@@ -238,6 +283,7 @@ mod tests {
                 AccountMeta::new(pool_state_pda, false), // pool_state
                 AccountMeta::new_readonly(pool_stake_pda, false), // pool_stake
                 AccountMeta::new(reserve_stake_pda, false), // reserve_stake
+                AccountMeta::new(transient_stake_pda, false), // transient_stake
                 AccountMeta::new_readonly(validator_vote, false), // validator_vote
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false), // clock
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false), // rent
@@ -260,25 +306,39 @@ mod tests {
         print_transaction_logs(&result);
         assert!(result.is_ok(), "InitializeReserve should succeed");
 
-        // Verify reserve stake is now owned by stake program (initialized)
+        // The reserve stays an undelegated stake account holding only its rent
+        let stake_rent = svm.minimum_balance_for_rent_exemption(200);
         let reserve_after = svm.get_account(&reserve_stake_pda).unwrap();
-        println!("Reserve stake owner after: {:?}", reserve_after.owner);
         assert_eq!(
             reserve_after.owner, STAKE_PROGRAM_ID,
             "Reserve should now be owned by stake program"
         );
+        assert_eq!(
+            u32::from_le_bytes(reserve_after.data[0..4].try_into().unwrap()),
+            1,
+            "Reserve should be initialized but not delegated"
+        );
+        assert_eq!(
+            reserve_after.lamports, stake_rent,
+            "Reserve should keep only its rent"
+        );
 
-        // Verify reserve has stake account data structure
-        assert!(
-            reserve_after.data.len() >= 200,
-            "Reserve should have stake account data"
+        // Everything above it now activates in this epoch's transient stake
+        let transient_after = svm.get_account(&transient_stake_pda).unwrap();
+        assert_eq!(
+            transient_after.owner, STAKE_PROGRAM_ID,
+            "Transient stake should be owned by stake program"
+        );
+        assert_eq!(
+            u32::from_le_bytes(transient_after.data[0..4].try_into().unwrap()),
+            2,
+            "Transient stake should be delegated"
         );
 
-        println!("\n=== Reserve Initialization Verified ===");
+        println!("\n=== Reserve Activation Verified ===");
         println!("  Reserve stake: {}", reserve_stake_pda);
-        println!("  Owner: {:?}", reserve_after.owner);
-        println!("  Lamports: {}", reserve_after.lamports);
-        println!("  Data length: {}", reserve_after.data.len());
+        println!("  Transient stake: {}", transient_stake_pda);
+        println!("  Transient lamports: {}", transient_after.lamports);
     }
 
     #[test]
@@ -290,11 +350,17 @@ mod tests {
             initialize_pool(&mut svm);
 
         // Fund reserve for initialization
-        svm.airdrop(&reserve_stake_pda, 1_000_000_000).unwrap();
+        fund_reserve(&mut svm, &pool_state_pda, &reserve_stake_pda, 2_000_000_000);
 
         let crank = Keypair::new();
         svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
 
+        let transient_stake_pda = derive_transient_stake_pda(
+            &pool_state_pda,
+            &validator_vote,
+            svm.get_sysvar::<Clock>().epoch,
+        );
+
         let instruction_data = vec![2u8]; // Discriminator for InitializeReserve
 
         let instruction = Instruction {
@@ -303,6 +369,7 @@ mod tests {
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new_readonly(pool_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
+                AccountMeta::new(transient_stake_pda, false), // transient_stake
                 AccountMeta::new_readonly(validator_vote, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false),
@@ -326,14 +393,14 @@ mod tests {
         print_transaction_logs(&result);
         assert!(result.is_ok(), "First InitializeReserve should succeed");
 
-        // Verify reserve is initialized
-        let reserve_after_first = svm.get_account(&reserve_stake_pda).unwrap();
+        // Verify this epoch's transient stake is in use
+        let transient_after_first = svm.get_account(&transient_stake_pda).unwrap();
         assert_eq!(
-            reserve_after_first.owner, STAKE_PROGRAM_ID,
-            "Reserve should be owned by stake program"
+            transient_after_first.owner, STAKE_PROGRAM_ID,
+            "Transient stake should be owned by stake program"
         );
 
-        // Second initialization should FAIL
+        // Second activation in the same epoch should FAIL
         let tx2 = Transaction::new_signed_with_payer(
             &[instruction],
             Some(&crank.pubkey()),
@@ -345,7 +412,7 @@ mod tests {
         print_transaction_logs(&result);
         assert!(
             result.is_err(),
-            "Second InitializeReserve should fail - already initialized"
+            "Second InitializeReserve should fail - transient stake in use"
         );
 
         println!("\n=== Test Passed: Double Initialize Reserve Rejected ===");
@@ -356,11 +423,11 @@ mod tests {
         let mut svm = setup_svm();
     
         // Initialize pool with validator A
-        let (_, pool_state_pda, _, pool_stake_pda, reserve_stake_pda, _validator_vote, _) =
+        let (_, pool_state_pda, _, pool_stake_pda, reserve_stake_pda, validator_vote, _) =
             initialize_pool(&mut svm);
     
         // Fund reserve
-        svm.airdrop(&reserve_stake_pda, 1_000_000_000).unwrap();
+        fund_reserve(&mut svm, &pool_state_pda, &reserve_stake_pda, 2_000_000_000);
     
         // Create a DIFFERENT validator vote account
         let attacker_identity = Keypair::new();
@@ -369,6 +436,12 @@ mod tests {
         let crank = Keypair::new();
         svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
     
+        let transient_stake_pda = derive_transient_stake_pda(
+            &pool_state_pda,
+            &validator_vote,
+            svm.get_sysvar::<Clock>().epoch,
+        );
+    
         let instruction_data = vec![2u8];
     
         // Try to initialize with wrong validator
@@ -378,6 +451,7 @@ mod tests {
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new_readonly(pool_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
+                AccountMeta::new(transient_stake_pda, false), // transient_stake
                 AccountMeta::new_readonly(attacker_validator, false), // WRONG validator
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false),
@@ -427,6 +501,12 @@ mod tests {
         let crank = Keypair::new();
         svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
 
+        let transient_stake_pda = derive_transient_stake_pda(
+            &pool_state_pda,
+            &validator_vote,
+            svm.get_sysvar::<Clock>().epoch,
+        );
+
         let instruction_data = vec![2u8];
 
         let instruction = Instruction {
@@ -435,6 +515,7 @@ mod tests {
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new_readonly(pool_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
+                AccountMeta::new(transient_stake_pda, false), // transient_stake
                 AccountMeta::new_readonly(validator_vote, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false),
@@ -486,6 +567,12 @@ mod tests {
         // Fund the non-existent reserve
         svm.airdrop(&reserve_stake_pda, 1_500_000_000).unwrap();
 
+        let transient_stake_pda = derive_transient_stake_pda(
+            &pool_state_pda,
+            &validator_vote,
+            svm.get_sysvar::<Clock>().epoch,
+        );
+
         let instruction_data = vec![2u8];
 
         let instruction = Instruction {
@@ -494,6 +581,7 @@ mod tests {
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new_readonly(pool_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
+                AccountMeta::new(transient_stake_pda, false), // transient_stake
                 AccountMeta::new_readonly(validator_vote, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false),
@@ -538,6 +626,12 @@ mod tests {
         let crank = Keypair::new();
         svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
 
+        let transient_stake_pda = derive_transient_stake_pda(
+            &pool_state_pda,
+            &validator_vote,
+            svm.get_sysvar::<Clock>().epoch,
+        );
+
         let instruction_data = vec![2u8];
 
         let instruction = Instruction {
@@ -546,6 +640,7 @@ mod tests {
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new_readonly(pool_stake_pda, false),
                 AccountMeta::new(fake_reserve.pubkey(), false), // WRONG reserve
+                AccountMeta::new(transient_stake_pda, false), // transient_stake
                 AccountMeta::new_readonly(validator_vote, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false),
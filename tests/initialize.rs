@@ -70,6 +70,10 @@ mod tests {
     fn create_initialize_instruction_data(seed: u64) -> Vec<u8> {
         let mut data = vec![0u8]; // Discriminator for Initialize
         data.extend_from_slice(&seed.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes()); // deposit_fee_bps
+        data.extend_from_slice(&0u16.to_le_bytes()); // withdrawal_fee_bps
+        data.extend_from_slice(&0u16.to_le_bytes()); // reward_fee_bps
+        data.extend_from_slice(&0u16.to_le_bytes()); // sol_withdrawal_fee_bps
         data
     }
 
@@ -609,4 +613,93 @@ mod tests {
                 .unwrap(),
         )
     }
+
+    #[test]
+    fn test_initialize_records_fees() {
+        let mut svm = setup_svm();
+        let initializer = Keypair::new();
+        svm.airdrop(&initializer.pubkey(), 5_000_000_000).unwrap();
+
+        let validator_identity = Keypair::new();
+        let validator_vote = create_vote_account(&mut svm, &validator_identity.pubkey());
+
+        let seed = 12345u64;
+        let (pool_state_pda, _) = derive_pool_state_pda(&initializer.pubkey(), seed);
+        let lst_mint = Keypair::new();
+        let (stake_account_pda, _) = derive_stake_account_pda(&pool_state_pda);
+        let (reserve_stake_pda, _) = derive_reserve_stake_account_pda(&pool_state_pda);
+        let initializer_lst_ata =
+            get_associated_token_address(&initializer.pubkey(), &lst_mint.pubkey());
+
+        let accounts = vec![
+            AccountMeta::new(initializer.pubkey(), true),
+            AccountMeta::new(initializer_lst_ata, false),
+            AccountMeta::new(pool_state_pda, false),
+            AccountMeta::new(lst_mint.pubkey(), true),
+            AccountMeta::new(stake_account_pda, false),
+            AccountMeta::new(reserve_stake_pda, false),
+            AccountMeta::new_readonly(validator_vote, false),
+            AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
+            AccountMeta::new_readonly(RENT_SYSVAR.into(), false),
+            AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false),
+            AccountMeta::new_readonly(STAKE_CONFIG, false),
+            AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+            AccountMeta::new_readonly(ATA_PROGRAM_ID, false),
+        ];
+
+        let fee_data = |deposit: u16, withdrawal: u16, reward: u16, sol_withdrawal: u16| {
+            let mut data = vec![0u8];
+            data.extend_from_slice(&seed.to_le_bytes());
+            data.extend_from_slice(&deposit.to_le_bytes());
+            data.extend_from_slice(&withdrawal.to_le_bytes());
+            data.extend_from_slice(&reward.to_le_bytes());
+            data.extend_from_slice(&sol_withdrawal.to_le_bytes());
+            data
+        };
+
+        // Fees above 10% are rejected
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id: PROGRAM_ID,
+                accounts: accounts.clone(),
+                data: fee_data(1_001, 0, 0, 0),
+            }],
+            Some(&initializer.pubkey()),
+            &[&initializer, &lst_mint],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_err(), "Fee above 1_000 bps should be rejected");
+
+        // Valid fees are stored in pool state
+        let tx = Transaction::new_signed_with_payer(
+            &[Instruction {
+                program_id: PROGRAM_ID,
+                accounts,
+                data: fee_data(50, 25, 1_000, 10),
+            }],
+            Some(&initializer.pubkey()),
+            &[&initializer, &lst_mint],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Initialize with valid fees should succeed");
+
+        let data = svm.get_account(&pool_state_pda).unwrap().data;
+        assert_eq!(
+            Pubkey::new_from_array(data[192..224].try_into().unwrap()),
+            initializer_lst_ata,
+            "Manager fee account should be the initializer ATA"
+        );
+        assert_eq!(u16::from_le_bytes(data[224..226].try_into().unwrap()), 50);
+        assert_eq!(u16::from_le_bytes(data[226..228].try_into().unwrap()), 25);
+        assert_eq!(u16::from_le_bytes(data[228..230].try_into().unwrap()), 1_000);
+        assert_eq!(u16::from_le_bytes(data[230..232].try_into().unwrap()), 10);
+
+        println!("\n=== Test Passed: Fees Recorded In Pool State ===");
+    }
 }
@@ -1,9 +1,11 @@
 #[cfg(test)]
 mod tests {
+    use liquid_staking_token::PoolState;
     use litesvm::LiteSVM;
     use pinocchio::sysvars::{clock::CLOCK_ID as CLOCK_SYSVAR, rent::RENT_ID as RENT_SYSVAR};
     use solana_sdk::{
         account::Account,
+        clock::Clock,
         instruction::{AccountMeta, Instruction},
         pubkey::Pubkey,
         signature::{Keypair, Signer},
@@ -66,6 +68,23 @@ mod tests {
         Pubkey::find_program_address(&[b"reserve_stake", pool_state.as_ref()], &PROGRAM_ID)
     }
 
+    fn derive_transient_stake_pda(
+        pool_state: &Pubkey,
+        validator_vote: &Pubkey,
+        epoch: u64,
+    ) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"transient_stake",
+                pool_state.as_ref(),
+                validator_vote.as_ref(),
+                &epoch.to_le_bytes(),
+            ],
+            &PROGRAM_ID,
+        )
+        .0
+    }
+
     fn derive_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
         Pubkey::find_program_address(
             &[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
@@ -77,6 +96,10 @@ mod tests {
     fn create_initialize_instruction_data(seed: u64) -> Vec<u8> {
         let mut data = vec![0u8]; // Discriminator for Initialize
         data.extend_from_slice(&seed.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes()); // deposit_fee_bps
+        data.extend_from_slice(&0u16.to_le_bytes()); // withdrawal_fee_bps
+        data.extend_from_slice(&0u16.to_le_bytes()); // reward_fee_bps
+        data.extend_from_slice(&0u16.to_le_bytes()); // sol_withdrawal_fee_bps
         data
     }
 
@@ -86,6 +109,23 @@ mod tests {
         data
     }
 
+    /// Stands in for SOL deposits: funds the reserve and records the
+    /// lamports as reserve SOL the pool tracks.
+    fn fund_reserve(
+        svm: &mut LiteSVM,
+        pool_state: &Pubkey,
+        reserve_stake: &Pubkey,
+        lamports: u64,
+    ) {
+        svm.airdrop(reserve_stake, lamports).unwrap();
+        let mut account = svm.get_account(pool_state).unwrap();
+        PoolState::load_mut(&mut account.data)
+            .unwrap()
+            .credit_reserve(lamports)
+            .unwrap();
+        svm.set_account(*pool_state, account).unwrap();
+    }
+
     fn setup_svm() -> LiteSVM {
         let mut svm = LiteSVM::new().with_builtins().with_sigverify(false);
 
@@ -223,18 +263,24 @@ mod tests {
             initialize_pool(&mut svm);
 
         // 2. Add lamports to reserve_stake (simulating deposits)
-        svm.airdrop(&reserve_stake_pda, 2_000_000_000).unwrap();
+        fund_reserve(&mut svm, &pool_state_pda, &reserve_stake_pda, 2_000_000_000);
 
-        // 3. Initialize and delegate reserve_stake
+        // 3. Activate the reserve into this epoch's transient stake
         let crank = Keypair::new();
         svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
 
+        let transient_stake_pda = derive_transient_stake_pda(
+            &pool_state_pda,
+            &validator_vote,
+            svm.get_sysvar::<Clock>().epoch,
+        );
         let init_reserve_ix = Instruction {
             program_id: PROGRAM_ID,
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false), // pool_state
                 AccountMeta::new_readonly(pool_stake_pda, false), // pool_stake
                 AccountMeta::new(reserve_stake_pda, false), // reserve_stake
+                AccountMeta::new(transient_stake_pda, false), // transient_stake
                 AccountMeta::new_readonly(validator_vote, false), // validator_vote
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false), // clock
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false), // rent
@@ -257,7 +303,7 @@ mod tests {
         print_transaction_logs(&result);
         assert!(result.is_ok(), "InitializeReserve should succeed");
 
-        println!("\n=== Reserve Initialized & Delegated ===");
+        println!("\n=== Reserve Activated ===");
 
         // 4. Warp forward to next epoch so both stakes become active
         // Stakes need to be in the same state (both active) to merge
@@ -267,12 +313,13 @@ mod tests {
         // 5. Call MergeReserve
         let pool_stake_before = svm.get_account(&pool_stake_pda).unwrap();
         let reserve_stake_before = svm.get_account(&reserve_stake_pda).unwrap();
+        let transient_stake_before = svm.get_account(&transient_stake_pda).unwrap();
 
         println!("\n=== Before Merge ===");
         println!("  Pool stake lamports: {}", pool_stake_before.lamports);
         println!(
-            "  Reserve stake lamports: {}",
-            reserve_stake_before.lamports
+            "  Transient stake lamports: {}",
+            transient_stake_before.lamports
         );
 
         let merge_ix = Instruction {
@@ -280,7 +327,8 @@ mod tests {
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false),    // pool_state
                 AccountMeta::new(pool_stake_pda, false),    // pool_stake (destination)
-                AccountMeta::new(reserve_stake_pda, false), // reserve_stake (source)
+                AccountMeta::new(transient_stake_pda, false), // transient_stake (source)
+                AccountMeta::new(reserve_stake_pda, false), // reserve_stake
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false), // clock
                 AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false), // stake_history
                 AccountMeta::new_readonly(STAKE_PROGRAM_ID, false), // stake_program
@@ -301,57 +349,152 @@ mod tests {
 
         // 6. Verify merge results
         let pool_stake_after = svm.get_account(&pool_stake_pda).unwrap();
-        let reserve_stake_after = svm.get_account(&reserve_stake_pda);
+        let transient_stake_after = svm.get_account(&transient_stake_pda);
 
         println!("\n=== After Merge ===");
         println!("  Pool stake lamports: {}", pool_stake_after.lamports);
 
-        // Reserve should be closed (absorbed into pool_stake)
-        match reserve_stake_after {
+        // Transient stake should be closed (absorbed into pool_stake)
+        match transient_stake_after {
             Some(acc) => {
-                println!("  Reserve stake lamports: {}", acc.lamports);
-                println!("  Reserve stake owner: {:?}", acc.owner);
-                // After merge, reserve should be empty/system-owned
+                println!("  Transient stake lamports: {}", acc.lamports);
                 assert_eq!(
                     acc.lamports, 0,
-                    "Reserve should have 0 lamports after merge"
+                    "Transient stake should have 0 lamports after merge"
                 );
             }
             None => {
-                println!("  Reserve stake: CLOSED");
+                println!("  Transient stake: CLOSED");
             }
         }
 
-        // Pool stake should have absorbed reserve's lamports
-        let expected_lamports = pool_stake_before.lamports + reserve_stake_before.lamports;
+        // Pool stake absorbs the transient delegation; its rent goes back to the reserve
+        let transient_rent = svm.minimum_balance_for_rent_exemption(200);
+        let expected_lamports =
+            pool_stake_before.lamports + transient_stake_before.lamports - transient_rent;
         assert_eq!(
             pool_stake_after.lamports, expected_lamports,
-            "Pool stake should have absorbed reserve lamports"
+            "Pool stake should have absorbed the transient delegation"
+        );
+
+        // The reserve gets the transient rent back and keeps accepting deposits
+        let reserve_stake_after = svm.get_account(&reserve_stake_pda).unwrap();
+        assert_eq!(
+            reserve_stake_after.lamports,
+            reserve_stake_before.lamports + transient_rent,
+            "Reserve should only gain the transient rent"
         );
 
         println!("\n=== Merge Verified Successfully ===");
     }
 
+    #[test]
+    fn test_merge_legacy_delegated_reserve() {
+        let mut svm = setup_svm();
+
+        let (_, pool_state_pda, _, pool_stake_pda, reserve_stake_pda, _, _) =
+            initialize_pool(&mut svm);
+
+        // The original InitializeReserve delegated the reserve itself; stand
+        // in for it with a copy of the pool stake, plus a later deposit
+        let rent = svm.minimum_balance_for_rent_exemption(200);
+        let delegated = 3_000_000_000u64;
+        let undelegated = 500_000_000u64;
+        let mut reserve = svm.get_account(&pool_stake_pda).unwrap();
+        reserve.lamports = rent + delegated + undelegated;
+        reserve.data[156..164].copy_from_slice(&delegated.to_le_bytes());
+        svm.set_account(reserve_stake_pda, reserve.clone()).unwrap();
+
+        let mut pool_account = svm.get_account(&pool_state_pda).unwrap();
+        PoolState::load_mut(&mut pool_account.data)
+            .unwrap()
+            .reserve_lamports = reserve.lamports;
+        svm.set_account(pool_state_pda, pool_account).unwrap();
+
+        svm.warp_to_slot(432_000 * 2);
+
+        let crank = Keypair::new();
+        svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
+
+        let pool_stake_before = svm.get_account(&pool_stake_pda).unwrap();
+
+        // The reserve is passed as the source
+        let merge_ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(pool_state_pda, false),    // pool_state
+                AccountMeta::new(pool_stake_pda, false),    // pool_stake (destination)
+                AccountMeta::new(reserve_stake_pda, false), // legacy reserve (source)
+                AccountMeta::new(reserve_stake_pda, false), // reserve_stake
+                AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false), // clock
+                AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false), // stake_history
+                AccountMeta::new_readonly(STAKE_PROGRAM_ID, false), // stake_program
+            ],
+            data: vec![3u8], // MergeReserve discriminator
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[merge_ix],
+            Some(&crank.pubkey()),
+            &[&crank],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(
+            result.is_ok(),
+            "MergeReserve should recover the legacy reserve"
+        );
+
+        // Only the delegation moves to the pool stake
+        let pool_stake_after = svm.get_account(&pool_stake_pda).unwrap();
+        assert_eq!(
+            pool_stake_after.lamports,
+            pool_stake_before.lamports + delegated
+        );
+
+        // The reserve is undelegated again and keeps its rent and the deposit
+        let reserve_after = svm.get_account(&reserve_stake_pda).unwrap();
+        assert_eq!(reserve_after.lamports, rent + undelegated);
+        assert_eq!(
+            &reserve_after.data[..4],
+            &[0u8; 4],
+            "Reserve should be uninitialized"
+        );
+
+        let pool_account = svm.get_account(&pool_state_pda).unwrap();
+        let pool_state = PoolState::load(&pool_account.data).unwrap();
+        assert_eq!(pool_state.reserve_lamports(), rent + undelegated);
+    }
+
     #[test]
     fn test_merge_reserve_before_initialized_fails() {
         let mut svm = setup_svm();
 
         // Initialize pool (reserve is created but NOT initialized/delegated)
-        let (_, pool_state_pda, _, pool_stake_pda, reserve_stake_pda, _, _) =
+        let (_, pool_state_pda, _, pool_stake_pda, reserve_stake_pda, validator_vote, _) =
             initialize_pool(&mut svm);
 
         // Add lamports to reserve but DON'T call InitializeReserve
-        svm.airdrop(&reserve_stake_pda, 2_000_000_000).unwrap();
+        fund_reserve(&mut svm, &pool_state_pda, &reserve_stake_pda, 2_000_000_000);
 
         let crank = Keypair::new();
         svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
 
+        let transient_stake_pda = derive_transient_stake_pda(
+            &pool_state_pda,
+            &validator_vote,
+            svm.get_sysvar::<Clock>().epoch,
+        );
+
         // Try to merge without initializing reserve first
         let merge_ix = Instruction {
             program_id: PROGRAM_ID,
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new(pool_stake_pda, false),
+                AccountMeta::new(transient_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false),
@@ -372,7 +515,7 @@ mod tests {
 
         assert!(
             result.is_err(),
-            "Merge should fail when reserve is not initialized/delegated"
+            "Merge should fail when no transient stake was activated"
         );
 
         println!("\n=== Test Passed: Merge Before Initialize Rejected ===");
@@ -387,18 +530,24 @@ mod tests {
             initialize_pool(&mut svm);
 
         // Add lamports to reserve
-        svm.airdrop(&reserve_stake_pda, 2_000_000_000).unwrap();
+        fund_reserve(&mut svm, &pool_state_pda, &reserve_stake_pda, 2_000_000_000);
 
         let crank = Keypair::new();
         svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
 
         // Initialize reserve
+        let transient_stake_pda = derive_transient_stake_pda(
+            &pool_state_pda,
+            &validator_vote,
+            svm.get_sysvar::<Clock>().epoch,
+        );
         let init_reserve_ix = Instruction {
             program_id: PROGRAM_ID,
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new_readonly(pool_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
+                AccountMeta::new(transient_stake_pda, false),
                 AccountMeta::new_readonly(validator_vote, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false),
@@ -427,6 +576,7 @@ mod tests {
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new(pool_stake_pda, false),
+                AccountMeta::new(transient_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false),
@@ -447,15 +597,15 @@ mod tests {
         print_transaction_logs(&result);
         assert!(result.is_ok(), "First merge should succeed");
 
-        // Verify reserve was absorbed
-        let reserve_after_first = svm.get_account(&reserve_stake_pda);
+        // Verify the transient stake was absorbed
+        let transient_after_first = svm.get_account(&transient_stake_pda);
         eprintln!("\n=== After First Merge ===");
-        match &reserve_after_first {
+        match &transient_after_first {
             Some(acc) => eprintln!(
-                "  Reserve lamports: {}, owner: {:?}",
+                "  Transient lamports: {}, owner: {:?}",
                 acc.lamports, acc.owner
             ),
-            None => eprintln!("  Reserve: CLOSED"),
+            None => eprintln!("  Transient: CLOSED"),
         }
 
         // Second merge should fail
@@ -471,7 +621,7 @@ mod tests {
 
         assert!(
             result.is_err(),
-            "Second merge should fail - reserve already absorbed"
+            "Second merge should fail - transient stake already absorbed"
         );
 
         println!("\n=== Test Passed: Double Merge Rejected ===");
@@ -482,7 +632,7 @@ mod tests {
         let mut svm = setup_svm();
 
         // Initialize pool
-        let (_, pool_state_pda, _, pool_stake_pda, reserve_stake_pda, _, _) =
+        let (_, pool_state_pda, _, pool_stake_pda, reserve_stake_pda, validator_vote, _) =
             initialize_pool(&mut svm);
 
         // DON'T add any lamports to reserve - it stays empty
@@ -496,11 +646,18 @@ mod tests {
             reserve_before.lamports
         );
 
+        let transient_stake_pda = derive_transient_stake_pda(
+            &pool_state_pda,
+            &validator_vote,
+            svm.get_sysvar::<Clock>().epoch,
+        );
+
         let merge_ix = Instruction {
             program_id: PROGRAM_ID,
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new(pool_stake_pda, false),
+                AccountMeta::new(transient_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false),
@@ -521,7 +678,7 @@ mod tests {
 
         assert!(
             result.is_err(),
-            "Merge should fail when reserve has 0 lamports"
+            "Merge should fail when the transient stake has 0 lamports"
         );
 
         println!("\n=== Test Passed: Empty Reserve Merge Rejected ===");
@@ -536,17 +693,23 @@ mod tests {
             initialize_pool(&mut svm);
 
         // Add lamports and initialize reserve
-        svm.airdrop(&reserve_stake_pda, 2_000_000_000).unwrap();
+        fund_reserve(&mut svm, &pool_state_pda, &reserve_stake_pda, 2_000_000_000);
 
         let crank = Keypair::new();
         svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
 
+        let transient_stake_pda = derive_transient_stake_pda(
+            &pool_state_pda,
+            &validator_vote,
+            svm.get_sysvar::<Clock>().epoch,
+        );
         let init_reserve_ix = Instruction {
             program_id: PROGRAM_ID,
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new_readonly(_pool_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
+                AccountMeta::new(transient_stake_pda, false),
                 AccountMeta::new_readonly(validator_vote, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false),
@@ -589,6 +752,7 @@ mod tests {
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new(fake_pool_stake.pubkey(), false), // WRONG pool stake
+                AccountMeta::new(transient_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false),
@@ -621,17 +785,23 @@ mod tests {
             initialize_pool(&mut svm);
 
         // Add lamports and initialize real reserve
-        svm.airdrop(&reserve_stake_pda, 2_000_000_000).unwrap();
+        fund_reserve(&mut svm, &pool_state_pda, &reserve_stake_pda, 2_000_000_000);
 
         let crank = Keypair::new();
         svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
 
+        let transient_stake_pda = derive_transient_stake_pda(
+            &pool_state_pda,
+            &validator_vote,
+            svm.get_sysvar::<Clock>().epoch,
+        );
         let init_reserve_ix = Instruction {
             program_id: PROGRAM_ID,
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new_readonly(pool_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
+                AccountMeta::new(transient_stake_pda, false),
                 AccountMeta::new_readonly(validator_vote, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false),
@@ -655,7 +825,7 @@ mod tests {
         let slots_per_epoch = 432_000;
         svm.warp_to_slot(slots_per_epoch * 2);
 
-        // Create a FAKE reserve stake account
+        // Create a FAKE transient stake account
         let fake_reserve = Keypair::new();
         svm.set_account(
             fake_reserve.pubkey(),
@@ -674,7 +844,8 @@ mod tests {
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new(pool_stake_pda, false),
-                AccountMeta::new(fake_reserve.pubkey(), false), // WRONG reserve
+                AccountMeta::new(fake_reserve.pubkey(), false), // WRONG transient stake
+                AccountMeta::new(reserve_stake_pda, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false),
                 AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
@@ -0,0 +1,194 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use liquid_staking_token::{
+        MAX_NAME_LEN, MAX_SYMBOL_LEN, PinocchioError,
+        client::{self, Pool, PoolFees, TokenMetadataArgs},
+    };
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        instruction::{AccountMeta, Instruction, InstructionError},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    };
+
+    /// Creates a pool managed by the returned keypair
+    fn initialize_pool(svm: &mut LiteSVM) -> (Keypair, Pool) {
+        let manager = Keypair::new();
+        let lst_mint = Keypair::new();
+        svm.airdrop(&manager.pubkey(), 10_000_000_000).unwrap();
+        let validator_vote = create_vote_account(svm, &Keypair::new().pubkey());
+
+        let seed = 89u64;
+        let ix = client::initialize(
+            &manager.pubkey(),
+            &lst_mint.pubkey(),
+            &validator_vote,
+            seed,
+            PoolFees::default(),
+        );
+        send(svm, &manager, ix, &[&lst_mint]).expect("Initialize should succeed");
+
+        let pool = load_pool(svm, &client::find_pool_state_address(seed).0);
+        (manager, pool)
+    }
+
+    fn metadata_args() -> TokenMetadataArgs {
+        TokenMetadataArgs {
+            name: "Staked SOL".to_string(),
+            symbol: "stSOL".to_string(),
+            uri: "https://example.com/stsol.json".to_string(),
+        }
+    }
+
+    /// Initialize with token metadata for a fresh mint and vote account
+    fn initialize_with_metadata_ix(
+        svm: &mut LiteSVM,
+        initializer: &Keypair,
+        lst_mint: &Keypair,
+        metadata: &TokenMetadataArgs,
+    ) -> Instruction {
+        svm.airdrop(&initializer.pubkey(), 10_000_000_000).unwrap();
+        let validator_vote = create_vote_account(svm, &Keypair::new().pubkey());
+
+        client::initialize_with_metadata(
+            &initializer.pubkey(),
+            &lst_mint.pubkey(),
+            &validator_vote,
+            90,
+            PoolFees::default(),
+            metadata,
+        )
+    }
+
+    #[test]
+    fn test_initialize_with_metadata_encoding() {
+        let initializer = Pubkey::new_unique();
+        let lst_mint = Pubkey::new_unique();
+        let fees = PoolFees::default();
+        let metadata = metadata_args();
+
+        let plain = client::initialize(&initializer, &lst_mint, &Pubkey::new_unique(), 7, fees);
+        let ix = client::initialize_with_metadata(
+            &initializer,
+            &lst_mint,
+            &Pubkey::new_unique(),
+            7,
+            fees,
+            &metadata,
+        );
+
+        // Metadata follows the fixed fields, each field prefixed by its length
+        assert_eq!(&ix.data[..plain.data.len()], &plain.data[..]);
+        let mut expected = Vec::new();
+        for field in [&metadata.name, &metadata.symbol, &metadata.uri] {
+            expected.push(field.len() as u8);
+            expected.extend_from_slice(field.as_bytes());
+        }
+        assert_eq!(&ix.data[plain.data.len()..], &expected[..]);
+
+        // Metadata PDA and program are appended after the existing accounts
+        assert_eq!(ix.accounts.len(), plain.accounts.len() + 2);
+        assert_eq!(
+            ix.accounts[plain.accounts.len()],
+            AccountMeta::new(client::find_metadata_address(&lst_mint).0, false)
+        );
+        assert_eq!(
+            ix.accounts[plain.accounts.len() + 1],
+            AccountMeta::new_readonly(client::TOKEN_METADATA_PROGRAM_ID, false)
+        );
+    }
+
+    #[test]
+    fn test_initialize_rejects_oversized_metadata() {
+        let mut svm = setup_svm();
+
+        for metadata in [
+            TokenMetadataArgs {
+                name: "n".repeat(MAX_NAME_LEN + 1),
+                ..metadata_args()
+            },
+            TokenMetadataArgs {
+                symbol: "s".repeat(MAX_SYMBOL_LEN + 1),
+                ..metadata_args()
+            },
+            TokenMetadataArgs {
+                symbol: String::new(),
+                ..metadata_args()
+            },
+        ] {
+            let initializer = Keypair::new();
+            let lst_mint = Keypair::new();
+            let ix = initialize_with_metadata_ix(&mut svm, &initializer, &lst_mint, &metadata);
+
+            assert_eq!(
+                send(&mut svm, &initializer, ix, &[&lst_mint]),
+                Err(custom_error(PinocchioError::InvalidTokenMetadata)),
+                "Metadata {metadata:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_initialize_rejects_wrong_metadata_account() {
+        let mut svm = setup_svm();
+        let initializer = Keypair::new();
+        let lst_mint = Keypair::new();
+
+        let mut ix =
+            initialize_with_metadata_ix(&mut svm, &initializer, &lst_mint, &metadata_args());
+        let metadata_index = ix.accounts.len() - 2;
+        ix.accounts[metadata_index].pubkey = Pubkey::new_unique();
+
+        assert_eq!(
+            send(&mut svm, &initializer, ix, &[&lst_mint]),
+            Err(custom_error(PinocchioError::InvalidMetadataAccount))
+        );
+    }
+
+    // The program's `NotEnoughAccountKeys` still surfaces under the old name
+    #[allow(deprecated)]
+    #[test]
+    fn test_initialize_metadata_requires_accounts() {
+        let mut svm = setup_svm();
+        let initializer = Keypair::new();
+        let lst_mint = Keypair::new();
+
+        let mut ix =
+            initialize_with_metadata_ix(&mut svm, &initializer, &lst_mint, &metadata_args());
+        ix.accounts.truncate(ix.accounts.len() - 2);
+
+        assert_eq!(
+            send(&mut svm, &initializer, ix, &[&lst_mint]),
+            Err(TransactionError::InstructionError(
+                0,
+                InstructionError::NotEnoughAccountKeys
+            ))
+        );
+    }
+
+    #[test]
+    fn test_update_token_metadata_requires_manager() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+
+        let impostor = Keypair::new();
+        svm.airdrop(&impostor.pubkey(), 1_000_000_000).unwrap();
+
+        let ix = client::update_token_metadata(
+            &Pool {
+                manager: impostor.pubkey(),
+                ..pool
+            },
+            &metadata_args(),
+        );
+
+        assert_eq!(
+            send(&mut svm, &impostor, ix, &[]),
+            Err(custom_error(PinocchioError::InvalidAuthority))
+        );
+    }
+}
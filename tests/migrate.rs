@@ -0,0 +1,222 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use liquid_staking_token::{
+        PinocchioError, PoolState,
+        client::{self, Pool, PoolFees},
+    };
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        clock::Clock,
+        instruction::InstructionError,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    };
+
+    /// Unversioned layout before transient stakes, roles and pause bits
+    const LEGACY_LEN: usize = 328;
+    /// Layout of the first deployment, before fees and the cached balance
+    const BASELINE_LEN: usize = 192;
+    const VERSION_OFFSET: usize = 161;
+
+    /// Creates a pool, then rewrites its account the way an unversioned
+    /// deployment stored it in `len` bytes
+    fn initialize_legacy_pool(svm: &mut LiteSVM, len: usize) -> (Keypair, Pool) {
+        let manager = Keypair::new();
+        let lst_mint = Keypair::new();
+        svm.airdrop(&manager.pubkey(), 10_000_000_000).unwrap();
+        let validator_vote = create_vote_account(svm, &Keypair::new().pubkey());
+
+        let seed = 99u64;
+        let ix = client::initialize(
+            &manager.pubkey(),
+            &lst_mint.pubkey(),
+            &validator_vote,
+            seed,
+            PoolFees::default(),
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&manager.pubkey()),
+            &[&manager, &lst_mint],
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx).expect("Initialize should succeed");
+
+        let (address, _) = client::find_pool_state_address(seed);
+        let mut account = svm.get_account(&address).unwrap();
+        let pool = Pool::from_account_data(address, &account.data).unwrap();
+
+        account.data.truncate(len);
+        account.data[VERSION_OFFSET] = 0;
+        account.lamports = svm.minimum_balance_for_rent_exemption(len);
+        svm.set_account(address, account).unwrap();
+
+        (manager, pool)
+    }
+
+    #[test]
+    fn test_migrate_legacy_pool() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_legacy_pool(&mut svm, LEGACY_LEN);
+
+        // Nothing but MigratePool accepts the old layout
+        let result = send(&mut svm, &manager, client::set_status(&pool, 0), &[]);
+        assert_eq!(
+            result,
+            Err(custom_error(PinocchioError::PoolMigrationRequired))
+        );
+
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+        send(
+            &mut svm,
+            &payer,
+            client::migrate_pool(&pool.address, &payer.pubkey()),
+            &[],
+        )
+        .expect("MigratePool should succeed");
+
+        let account = svm.get_account(&pool.address).unwrap();
+        assert_eq!(account.data.len(), PoolState::LEN);
+        assert!(
+            account.lamports >= svm.minimum_balance_for_rent_exemption(PoolState::LEN),
+            "Payer should top up rent for the larger account"
+        );
+
+        let pool_state = client::decode_pool_state(&account.data).unwrap();
+        assert_eq!(pool_state.version(), PoolState::CURRENT_VERSION);
+        assert_eq!(
+            pool_state.staker(),
+            manager.pubkey().to_bytes(),
+            "The old authority keeps the staker role"
+        );
+        assert_eq!(pool_state.transient_lamports(), 0);
+        assert_eq!(pool_state.status(), 0);
+
+        let migrated = Pool::new(pool.address, &pool_state);
+        assert_eq!(migrated, pool, "Existing fields are preserved");
+
+        send(&mut svm, &manager, client::set_status(&pool, 0), &[])
+            .expect("Migrated pool should be usable");
+    }
+
+    // The program's `NotEnoughAccountKeys` still surfaces under the old name
+    #[allow(deprecated)]
+    #[test]
+    fn test_migrate_baseline_pool() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_legacy_pool(&mut svm, BASELINE_LEN);
+
+        // Rewards the baseline program would have priced in from lamports
+        let mut pool_stake = svm.get_account(&pool.pool_stake).unwrap();
+        pool_stake.lamports += 5_000_000;
+        svm.set_account(pool.pool_stake, pool_stake).unwrap();
+        let expected_total = svm.get_account(&pool.pool_stake).unwrap().lamports
+            + svm.get_account(&pool.reserve_stake).unwrap().lamports;
+
+        // The stake accounts are needed to value the pool
+        let mut ix = client::migrate_pool(&pool.address, &manager.pubkey());
+        ix.accounts.truncate(3);
+        assert_eq!(
+            send(&mut svm, &manager, ix, &[]),
+            Err(TransactionError::InstructionError(
+                0,
+                InstructionError::NotEnoughAccountKeys
+            ))
+        );
+
+        send(
+            &mut svm,
+            &manager,
+            client::migrate_pool(&pool.address, &manager.pubkey()),
+            &[],
+        )
+        .expect("MigratePool should succeed");
+
+        let account = svm.get_account(&pool.address).unwrap();
+        assert_eq!(account.data.len(), PoolState::LEN);
+
+        let pool_state = client::decode_pool_state(&account.data).unwrap();
+        assert_eq!(pool_state.version(), PoolState::CURRENT_VERSION);
+        assert_eq!(pool_state.total_lamports(), expected_total);
+        // Balance the pool stake holds beyond its delegation is swept into the tracked reserve
+        let reserve_balance = svm.get_account(&pool.reserve_stake).unwrap().lamports;
+        assert_eq!(pool_state.reserve_lamports(), reserve_balance);
+        assert_eq!(
+            reserve_balance + svm.get_account(&pool.pool_stake).unwrap().lamports,
+            expected_total
+        );
+        assert_eq!(
+            pool_state.last_update_epoch(),
+            svm.get_sysvar::<Clock>().epoch
+        );
+        assert_eq!(pool_state.staker(), manager.pubkey().to_bytes());
+        assert_eq!(
+            Pubkey::new_from_array(pool_state.manager_fee_account()),
+            pool.lst_token_address(&manager.pubkey()),
+            "Fees go to the manager's token account"
+        );
+        assert_eq!(pool_state.deposit_fee_bps(), 0);
+        assert_eq!(pool_state.withdrawal_fee_bps(), 0);
+        assert_eq!(pool_state.reward_fee_bps(), 0);
+        assert_eq!(pool_state.sol_withdrawal_fee_bps(), 0);
+        assert_eq!(pool_state.max_total_lamports(), 0);
+        assert_eq!(pool_state.min_deposit_lamports(), 0);
+
+        send(&mut svm, &manager, client::set_status(&pool, 0), &[])
+            .expect("Migrated pool should be usable");
+    }
+
+    #[test]
+    fn test_migrate_current_pool_fails() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_legacy_pool(&mut svm, LEGACY_LEN);
+
+        send(
+            &mut svm,
+            &manager,
+            client::migrate_pool(&pool.address, &manager.pubkey()),
+            &[],
+        )
+        .expect("MigratePool should succeed");
+
+        let result = send(
+            &mut svm,
+            &manager,
+            client::migrate_pool(&pool.address, &manager.pubkey()),
+            &[],
+        );
+        assert_eq!(
+            result,
+            Err(custom_error(PinocchioError::PoolAlreadyMigrated))
+        );
+    }
+
+    #[test]
+    fn test_unknown_version_is_rejected() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_legacy_pool(&mut svm, LEGACY_LEN);
+
+        send(
+            &mut svm,
+            &manager,
+            client::migrate_pool(&pool.address, &manager.pubkey()),
+            &[],
+        )
+        .expect("MigratePool should succeed");
+
+        let mut account = svm.get_account(&pool.address).unwrap();
+        account.data[VERSION_OFFSET] = PoolState::CURRENT_VERSION + 1;
+        svm.set_account(pool.address, account).unwrap();
+
+        let result = send(&mut svm, &manager, client::set_status(&pool, 0), &[]);
+        assert_eq!(
+            result,
+            Err(custom_error(PinocchioError::UnsupportedPoolVersion))
+        );
+    }
+}
@@ -0,0 +1,270 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use liquid_staking_token::{
+        PinocchioError,
+        client::{self, AverageRate, Pool, PoolFees},
+    };
+    use litesvm::{LiteSVM, types::TransactionMetadata};
+    use solana_sdk::{
+        clock::Clock,
+        instruction::{Instruction, InstructionError},
+        signature::{Keypair, Signer},
+        transaction::{Transaction, TransactionError},
+    };
+
+    /// Creates a pool managed by the returned keypair
+    fn initialize_pool(svm: &mut LiteSVM) -> (Keypair, Pool) {
+        let manager = Keypair::new();
+        let lst_mint = Keypair::new();
+        svm.airdrop(&manager.pubkey(), 10_000_000_000).unwrap();
+        let validator_vote = create_vote_account(svm, &Keypair::new().pubkey());
+
+        let seed = 24u64;
+        let ix = client::initialize(
+            &manager.pubkey(),
+            &lst_mint.pubkey(),
+            &validator_vote,
+            seed,
+            PoolFees::default(),
+        );
+        send(svm, &manager, ix, &[&lst_mint]).expect("Initialize should succeed");
+
+        let pool = load_pool(svm, &client::find_pool_state_address(seed).0);
+        (manager, pool)
+    }
+
+    fn send_with_metadata(
+        svm: &mut LiteSVM,
+        payer: &Keypair,
+        ix: Instruction,
+        extra_signers: &[&Keypair],
+    ) -> Result<TransactionMetadata, TransactionError> {
+        let mut signers = vec![payer];
+        signers.extend_from_slice(extra_signers);
+
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &signers,
+            svm.latest_blockhash(),
+        );
+        svm.send_transaction(tx).map_err(|e| e.err)
+    }
+
+    /// Moves to the next epoch and runs the epoch update crank
+    fn update_next_epoch(svm: &mut LiteSVM, pool: &Pool, crank: &Keypair) {
+        let mut clock = svm.get_sysvar::<Clock>();
+        clock.epoch += 1;
+        svm.set_sysvar(&clock);
+        svm.expire_blockhash();
+
+        send(svm, crank, client::update_pool_balance(pool, &[]), &[])
+            .expect("UpdatePoolBalance should succeed");
+    }
+
+    /// Lamports per LST recorded in the pool state
+    fn pool_rate(svm: &LiteSVM, pool: &Pool) -> u64 {
+        let data = svm.get_account(&pool.address).unwrap().data;
+        let pool_state = client::decode_pool_state(&data).unwrap();
+        liquid_staking_token::calculate_exchange_rate(
+            pool_state.total_lamports,
+            pool_state.lst_supply,
+        )
+        .unwrap()
+    }
+
+    fn history(svm: &LiteSVM, pool: &Pool) -> Vec<(u64, u64)> {
+        let address = client::find_rate_history_address(&pool.address).0;
+        client::decode_rate_history(&svm.get_account(&address).unwrap().data)
+            .expect("Rate history should decode")
+            .iter()
+            .map(|entry| (entry.epoch, entry.rate().unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_update_appends_to_history() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+        let crank = Keypair::new();
+        svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
+
+        send(
+            &mut svm,
+            &manager,
+            client::initialize_rate_history(&pool),
+            &[],
+        )
+        .expect("InitializeRateHistory should succeed");
+        let pool = load_pool(&svm, &pool.address);
+        assert_eq!(
+            pool.rate_history,
+            Some(client::find_rate_history_address(&pool.address).0)
+        );
+
+        let start_epoch = svm.get_sysvar::<Clock>().epoch;
+        let start_rate = pool_rate(&svm, &pool);
+        assert_eq!(history(&svm, &pool), vec![(start_epoch, start_rate)]);
+
+        add_rewards(&mut svm, &pool.pool_stake, 50_000_000);
+        update_next_epoch(&mut svm, &pool, &crank);
+        update_next_epoch(&mut svm, &pool, &crank);
+
+        let rate = pool_rate(&svm, &pool);
+        assert!(rate > start_rate, "Rewards should raise the rate");
+        assert_eq!(
+            history(&svm, &pool),
+            vec![
+                (start_epoch + 2, rate),
+                (start_epoch + 1, rate),
+                (start_epoch, start_rate),
+            ],
+            "Entries should read newest first"
+        );
+    }
+
+    #[test]
+    fn test_update_requires_history() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+
+        send(
+            &mut svm,
+            &manager,
+            client::initialize_rate_history(&pool),
+            &[],
+        )
+        .expect("InitializeRateHistory should succeed");
+
+        let mut clock = svm.get_sysvar::<Clock>();
+        clock.epoch += 1;
+        svm.set_sysvar(&clock);
+
+        // `pool` was loaded before the history existed, so it leaves it out
+        let result = send(
+            &mut svm,
+            &manager,
+            client::update_pool_balance(&pool, &[]),
+            &[],
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            custom_error(PinocchioError::MissingRateHistory)
+        );
+
+        let pool = load_pool(&svm, &pool.address);
+        send(
+            &mut svm,
+            &manager,
+            client::update_pool_balance(&pool, &[]),
+            &[],
+        )
+        .expect("UpdatePoolBalance with the history should succeed");
+    }
+
+    #[test]
+    fn test_only_manager_initializes_history() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+
+        let impostor = Keypair::new();
+        svm.airdrop(&impostor.pubkey(), 1_000_000_000).unwrap();
+        let spoofed = Pool {
+            manager: impostor.pubkey(),
+            ..pool
+        };
+        let result = send(
+            &mut svm,
+            &impostor,
+            client::initialize_rate_history(&spoofed),
+            &[],
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            custom_error(PinocchioError::InvalidAuthority)
+        );
+
+        send(
+            &mut svm,
+            &manager,
+            client::initialize_rate_history(&pool),
+            &[],
+        )
+        .expect("InitializeRateHistory should succeed");
+        svm.expire_blockhash();
+        let result = send(
+            &mut svm,
+            &manager,
+            client::initialize_rate_history(&pool),
+            &[],
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            custom_error(PinocchioError::RateHistoryAlreadyInitialized)
+        );
+    }
+
+    #[test]
+    fn test_get_average_rate() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+        let crank = Keypair::new();
+        svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
+
+        send(
+            &mut svm,
+            &manager,
+            client::initialize_rate_history(&pool),
+            &[],
+        )
+        .expect("InitializeRateHistory should succeed");
+        let pool = load_pool(&svm, &pool.address);
+        let start_epoch = svm.get_sysvar::<Clock>().epoch;
+        let mut rates = vec![pool_rate(&svm, &pool)];
+
+        for _ in 0..2 {
+            add_rewards(&mut svm, &pool.pool_stake, 100_000_000);
+            update_next_epoch(&mut svm, &pool, &crank);
+            rates.push(pool_rate(&svm, &pool));
+        }
+
+        let query = |svm: &mut LiteSVM, epochs: u64| {
+            let metadata =
+                send_with_metadata(svm, &crank, client::get_average_rate(&pool, epochs), &[])
+                    .expect("GetAverageRate should succeed");
+            svm.expire_blockhash();
+            assert_eq!(metadata.return_data.program_id, client::PROGRAM_ID);
+            AverageRate::from_return_data(&metadata.return_data.data)
+                .expect("Return data should decode")
+        };
+
+        assert_eq!(
+            query(&mut svm, 2),
+            AverageRate {
+                current_rate: rates[2],
+                average_rate: (rates[1] + rates[2]) / 2,
+                first_epoch: start_epoch + 1,
+                last_epoch: start_epoch + 2,
+            }
+        );
+        assert_eq!(
+            query(&mut svm, 100),
+            AverageRate {
+                current_rate: rates[2],
+                average_rate: rates.iter().sum::<u64>() / 3,
+                first_epoch: start_epoch,
+                last_epoch: start_epoch + 2,
+            },
+            "A window longer than the history averages all of it"
+        );
+
+        let result = send(&mut svm, &crank, client::get_average_rate(&pool, 0), &[]);
+        assert_eq!(
+            result.unwrap_err(),
+            TransactionError::InstructionError(0, InstructionError::InvalidInstructionData)
+        );
+    }
+}
@@ -0,0 +1,184 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+
+    /// Helper to execute Redelegate signed by `authority`
+    fn execute_redelegate(
+        svm: &mut LiteSVM,
+        authority: &Keypair,
+        pool_state_pda: &Pubkey,
+        pool_stake_pda: &Pubkey,
+        new_validator_vote: &Pubkey,
+    ) -> TransactionResult {
+        let instruction = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(authority.pubkey(), true),
+                AccountMeta::new(*pool_state_pda, false),
+                AccountMeta::new(*pool_stake_pda, false),
+                AccountMeta::new_readonly(*new_validator_vote, false),
+                AccountMeta::new_readonly(CLOCK_SYSVAR, false),
+                AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+            ],
+            data: vec![9u8], // Redelegate discriminator
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&authority.pubkey()),
+            &[authority],
+            svm.latest_blockhash(),
+        );
+
+        send_transaction(svm, transaction)
+    }
+
+    /// Helper to execute the CompleteRedelegate crank
+    fn execute_complete_redelegate(
+        svm: &mut LiteSVM,
+        pool_state_pda: &Pubkey,
+        pool_stake_pda: &Pubkey,
+        new_validator_vote: &Pubkey,
+    ) -> TransactionResult {
+        let crank = Keypair::new();
+        svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
+        let (reserve_stake_pda, _) = derive_reserve_stake_account_pda(pool_state_pda);
+
+        let instruction = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(*pool_state_pda, false),
+                AccountMeta::new(*pool_stake_pda, false),
+                AccountMeta::new(reserve_stake_pda, false),
+                AccountMeta::new_readonly(*new_validator_vote, false),
+                AccountMeta::new_readonly(CLOCK_SYSVAR, false),
+                AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false),
+                AccountMeta::new_readonly(STAKE_CONFIG, false),
+                AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+            ],
+            data: vec![10u8], // CompleteRedelegate discriminator
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&crank.pubkey()),
+            &[&crank],
+            svm.latest_blockhash(),
+        );
+
+        send_transaction(svm, transaction)
+    }
+
+    /// Helper to read (validator_vote, pending_validator_vote) from pool state
+    fn get_validator_votes(svm: &LiteSVM, pool_state_pda: &Pubkey) -> (Pubkey, Pubkey) {
+        let data = svm.get_account(pool_state_pda).unwrap().data;
+        (
+            Pubkey::new_from_array(data[65..97].try_into().unwrap()),
+            Pubkey::new_from_array(data[296..328].try_into().unwrap()),
+        )
+    }
+
+    #[test]
+    fn test_redelegate_success() {
+        let mut svm = setup_svm();
+
+        let (initializer, pool_state_pda, _, pool_stake_pda, _, old_vote, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+        let new_vote = create_vote_account(&mut svm, &Keypair::new().pubkey());
+
+        let result =
+            execute_redelegate(&mut svm, &initializer, &pool_state_pda, &pool_stake_pda, &new_vote);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Redelegate should succeed");
+
+        let (validator_vote, pending_vote) = get_validator_votes(&svm, &pool_state_pda);
+        assert_eq!(validator_vote, old_vote, "Vote should not change until completion");
+        assert_eq!(pending_vote, new_vote, "New vote should be pending");
+
+        println!("\n=== Test Passed: Redelegate Success ===");
+    }
+
+    #[test]
+    fn test_redelegate_not_authority_fails() {
+        let mut svm = setup_svm();
+
+        let (_, pool_state_pda, _, pool_stake_pda, _, _, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+        let new_vote = create_vote_account(&mut svm, &Keypair::new().pubkey());
+
+        let attacker = Keypair::new();
+        svm.airdrop(&attacker.pubkey(), 1_000_000_000).unwrap();
+
+        let result =
+            execute_redelegate(&mut svm, &attacker, &pool_state_pda, &pool_stake_pda, &new_vote);
+        assert!(result.is_err(), "Only the staker can redelegate");
+
+        println!("\n=== Test Passed: Redelegate Not Authority Fails ===");
+    }
+
+    #[test]
+    fn test_redelegate_same_validator_fails() {
+        let mut svm = setup_svm();
+
+        let (initializer, pool_state_pda, _, pool_stake_pda, _, old_vote, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+
+        let result =
+            execute_redelegate(&mut svm, &initializer, &pool_state_pda, &pool_stake_pda, &old_vote);
+        assert!(result.is_err(), "Redelegating to the current validator should fail");
+
+        println!("\n=== Test Passed: Redelegate Same Validator Fails ===");
+    }
+
+    #[test]
+    fn test_complete_redelegate_without_pending_fails() {
+        let mut svm = setup_svm();
+
+        let (_, pool_state_pda, _, pool_stake_pda, _, _, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+        let new_vote = create_vote_account(&mut svm, &Keypair::new().pubkey());
+
+        let result =
+            execute_complete_redelegate(&mut svm, &pool_state_pda, &pool_stake_pda, &new_vote);
+        assert!(result.is_err(), "CompleteRedelegate needs a pending redelegation");
+
+        println!("\n=== Test Passed: CompleteRedelegate Without Pending Fails ===");
+    }
+
+    #[test]
+    fn test_complete_redelegate_after_cooldown() {
+        let mut svm = setup_svm();
+
+        let (initializer, pool_state_pda, _, pool_stake_pda, _, _, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+        let new_vote = create_vote_account(&mut svm, &Keypair::new().pubkey());
+
+        execute_redelegate(&mut svm, &initializer, &pool_state_pda, &pool_stake_pda, &new_vote)
+            .expect("Redelegate should succeed");
+
+        let slots_per_epoch = 432_000;
+        svm.warp_to_slot(slots_per_epoch * 4);
+        advance_epoch(&mut svm);
+        advance_epoch(&mut svm);
+
+        let result =
+            execute_complete_redelegate(&mut svm, &pool_state_pda, &pool_stake_pda, &new_vote);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "CompleteRedelegate should succeed after cooldown");
+
+        let (validator_vote, pending_vote) = get_validator_votes(&svm, &pool_state_pda);
+        assert_eq!(validator_vote, new_vote, "Pool should now use the new validator");
+        assert_eq!(pending_vote, Pubkey::default(), "Pending vote should be cleared");
+
+        println!("\n=== Test Passed: CompleteRedelegate After Cooldown ===");
+    }
+}
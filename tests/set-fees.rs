@@ -0,0 +1,167 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use liquid_staking_token::{
+        PinocchioError,
+        client::{self, Pool, PoolFees},
+    };
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        account::Account,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    /// Creates a pool managed by the returned keypair
+    fn initialize_pool(svm: &mut LiteSVM) -> (Keypair, Pool) {
+        let manager = Keypair::new();
+        let lst_mint = Keypair::new();
+        svm.airdrop(&manager.pubkey(), 10_000_000_000).unwrap();
+        let validator_vote = create_vote_account(svm, &Keypair::new().pubkey());
+
+        let seed = 26u64;
+        let ix = client::initialize(
+            &manager.pubkey(),
+            &lst_mint.pubkey(),
+            &validator_vote,
+            seed,
+            PoolFees::default(),
+        );
+        send(svm, &manager, ix, &[&lst_mint]).expect("Initialize should succeed");
+
+        let pool = load_pool(svm, &client::find_pool_state_address(seed).0);
+        (manager, pool)
+    }
+
+    /// Creates an LST account owned by a fresh wallet
+    fn create_fee_account(svm: &mut LiteSVM, pool: &Pool) -> Pubkey {
+        let owner = Keypair::new();
+        svm.airdrop(&owner.pubkey(), 1_000_000_000).unwrap();
+
+        let ix = spl_associated_token_account::instruction::create_associated_token_account(
+            &owner.pubkey(),
+            &owner.pubkey(),
+            &pool.lst_mint,
+            &client::TOKEN_PROGRAM_ID,
+        );
+        send(svm, &owner, ix, &[]).expect("ATA creation should succeed");
+
+        pool.lst_token_address(&owner.pubkey())
+    }
+
+    fn fees() -> PoolFees {
+        PoolFees {
+            deposit_fee_bps: 10,
+            withdrawal_fee_bps: 20,
+            reward_fee_bps: 500,
+            sol_withdrawal_fee_bps: 40,
+        }
+    }
+
+    #[test]
+    fn test_set_fees() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+        let fee_account = create_fee_account(&mut svm, &pool);
+
+        send(
+            &mut svm,
+            &manager,
+            client::set_fees(&pool, fees(), &fee_account),
+            &[],
+        )
+        .expect("Manager should set the fees");
+
+        let pool_state =
+            client::decode_pool_state(&svm.get_account(&pool.address).unwrap().data).unwrap();
+        assert_eq!(pool_state.deposit_fee_bps(), 10);
+        assert_eq!(pool_state.withdrawal_fee_bps(), 20);
+        assert_eq!(pool_state.reward_fee_bps(), 500);
+        assert_eq!(pool_state.sol_withdrawal_fee_bps(), 40);
+        assert_eq!(
+            load_pool(&svm, &pool.address).manager_fee_account,
+            fee_account
+        );
+    }
+
+    #[test]
+    fn test_set_fees_requires_manager() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+
+        let impostor = Keypair::new();
+        svm.airdrop(&impostor.pubkey(), 1_000_000_000).unwrap();
+        let ix = client::set_fees(
+            &Pool {
+                manager: impostor.pubkey(),
+                ..pool
+            },
+            fees(),
+            &pool.manager_fee_account,
+        );
+
+        assert_eq!(
+            send(&mut svm, &impostor, ix, &[]),
+            Err(custom_error(PinocchioError::InvalidAuthority))
+        );
+    }
+
+    #[test]
+    fn test_set_fees_validates_input() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+
+        let too_high = PoolFees {
+            reward_fee_bps: 1_001,
+            ..fees()
+        };
+        assert_eq!(
+            send(
+                &mut svm,
+                &manager,
+                client::set_fees(&pool, too_high, &pool.manager_fee_account),
+                &[],
+            ),
+            Err(custom_error(PinocchioError::InvalidFee))
+        );
+
+        // A token account for some other mint
+        let other_mint_account = Keypair::new().pubkey();
+        let mut data = vec![0u8; 165];
+        data[0..32].copy_from_slice(Keypair::new().pubkey().as_ref());
+        data[32..64].copy_from_slice(manager.pubkey().as_ref());
+        data[108] = 1;
+        svm.set_account(
+            other_mint_account,
+            Account {
+                lamports: 10_000_000,
+                data,
+                owner: client::TOKEN_PROGRAM_ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            send(
+                &mut svm,
+                &manager,
+                client::set_fees(&pool, fees(), &other_mint_account),
+                &[],
+            ),
+            Err(custom_error(PinocchioError::InvalidManagerFeeAccount))
+        );
+
+        assert_eq!(
+            send(
+                &mut svm,
+                &manager,
+                client::set_fees(&pool, fees(), &pool.address),
+                &[],
+            ),
+            Err(custom_error(PinocchioError::InvalidOwner))
+        );
+    }
+}
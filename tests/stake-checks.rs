@@ -0,0 +1,143 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use liquid_staking_token::{
+        PinocchioError,
+        client::{self, Pool, PoolFees},
+    };
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        clock::Clock,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::TransactionError,
+    };
+
+    /// Creates a pool managed by the returned keypair
+    fn initialize_pool(svm: &mut LiteSVM) -> (Keypair, Pool) {
+        let manager = Keypair::new();
+        let lst_mint = Keypair::new();
+        svm.airdrop(&manager.pubkey(), 10_000_000_000).unwrap();
+        let validator_vote = create_vote_account(svm, &Keypair::new().pubkey());
+
+        let seed = 22u64;
+        let ix = client::initialize(
+            &manager.pubkey(),
+            &lst_mint.pubkey(),
+            &validator_vote,
+            seed,
+            PoolFees::default(),
+        );
+        send(svm, &manager, ix, &[&lst_mint]).expect("Initialize should succeed");
+
+        let pool = load_pool(svm, &client::find_pool_state_address(seed).0);
+        (manager, pool)
+    }
+
+    const SLOTS_PER_EPOCH: u64 = 432_000;
+
+    // Offsets into stake account data
+    const WITHDRAWER: usize = 44;
+    const CUSTODIAN: usize = 92;
+    const VOTER: usize = 124;
+
+    /// Overwrites 32 bytes of a stake account at `offset` with a new key
+    fn tamper(svm: &mut LiteSVM, address: &Pubkey, offset: usize) {
+        let mut account = svm.get_account(address).unwrap();
+        account.data[offset..offset + 32].copy_from_slice(Keypair::new().pubkey().as_ref());
+        svm.set_account(*address, account).unwrap();
+    }
+
+    fn withdraw(svm: &mut LiteSVM, pool: &Pool, user: &Keypair) -> Result<(), TransactionError> {
+        send(
+            svm,
+            user,
+            client::withdraw(pool, &user.pubkey(), 1_000_000_000, 1, 0),
+            &[],
+        )
+    }
+
+    #[test]
+    fn test_withdraw_rejects_foreign_withdrawer() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+        let user = deposit(&mut svm, &pool, 5_000_000_000);
+
+        tamper(&mut svm, &pool.pool_stake, WITHDRAWER);
+
+        let result = withdraw(&mut svm, &pool, &user);
+        assert_eq!(
+            result.unwrap_err(),
+            custom_error(PinocchioError::StakeAuthorityMismatch)
+        );
+    }
+
+    #[test]
+    fn test_withdraw_rejects_custodian() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+        let user = deposit(&mut svm, &pool, 5_000_000_000);
+
+        tamper(&mut svm, &pool.pool_stake, CUSTODIAN);
+
+        let result = withdraw(&mut svm, &pool, &user);
+        assert_eq!(
+            result.unwrap_err(),
+            custom_error(PinocchioError::StakeLockupInForce)
+        );
+    }
+
+    #[test]
+    fn test_withdraw_rejects_other_validator() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+        let user = deposit(&mut svm, &pool, 5_000_000_000);
+
+        tamper(&mut svm, &pool.pool_stake, VOTER);
+
+        let result = withdraw(&mut svm, &pool, &user);
+        assert_eq!(
+            result.unwrap_err(),
+            custom_error(PinocchioError::StakeValidatorMismatch)
+        );
+    }
+
+    #[test]
+    fn test_merge_rejects_tampered_transient() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+        deposit(&mut svm, &pool, 5_000_000_000);
+
+        let crank = Keypair::new();
+        svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
+        let transient_epoch = svm.get_sysvar::<Clock>().epoch;
+        send(
+            &mut svm,
+            &crank,
+            client::initialize_reserve(&pool, transient_epoch),
+            &[],
+        )
+        .expect("InitializeReserve should succeed");
+
+        svm.warp_to_slot(SLOTS_PER_EPOCH * 2);
+        let (transient_stake, _) = client::find_transient_stake_address(
+            &pool.address,
+            &pool.validator_vote,
+            transient_epoch,
+        );
+        tamper(&mut svm, &transient_stake, WITHDRAWER);
+
+        let result = send(
+            &mut svm,
+            &crank,
+            client::merge_reserve(&pool, transient_epoch),
+            &[],
+        );
+        assert_eq!(
+            result.unwrap_err(),
+            custom_error(PinocchioError::StakeAuthorityMismatch)
+        );
+    }
+}
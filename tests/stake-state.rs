@@ -0,0 +1,215 @@
+#[cfg(test)]
+mod tests {
+    use liquid_staking_token::{StakeActivation, StakeHistory, StakeHistoryEntry, StakeStateV2};
+
+    const SOL: u64 = 1_000_000_000;
+
+    const STAKER: [u8; 32] = [1; 32];
+    const WITHDRAWER: [u8; 32] = [2; 32];
+    const CUSTODIAN: [u8; 32] = [3; 32];
+    const VOTER: [u8; 32] = [4; 32];
+
+    /// Raw stake account data as the stake program lays it out, padded to the
+    /// 200 bytes it allocates.
+    fn stake_data(tag: u32, stake: u64, activation_epoch: u64, deactivation_epoch: u64) -> Vec<u8> {
+        let mut data = vec![0u8; 200];
+        data[0..4].copy_from_slice(&tag.to_le_bytes());
+        data[4..12].copy_from_slice(&2_282_880u64.to_le_bytes());
+        data[12..44].copy_from_slice(&STAKER);
+        data[44..76].copy_from_slice(&WITHDRAWER);
+        data[76..84].copy_from_slice(&1_700_000_000i64.to_le_bytes());
+        data[84..92].copy_from_slice(&7u64.to_le_bytes());
+        data[92..124].copy_from_slice(&CUSTODIAN);
+        data[124..156].copy_from_slice(&VOTER);
+        data[156..164].copy_from_slice(&stake.to_le_bytes());
+        data[164..172].copy_from_slice(&activation_epoch.to_le_bytes());
+        data[172..180].copy_from_slice(&deactivation_epoch.to_le_bytes());
+        data[180..188].copy_from_slice(&0.25f64.to_le_bytes());
+        data[188..196].copy_from_slice(&42u64.to_le_bytes());
+        data
+    }
+
+    /// Stake history sysvar data, entries given newest first.
+    fn history_data(entries: &[(u64, StakeHistoryEntry)]) -> Vec<u8> {
+        let mut data = (entries.len() as u64).to_le_bytes().to_vec();
+        for (epoch, entry) in entries {
+            data.extend_from_slice(&epoch.to_le_bytes());
+            data.extend_from_slice(&entry.effective.to_le_bytes());
+            data.extend_from_slice(&entry.activating.to_le_bytes());
+            data.extend_from_slice(&entry.deactivating.to_le_bytes());
+        }
+        data
+    }
+
+    fn entry(effective: u64, activating: u64, deactivating: u64) -> StakeHistoryEntry {
+        StakeHistoryEntry {
+            effective,
+            activating,
+            deactivating,
+        }
+    }
+
+    #[test]
+    fn parses_delegated_stake() {
+        let data = stake_data(StakeStateV2::STAKE, 5 * SOL, 10, u64::MAX);
+        let state = StakeStateV2::load(&data).unwrap();
+
+        assert_eq!(StakeStateV2::LEN, 197);
+        assert_eq!(state.state(), StakeStateV2::STAKE);
+
+        let meta = state.meta().unwrap();
+        assert_eq!(meta.rent_exempt_reserve(), 2_282_880);
+        assert_eq!(meta.authorized.staker, STAKER);
+        assert_eq!(meta.authorized.withdrawer, WITHDRAWER);
+        assert_eq!(meta.lockup.unix_timestamp(), 1_700_000_000);
+        assert_eq!(meta.lockup.epoch(), 7);
+        assert_eq!(meta.lockup.custodian, CUSTODIAN);
+
+        let stake = state.stake().unwrap();
+        assert_eq!(stake.credits_observed(), 42);
+
+        let delegation = state.delegation().unwrap();
+        assert_eq!(delegation.voter_pubkey, VOTER);
+        assert_eq!(delegation.stake(), 5 * SOL);
+        assert_eq!(delegation.activation_epoch(), 10);
+        assert_eq!(delegation.deactivation_epoch(), u64::MAX);
+        assert!(!delegation.is_deactivating());
+    }
+
+    #[test]
+    fn hides_fields_by_state() {
+        let data = stake_data(StakeStateV2::UNINITIALIZED, 0, 0, 0);
+        let state = StakeStateV2::load(&data).unwrap();
+        assert!(state.is_uninitialized());
+        assert!(state.meta().is_none());
+        assert!(state.delegation().is_none());
+
+        let data = stake_data(StakeStateV2::INITIALIZED, 0, 0, 0);
+        let state = StakeStateV2::load(&data).unwrap();
+        assert!(!state.is_uninitialized());
+        assert!(state.meta().is_some());
+        assert!(state.delegation().is_none());
+    }
+
+    #[test]
+    fn rejects_short_data() {
+        assert!(StakeStateV2::load(&[0u8; 196]).is_err());
+        assert!(StakeStateV2::load(&[]).is_err());
+    }
+
+    #[test]
+    fn lockup_in_force() {
+        let data = stake_data(StakeStateV2::INITIALIZED, 0, 0, 0);
+        let lockup = &StakeStateV2::load(&data).unwrap().meta().unwrap().lockup;
+
+        assert!(lockup.is_in_force(1_600_000_000, 100));
+        assert!(lockup.is_in_force(1_800_000_000, 6));
+        assert!(!lockup.is_in_force(1_800_000_000, 7));
+    }
+
+    #[test]
+    fn stake_history_lookup() {
+        let data = history_data(&[
+            (12, entry(3, 0, 0)),
+            (11, entry(2, 0, 0)),
+            (9, entry(1, 0, 0)),
+        ]);
+        let history = StakeHistory::new(&data).unwrap();
+
+        assert_eq!(history.get(12), Some(entry(3, 0, 0)));
+        assert_eq!(history.get(11), Some(entry(2, 0, 0)));
+        assert_eq!(history.get(9), Some(entry(1, 0, 0)));
+        assert_eq!(history.get(10), None);
+        assert_eq!(history.get(13), None);
+
+        assert!(StakeHistory::new(&history_data(&[])).unwrap().get(0).is_none());
+        assert!(StakeHistory::new(&data[..40]).is_err());
+        assert!(StakeHistory::new(&[]).is_err());
+    }
+
+    #[test]
+    fn activation_warmup() {
+        let data = stake_data(StakeStateV2::STAKE, 100 * SOL, 10, u64::MAX);
+        let delegation = StakeStateV2::load(&data).unwrap().delegation().unwrap();
+
+        let history = history_data(&[
+            (11, entry(1_090 * SOL, 10 * SOL, 0)),
+            (10, entry(1_000 * SOL, 100 * SOL, 0)),
+        ]);
+        let history = StakeHistory::new(&history).unwrap();
+
+        assert_eq!(delegation.activation(9, &history), StakeActivation::default());
+        assert_eq!(
+            delegation.activation(10, &history),
+            StakeActivation {
+                effective: 0,
+                activating: 100 * SOL,
+                deactivating: 0,
+            }
+        );
+
+        // The cluster lets 9% of its effective stake warm up per epoch
+        let activation = delegation.activation(11, &history);
+        assert_eq!(activation.effective, 90 * SOL);
+        assert_eq!(activation.activating, 10 * SOL);
+        assert!(!activation.is_settled());
+
+        let activation = delegation.activation(12, &history);
+        assert_eq!(activation.effective, 100 * SOL);
+        assert!(activation.is_settled());
+    }
+
+    #[test]
+    fn activation_without_history() {
+        let history = history_data(&[]);
+        let history = StakeHistory::new(&history).unwrap();
+
+        // Older than the history the sysvar keeps
+        let data = stake_data(StakeStateV2::STAKE, 100 * SOL, 10, u64::MAX);
+        let delegation = StakeStateV2::load(&data).unwrap().delegation().unwrap();
+        assert_eq!(
+            delegation.activation(900, &history),
+            StakeActivation {
+                effective: 100 * SOL,
+                activating: 0,
+                deactivating: 0,
+            }
+        );
+
+        // Bootstrap stake
+        let data = stake_data(StakeStateV2::STAKE, 100 * SOL, u64::MAX, u64::MAX);
+        let delegation = StakeStateV2::load(&data).unwrap().delegation().unwrap();
+        assert_eq!(delegation.activation(0, &history).effective, 100 * SOL);
+    }
+
+    #[test]
+    fn activation_cooldown() {
+        let data = stake_data(StakeStateV2::STAKE, 100 * SOL, 0, 20);
+        let delegation = StakeStateV2::load(&data).unwrap().delegation().unwrap();
+        assert!(delegation.is_deactivating());
+
+        let history = history_data(&[
+            (21, entry(910 * SOL, 0, 10 * SOL)),
+            (20, entry(1_000 * SOL, 0, 100 * SOL)),
+        ]);
+        let history = StakeHistory::new(&history).unwrap();
+
+        assert_eq!(
+            delegation.activation(20, &history),
+            StakeActivation {
+                effective: 100 * SOL,
+                activating: 0,
+                deactivating: 100 * SOL,
+            }
+        );
+        assert_eq!(
+            delegation.activation(21, &history),
+            StakeActivation {
+                effective: 10 * SOL,
+                activating: 0,
+                deactivating: 10 * SOL,
+            }
+        );
+        assert_eq!(delegation.activation(22, &history), StakeActivation::default());
+    }
+}
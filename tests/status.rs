@@ -0,0 +1,208 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use liquid_staking_token::{
+        PAUSE_ALL, PAUSE_CRANKS, PAUSE_DEPOSITS, PAUSE_WITHDRAWALS, PinocchioError,
+        client::{self, Pool, PoolFees},
+    };
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        clock::Clock,
+        signature::{Keypair, Signer},
+    };
+
+    /// Creates a pool managed by the returned keypair
+    fn initialize_pool(svm: &mut LiteSVM) -> (Keypair, Pool) {
+        let manager = Keypair::new();
+        let lst_mint = Keypair::new();
+        svm.airdrop(&manager.pubkey(), 10_000_000_000).unwrap();
+        let validator_vote = create_vote_account(svm, &Keypair::new().pubkey());
+
+        let seed = 88u64;
+        let ix = client::initialize(
+            &manager.pubkey(),
+            &lst_mint.pubkey(),
+            &validator_vote,
+            seed,
+            PoolFees::default(),
+        );
+        send(svm, &manager, ix, &[&lst_mint]).expect("Initialize should succeed");
+
+        let pool = load_pool(svm, &client::find_pool_state_address(seed).0);
+        (manager, pool)
+    }
+
+    fn status(svm: &LiteSVM, pool: &Pool) -> u8 {
+        client::decode_pool_state(&svm.get_account(&pool.address).unwrap().data)
+            .expect("Pool state should decode")
+            .status()
+    }
+
+    #[test]
+    fn test_pause_deposits() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+
+        send(
+            &mut svm,
+            &manager,
+            client::set_status(&pool, PAUSE_DEPOSITS),
+            &[],
+        )
+        .expect("SetStatus should succeed");
+        assert_eq!(status(&svm, &pool), PAUSE_DEPOSITS);
+
+        let user = Keypair::new();
+        svm.airdrop(&user.pubkey(), 3_000_000_000).unwrap();
+        let result = send(
+            &mut svm,
+            &user,
+            client::deposit(&pool, &user.pubkey(), 2_000_000_000, 0),
+            &[],
+        );
+        assert_eq!(result, Err(custom_error(PinocchioError::DepositsPaused)));
+
+        // Resuming lets deposits through again
+        send(&mut svm, &manager, client::set_status(&pool, 0), &[])
+            .expect("SetStatus should succeed");
+        deposit(&mut svm, &pool, 2_000_000_000);
+    }
+
+    #[test]
+    fn test_pause_withdrawals_keeps_deposits_open() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+
+        send(
+            &mut svm,
+            &manager,
+            client::set_status(&pool, PAUSE_WITHDRAWALS),
+            &[],
+        )
+        .expect("SetStatus should succeed");
+
+        let user = deposit(&mut svm, &pool, 3_000_000_000);
+
+        let result = send(
+            &mut svm,
+            &user,
+            client::withdraw_sol(&pool, &user.pubkey(), 1_000_000_000, 0),
+            &[],
+        );
+        assert_eq!(result, Err(custom_error(PinocchioError::WithdrawalsPaused)));
+
+        let result = send(
+            &mut svm,
+            &user,
+            client::withdraw(&pool, &user.pubkey(), 1_000_000_000, 0, 0),
+            &[],
+        );
+        assert_eq!(result, Err(custom_error(PinocchioError::WithdrawalsPaused)));
+    }
+
+    #[test]
+    fn test_pause_cranks() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+        deposit(&mut svm, &pool, 3_000_000_000);
+
+        send(
+            &mut svm,
+            &manager,
+            client::set_status(&pool, PAUSE_CRANKS),
+            &[],
+        )
+        .expect("SetStatus should succeed");
+
+        let crank = Keypair::new();
+        svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
+        let epoch = svm.get_sysvar::<Clock>().epoch;
+        let result = send(
+            &mut svm,
+            &crank,
+            client::initialize_reserve(&pool, epoch),
+            &[],
+        );
+        assert_eq!(result, Err(custom_error(PinocchioError::CranksPaused)));
+    }
+
+    #[test]
+    fn test_withdraw_complete_stays_open_when_paused() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+        let user = deposit(&mut svm, &pool, 10_000_000_000);
+
+        // Move the deposit into the pool stake so it can be withdrawn as stake
+        let crank = Keypair::new();
+        svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
+        let transient_epoch = svm.get_sysvar::<Clock>().epoch;
+        send(
+            &mut svm,
+            &crank,
+            client::initialize_reserve(&pool, transient_epoch),
+            &[],
+        )
+        .expect("InitializeReserve should succeed");
+
+        let slots_per_epoch = 432_000;
+        svm.warp_to_slot(slots_per_epoch * 2);
+        send(
+            &mut svm,
+            &crank,
+            client::merge_reserve(&pool, transient_epoch),
+            &[],
+        )
+        .expect("MergeReserve should succeed");
+
+        let nonce = 1;
+        send(
+            &mut svm,
+            &user,
+            client::withdraw(&pool, &user.pubkey(), 5_000_000_000, nonce, 0),
+            &[],
+        )
+        .expect("Withdraw should succeed");
+
+        send(
+            &mut svm,
+            &manager,
+            client::set_status(&pool, PAUSE_ALL),
+            &[],
+        )
+        .expect("SetStatus should succeed");
+
+        // Users are never trapped: deactivating stake can still be claimed
+        svm.warp_to_slot(slots_per_epoch * 4);
+        send(
+            &mut svm,
+            &user,
+            client::withdraw_complete(&pool, &user.pubkey(), nonce),
+            &[],
+        )
+        .expect("WithdrawComplete should succeed while paused");
+    }
+
+    #[test]
+    fn test_set_status_rejects_unknown_bits_and_non_manager() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_pool(&mut svm);
+
+        let result = send(&mut svm, &manager, client::set_status(&pool, 1 << 7), &[]);
+        assert_eq!(result, Err(custom_error(PinocchioError::InvalidStatus)));
+
+        let attacker = Keypair::new();
+        svm.airdrop(&attacker.pubkey(), 1_000_000_000).unwrap();
+        let mut forged = pool;
+        forged.manager = attacker.pubkey();
+        let result = send(
+            &mut svm,
+            &attacker,
+            client::set_status(&forged, PAUSE_ALL),
+            &[],
+        );
+        assert_eq!(result, Err(custom_error(PinocchioError::InvalidAuthority)));
+        assert_eq!(status(&svm, &pool), 0);
+    }
+}
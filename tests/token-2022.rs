@@ -0,0 +1,130 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use liquid_staking_token::{
+        MIN_STAKE_DELEGATION, PinocchioError,
+        client::{self, Pool, PoolFees},
+    };
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    /// Creates a pool whose LST mint is owned by Token-2022
+    fn initialize_token_2022_pool(svm: &mut LiteSVM) -> (Keypair, Pool) {
+        let manager = Keypair::new();
+        let lst_mint = Keypair::new();
+        svm.airdrop(&manager.pubkey(), 10_000_000_000).unwrap();
+        let validator_vote = create_vote_account(svm, &Keypair::new().pubkey());
+
+        let seed = 2022u64;
+        let ix = client::initialize_with_token_program(
+            &manager.pubkey(),
+            &lst_mint.pubkey(),
+            &validator_vote,
+            seed,
+            PoolFees::default(),
+            &client::TOKEN_2022_PROGRAM_ID,
+            None,
+        );
+        send(svm, &manager, ix, &[&lst_mint]).expect("Initialize should succeed");
+
+        let pool = load_pool(svm, &client::find_pool_state_address(seed).0);
+        (manager, pool)
+    }
+
+    /// Token amount of an SPL Token or Token-2022 account
+    fn token_balance(svm: &LiteSVM, account: &Pubkey) -> u64 {
+        let data = svm.get_account(account).unwrap().data;
+        u64::from_le_bytes(data[64..72].try_into().unwrap())
+    }
+
+    /// Funds a user and creates their Token-2022 LST account
+    fn create_user(svm: &mut LiteSVM, pool: &Pool, lamports: u64) -> Keypair {
+        let user = Keypair::new();
+        svm.airdrop(&user.pubkey(), lamports).unwrap();
+
+        let create_ata_ix =
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &user.pubkey(),
+                &user.pubkey(),
+                &pool.lst_mint,
+                &pool.token_program,
+            );
+        send(svm, &user, create_ata_ix, &[]).expect("ATA creation should succeed");
+
+        user
+    }
+
+    #[test]
+    fn test_initialize_with_token_2022() {
+        let mut svm = setup_svm();
+        let (manager, pool) = initialize_token_2022_pool(&mut svm);
+
+        assert_eq!(pool.token_program, client::TOKEN_2022_PROGRAM_ID);
+        assert_eq!(
+            svm.get_account(&pool.lst_mint).unwrap().owner,
+            client::TOKEN_2022_PROGRAM_ID
+        );
+
+        let manager_lst = pool.lst_token_address(&manager.pubkey());
+        assert_eq!(pool.manager_fee_account, manager_lst);
+        assert_eq!(token_balance(&svm, &manager_lst), MIN_STAKE_DELEGATION);
+    }
+
+    #[test]
+    fn test_deposit_and_withdraw_sol_with_token_2022() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_token_2022_pool(&mut svm);
+
+        let user = create_user(&mut svm, &pool, 3_000_000_000);
+        let user_lst = pool.lst_token_address(&user.pubkey());
+
+        send(
+            &mut svm,
+            &user,
+            client::deposit(&pool, &user.pubkey(), 1_000_000_000, 0),
+            &[],
+        )
+        .expect("Deposit should succeed");
+        let minted = token_balance(&svm, &user_lst);
+        assert!(minted > 0, "Deposit should mint Token-2022 LST");
+
+        send(
+            &mut svm,
+            &user,
+            client::withdraw_sol(&pool, &user.pubkey(), minted / 2, 0),
+            &[],
+        )
+        .expect("WithdrawSol should succeed");
+        assert_eq!(token_balance(&svm, &user_lst), minted - minted / 2);
+    }
+
+    #[test]
+    fn test_token_program_must_match_pool() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_token_2022_pool(&mut svm);
+        let user = create_user(&mut svm, &pool, 2_000_000_000);
+
+        // The legacy program does not own this pool's mint
+        let legacy = Pool {
+            token_program: client::TOKEN_PROGRAM_ID,
+            ..pool
+        };
+        let mut ix = client::deposit(&legacy, &user.pubkey(), 1_000_000_000, 0);
+        let user_lst_index = ix
+            .accounts
+            .iter()
+            .position(|meta| meta.pubkey == legacy.lst_token_address(&user.pubkey()))
+            .unwrap();
+        ix.accounts[user_lst_index].pubkey = pool.lst_token_address(&user.pubkey());
+
+        assert_eq!(
+            send(&mut svm, &user, ix, &[]),
+            Err(custom_error(PinocchioError::InvalidOwner))
+        );
+    }
+}
@@ -0,0 +1,282 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use solana_sdk::{
+        clock::Clock,
+        signature::{Keypair, Signer},
+    };
+
+    #[test]
+    fn test_initialize_caches_pool_balance() {
+        let mut svm = setup_svm();
+
+        let (_, pool_state_pda, _, pool_stake_pda, reserve_stake_pda, _, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+
+        let (total_lamports, last_update_epoch) = get_cached_balance(&svm, &pool_state_pda);
+        let actual_total = svm.get_account(&pool_stake_pda).unwrap().lamports
+            + svm.get_account(&reserve_stake_pda).unwrap().lamports;
+
+        assert_eq!(total_lamports, actual_total, "Initialize should cache the pool value");
+        assert_eq!(
+            last_update_epoch,
+            svm.get_sysvar::<Clock>().epoch,
+            "Initialize should record the current epoch"
+        );
+
+        println!("\n=== Test Passed: Initialize Caches Pool Balance ===");
+    }
+
+    #[test]
+    fn test_update_pool_balance_success() {
+        let mut svm = setup_svm();
+
+        let (_, pool_state_pda, lst_mint_pda, pool_stake_pda, reserve_stake_pda, _, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+
+        let reward_amount = 500_000_000u64;
+        add_rewards(&mut svm, &pool_stake_pda, reward_amount);
+        let (total_before, _) = get_cached_balance(&svm, &pool_state_pda);
+
+        advance_epoch(&mut svm);
+        let result = execute_update_pool_balance(
+            &mut svm,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "UpdatePoolBalance should succeed");
+
+        let (total_after, last_update_epoch) = get_cached_balance(&svm, &pool_state_pda);
+        assert_eq!(
+            total_after,
+            total_before + reward_amount,
+            "Cached total should include rewards"
+        );
+        assert_eq!(last_update_epoch, svm.get_sysvar::<Clock>().epoch);
+
+        println!("\n=== Test Passed: UpdatePoolBalance Success ===");
+    }
+
+    #[test]
+    fn test_update_pool_balance_twice_in_epoch_fails() {
+        let mut svm = setup_svm();
+
+        let (_, pool_state_pda, lst_mint_pda, pool_stake_pda, reserve_stake_pda, _, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+
+        // Initialize already recorded the current epoch
+        let result = execute_update_pool_balance(
+            &mut svm,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_err(), "Update in the initialize epoch should fail");
+
+        advance_epoch(&mut svm);
+        let result = execute_update_pool_balance(
+            &mut svm,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+        );
+        assert!(result.is_ok(), "First update in a new epoch should succeed");
+
+        let result = execute_update_pool_balance(
+            &mut svm,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_err(), "Second update in the same epoch should fail");
+
+        println!("\n=== Test Passed: Only One Update Per Epoch ===");
+    }
+
+    #[test]
+    fn test_update_pool_balance_mints_reward_fee() {
+        let mut svm = setup_svm();
+
+        // 10% reward fee
+        let reward_fee_bps = 1_000u16;
+        let (_, pool_state_pda, lst_mint_pda, pool_stake_pda, reserve_stake_pda, _, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, reward_fee_bps, 0);
+
+        let manager_fee_account = get_manager_fee_account(&svm, &pool_state_pda);
+        let manager_before =
+            get_token_balance(&svm.get_account(&manager_fee_account).unwrap().data);
+        let supply_before = get_mint_supply(&svm.get_account(&lst_mint_pda).unwrap().data);
+
+        let reward_amount = 1_000_000_000u64;
+        add_rewards(&mut svm, &pool_stake_pda, reward_amount);
+
+        advance_epoch(&mut svm);
+        let result = execute_update_pool_balance(
+            &mut svm,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "UpdatePoolBalance should succeed");
+
+        let (total_after, _) = get_cached_balance(&svm, &pool_state_pda);
+        let fee_lamports = reward_amount * reward_fee_bps as u64 / 10_000;
+        let expected_fee_lst = (fee_lamports as u128 * supply_before as u128
+            / (total_after - fee_lamports) as u128) as u64;
+
+        let manager_after =
+            get_token_balance(&svm.get_account(&manager_fee_account).unwrap().data);
+        assert_eq!(
+            manager_after - manager_before,
+            expected_fee_lst,
+            "Manager should receive LST worth the reward fee"
+        );
+
+        // Manager's new LST is worth ~fee_lamports at the post-update rate
+        let supply_after = get_mint_supply(&svm.get_account(&lst_mint_pda).unwrap().data);
+        let fee_value = expected_fee_lst as u128 * total_after as u128 / supply_after as u128;
+        assert!(
+            (fee_value as i128 - fee_lamports as i128).abs() <= 1,
+            "Minted fee should be worth the fee lamports. Expected {}, got {}",
+            fee_lamports,
+            fee_value
+        );
+
+        println!("\n=== Test Passed: Reward Fee Minted ===");
+    }
+
+    #[test]
+    fn test_update_pool_balance_ignores_donations() {
+        let mut svm = setup_svm();
+
+        let (_, pool_state_pda, lst_mint_pda, pool_stake_pda, reserve_stake_pda, _, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 1_000, 0);
+
+        let (total_before, _) = get_cached_balance(&svm, &pool_state_pda);
+        let supply_before = get_mint_supply(&svm.get_account(&lst_mint_pda).unwrap().data);
+
+        // Lamports sent straight to the pool's accounts are neither delegated nor tracked
+        add_lamports(&mut svm, &pool_stake_pda, 2_000_000_000);
+        add_lamports(&mut svm, &reserve_stake_pda, 3_000_000_000);
+
+        advance_epoch(&mut svm);
+        let result = execute_update_pool_balance(
+            &mut svm,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "UpdatePoolBalance should succeed");
+
+        let (total_after, _) = get_cached_balance(&svm, &pool_state_pda);
+        assert_eq!(total_after, total_before, "Donations should not move the rate");
+
+        let supply_after = get_mint_supply(&svm.get_account(&lst_mint_pda).unwrap().data);
+        assert_eq!(supply_after, supply_before, "Donations should not be charged the reward fee");
+
+        println!("\n=== Test Passed: Donations Ignored By Update ===");
+    }
+
+    #[test]
+    fn test_deposit_with_stale_balance_fails() {
+        let mut svm = setup_svm();
+
+        let (_, pool_state_pda, lst_mint_pda, pool_stake_pda, reserve_stake_pda, _, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+
+        let depositor = Keypair::new();
+        svm.airdrop(&depositor.pubkey(), 5_000_000_000).unwrap();
+        let depositor_lst_ata = create_depositor_ata(&mut svm, &depositor, &lst_mint_pda);
+
+        advance_epoch(&mut svm);
+
+        let result = execute_deposit(
+            &mut svm,
+            &depositor,
+            &pool_state_pda,
+            &lst_mint_pda,
+            &depositor_lst_ata,
+            1_200_000_000,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_err(), "Deposit against a stale balance should fail");
+
+        let result = execute_update_pool_balance(
+            &mut svm,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+        );
+        assert!(result.is_ok(), "UpdatePoolBalance should succeed");
+
+        let result = execute_deposit(
+            &mut svm,
+            &depositor,
+            &pool_state_pda,
+            &lst_mint_pda,
+            &depositor_lst_ata,
+            1_200_000_000,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Deposit after update should succeed");
+
+        println!("\n=== Test Passed: Stale Balance Rejected ===");
+    }
+
+    #[test]
+    fn test_donation_does_not_move_exchange_rate() {
+        let mut svm = setup_svm();
+
+        let (_, pool_state_pda, lst_mint_pda, _, reserve_stake_pda, _, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+
+        let (total_cached, _) = get_cached_balance(&svm, &pool_state_pda);
+        let supply = get_mint_supply(&svm.get_account(&lst_mint_pda).unwrap().data);
+
+        // Attacker donates lamports straight into the reserve
+        add_lamports(&mut svm, &reserve_stake_pda, 5_000_000_000);
+
+        let depositor = Keypair::new();
+        let deposit_amount = 1_200_000_000u64;
+        svm.airdrop(&depositor.pubkey(), 5_000_000_000).unwrap();
+        let depositor_lst_ata = create_depositor_ata(&mut svm, &depositor, &lst_mint_pda);
+
+        let result = execute_deposit(
+            &mut svm,
+            &depositor,
+            &pool_state_pda,
+            &lst_mint_pda,
+            &depositor_lst_ata,
+            deposit_amount,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Deposit should succeed");
+
+        // Rate comes from the cached balance, not the inflated lamports
+        let expected_lst =
+            (deposit_amount as u128 * supply as u128 / total_cached as u128) as u64;
+        let depositor_balance =
+            get_token_balance(&svm.get_account(&depositor_lst_ata).unwrap().data);
+        assert_eq!(
+            depositor_balance, expected_lst,
+            "Donation should not affect the deposit rate"
+        );
+
+        println!("\n=== Test Passed: Donation Ignored Until Next Update ===");
+    }
+}
@@ -0,0 +1,566 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use liquid_staking_token::{
+        PoolState,
+        client::{self, Pool},
+    };
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        clock::Clock,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+    use spl_token::ID as TOKEN_PROGRAM_ID;
+
+    /// Helper to execute AddValidator signed by `authority`
+    fn execute_add_validator(
+        svm: &mut LiteSVM,
+        authority: &Keypair,
+        pool_state_pda: &Pubkey,
+        lst_mint_pda: &Pubkey,
+        validator_vote: &Pubkey,
+    ) -> TransactionResult {
+        let (validator_list_pda, _) = derive_validator_list_pda(pool_state_pda);
+        let (validator_stake_pda, _) = derive_validator_stake_pda(pool_state_pda, validator_vote);
+
+        let instruction = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(authority.pubkey(), true), // authority
+                AccountMeta::new(get_manager_fee_account(svm, pool_state_pda), false), // authority_lst_account
+                AccountMeta::new(*pool_state_pda, false),  // pool_state
+                AccountMeta::new(validator_list_pda, false), // validator_list
+                AccountMeta::new(validator_stake_pda, false), // validator_stake
+                AccountMeta::new_readonly(*validator_vote, false), // validator_vote
+                AccountMeta::new(*lst_mint_pda, false),    // lst_mint
+                AccountMeta::new_readonly(CLOCK_SYSVAR, false), // clock
+                AccountMeta::new_readonly(RENT_SYSVAR, false), // rent
+                AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false), // stake_history
+                AccountMeta::new_readonly(STAKE_CONFIG, false), // stake_config
+                AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false), // system_program
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false), // token_program
+                AccountMeta::new_readonly(STAKE_PROGRAM_ID, false), // stake_program
+            ],
+            data: vec![7u8], // AddValidator discriminator
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&authority.pubkey()),
+            &[authority],
+            svm.latest_blockhash(),
+        );
+
+        send_transaction(svm, transaction)
+    }
+
+    /// Helper to execute RemoveValidator signed by `authority`
+    fn execute_remove_validator(
+        svm: &mut LiteSVM,
+        authority: &Keypair,
+        pool_state_pda: &Pubkey,
+        reserve_stake_pda: &Pubkey,
+        validator_vote: &Pubkey,
+    ) -> TransactionResult {
+        let (validator_list_pda, _) = derive_validator_list_pda(pool_state_pda);
+        let (validator_stake_pda, _) = derive_validator_stake_pda(pool_state_pda, validator_vote);
+
+        let instruction = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(authority.pubkey(), true),
+                AccountMeta::new_readonly(*pool_state_pda, false),
+                AccountMeta::new(validator_list_pda, false),
+                AccountMeta::new(validator_stake_pda, false),
+                AccountMeta::new(*reserve_stake_pda, false),
+                AccountMeta::new_readonly(CLOCK_SYSVAR, false),
+                AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false),
+                AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+            ],
+            data: vec![8u8], // RemoveValidator discriminator
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&authority.pubkey()),
+            &[authority],
+            svm.latest_blockhash(),
+        );
+
+        send_transaction(svm, transaction)
+    }
+
+    /// (vote, stake, active_lamports, status, transient_lamports)
+    type ValidatorEntry = (Pubkey, Pubkey, u64, u8, u64);
+
+    /// Helper to read (count, entries) from the validator list
+    fn get_validator_list(svm: &LiteSVM, validator_list_pda: &Pubkey) -> (u32, Vec<ValidatorEntry>) {
+        let data = svm.get_account(validator_list_pda).unwrap().data;
+        let count = u32::from_le_bytes(data[4..8].try_into().unwrap());
+
+        let entries = data[40..]
+            .chunks_exact(96)
+            .map(|entry| {
+                (
+                    Pubkey::new_from_array(entry[0..32].try_into().unwrap()),
+                    Pubkey::new_from_array(entry[32..64].try_into().unwrap()),
+                    u64::from_le_bytes(entry[64..72].try_into().unwrap()),
+                    entry[88],
+                    u64::from_le_bytes(entry[72..80].try_into().unwrap()),
+                )
+            })
+            .collect();
+
+        (count, entries)
+    }
+
+    /// Deposits normally fill the reserve; credit it directly instead
+    fn fund_reserve(
+        svm: &mut LiteSVM,
+        pool_state_pda: &Pubkey,
+        reserve_stake_pda: &Pubkey,
+        amount: u64,
+    ) {
+        svm.airdrop(reserve_stake_pda, amount).unwrap();
+        let mut account = svm.get_account(pool_state_pda).unwrap();
+        PoolState::load_mut(&mut account.data)
+            .unwrap()
+            .credit_reserve(amount)
+            .unwrap();
+        svm.set_account(*pool_state_pda, account).unwrap();
+    }
+
+    /// Helper to send a client instruction signed by `signer`
+    fn send_instruction(
+        svm: &mut LiteSVM,
+        signer: &Keypair,
+        instruction: Instruction,
+    ) -> TransactionResult {
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&signer.pubkey()),
+            &[signer],
+            svm.latest_blockhash(),
+        );
+        let result = send_transaction(svm, transaction);
+        print_transaction_logs(&result);
+        result
+    }
+
+    #[test]
+    fn test_add_validator_success() {
+        let mut svm = setup_svm();
+
+        let (initializer, pool_state_pda, lst_mint_pda, pool_stake_pda, _, primary_vote, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+        svm.airdrop(&initializer.pubkey(), 5_000_000_000).unwrap();
+
+        let new_vote = create_vote_account(&mut svm, &Keypair::new().pubkey());
+        let authority_lst = get_manager_fee_account(&svm, &pool_state_pda);
+        let lst_before =
+            get_token_balance(&svm.get_account(&authority_lst).unwrap().data);
+        let (total_before, _) = get_cached_balance(&svm, &pool_state_pda);
+
+        let result = execute_add_validator(
+            &mut svm,
+            &initializer,
+            &pool_state_pda,
+            &lst_mint_pda,
+            &new_vote,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "AddValidator should succeed");
+
+        let (validator_list_pda, _) = derive_validator_list_pda(&pool_state_pda);
+        let (validator_stake_pda, _) = derive_validator_stake_pda(&pool_state_pda, &new_vote);
+        let (count, entries) = get_validator_list(&svm, &validator_list_pda);
+
+        assert_eq!(count, 2, "List should hold the primary and the new validator");
+        assert_eq!(entries[0].0, primary_vote);
+        assert_eq!(entries[0].1, pool_stake_pda);
+        assert_eq!(entries[1].0, new_vote);
+        assert_eq!(entries[1].1, validator_stake_pda);
+        assert_eq!(entries[1].3, 0, "New validator should be active");
+
+        let stake_lamports = svm.get_account(&validator_stake_pda).unwrap().lamports;
+        let (total_after, _) = get_cached_balance(&svm, &pool_state_pda);
+        assert_eq!(total_after, total_before + stake_lamports);
+
+        let lst_after = get_token_balance(&svm.get_account(&authority_lst).unwrap().data);
+        assert!(lst_after > lst_before, "Authority should be paid LST for the new stake");
+
+        println!("\n=== Test Passed: AddValidator Success ===");
+    }
+
+    #[test]
+    fn test_add_second_validator_grows_list() {
+        let mut svm = setup_svm();
+
+        let (initializer, pool_state_pda, lst_mint_pda, _, _, _, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+        svm.airdrop(&initializer.pubkey(), 10_000_000_000).unwrap();
+
+        let vote_a = create_vote_account(&mut svm, &Keypair::new().pubkey());
+        let vote_b = create_vote_account(&mut svm, &Keypair::new().pubkey());
+
+        execute_add_validator(&mut svm, &initializer, &pool_state_pda, &lst_mint_pda, &vote_a)
+            .expect("First AddValidator should succeed");
+        let result =
+            execute_add_validator(&mut svm, &initializer, &pool_state_pda, &lst_mint_pda, &vote_b);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Second AddValidator should succeed");
+
+        let (validator_list_pda, _) = derive_validator_list_pda(&pool_state_pda);
+        let (count, entries) = get_validator_list(&svm, &validator_list_pda);
+        assert_eq!(count, 3);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[2].0, vote_b);
+
+        println!("\n=== Test Passed: Second Validator Grows List ===");
+    }
+
+    #[test]
+    fn test_add_validator_not_authority_fails() {
+        let mut svm = setup_svm();
+
+        let (_, pool_state_pda, lst_mint_pda, _, _, _, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+
+        let attacker = Keypair::new();
+        svm.airdrop(&attacker.pubkey(), 5_000_000_000).unwrap();
+        let new_vote = create_vote_account(&mut svm, &Keypair::new().pubkey());
+
+        let result =
+            execute_add_validator(&mut svm, &attacker, &pool_state_pda, &lst_mint_pda, &new_vote);
+        assert!(result.is_err(), "Only the staker can add validators");
+
+        println!("\n=== Test Passed: AddValidator Not Authority Fails ===");
+    }
+
+    #[test]
+    fn test_add_duplicate_validator_fails() {
+        let mut svm = setup_svm();
+
+        let (initializer, pool_state_pda, lst_mint_pda, _, _, primary_vote, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+        svm.airdrop(&initializer.pubkey(), 5_000_000_000).unwrap();
+
+        let result = execute_add_validator(
+            &mut svm,
+            &initializer,
+            &pool_state_pda,
+            &lst_mint_pda,
+            &primary_vote,
+        );
+        assert!(result.is_err(), "Primary validator cannot be added again");
+
+        println!("\n=== Test Passed: Duplicate Validator Fails ===");
+    }
+
+    #[test]
+    fn test_update_pool_balance_counts_validator_stakes() {
+        let mut svm = setup_svm();
+
+        let (initializer, pool_state_pda, lst_mint_pda, pool_stake_pda, reserve_stake_pda, _, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+        svm.airdrop(&initializer.pubkey(), 5_000_000_000).unwrap();
+
+        let new_vote = create_vote_account(&mut svm, &Keypair::new().pubkey());
+        execute_add_validator(&mut svm, &initializer, &pool_state_pda, &lst_mint_pda, &new_vote)
+            .expect("AddValidator should succeed");
+
+        let (validator_list_pda, _) = derive_validator_list_pda(&pool_state_pda);
+        let (validator_stake_pda, _) = derive_validator_stake_pda(&pool_state_pda, &new_vote);
+
+        let reward_amount = 300_000_000u64;
+        add_rewards(&mut svm, &validator_stake_pda, reward_amount);
+        advance_epoch(&mut svm);
+
+        let result = execute_update_pool_balance_with_validators(
+            &mut svm,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+            None,
+            &[],
+        );
+        assert!(result.is_err(), "Crank must include the validator list");
+
+        let result = execute_update_pool_balance_with_validators(
+            &mut svm,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+            Some(&validator_list_pda),
+            &[validator_stake_pda],
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "UpdatePoolBalance should succeed");
+
+        let pool_stake_lamports = svm.get_account(&pool_stake_pda).unwrap().lamports;
+        let reserve_lamports = svm.get_account(&reserve_stake_pda).unwrap().lamports;
+        let validator_lamports = svm.get_account(&validator_stake_pda).unwrap().lamports;
+        let (total_lamports, _) = get_cached_balance(&svm, &pool_state_pda);
+        assert_eq!(
+            total_lamports,
+            pool_stake_lamports + reserve_lamports + validator_lamports,
+            "Cached total should include every validator stake"
+        );
+
+        let (_, entries) = get_validator_list(&svm, &validator_list_pda);
+        assert_eq!(entries[0].2, pool_stake_lamports);
+        assert_eq!(entries[1].2, validator_lamports);
+
+        println!("\n=== Test Passed: UpdatePoolBalance Counts Validator Stakes ===");
+    }
+
+    #[test]
+    fn test_remove_primary_validator_fails() {
+        let mut svm = setup_svm();
+
+        let (initializer, pool_state_pda, lst_mint_pda, _, reserve_stake_pda, primary_vote, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+        svm.airdrop(&initializer.pubkey(), 5_000_000_000).unwrap();
+
+        let new_vote = create_vote_account(&mut svm, &Keypair::new().pubkey());
+        execute_add_validator(&mut svm, &initializer, &pool_state_pda, &lst_mint_pda, &new_vote)
+            .expect("AddValidator should succeed");
+
+        let result = execute_remove_validator(
+            &mut svm,
+            &initializer,
+            &pool_state_pda,
+            &reserve_stake_pda,
+            &primary_vote,
+        );
+        assert!(result.is_err(), "Primary validator cannot be removed");
+
+        println!("\n=== Test Passed: Remove Primary Validator Fails ===");
+    }
+
+    #[test]
+    fn test_remove_validator_two_phase() {
+        let mut svm = setup_svm();
+
+        let (initializer, pool_state_pda, lst_mint_pda, _, reserve_stake_pda, _, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+        svm.airdrop(&initializer.pubkey(), 5_000_000_000).unwrap();
+
+        let new_vote = create_vote_account(&mut svm, &Keypair::new().pubkey());
+        execute_add_validator(&mut svm, &initializer, &pool_state_pda, &lst_mint_pda, &new_vote)
+            .expect("AddValidator should succeed");
+
+        let (validator_list_pda, _) = derive_validator_list_pda(&pool_state_pda);
+        let (validator_stake_pda, _) = derive_validator_stake_pda(&pool_state_pda, &new_vote);
+        let stake_lamports = svm.get_account(&validator_stake_pda).unwrap().lamports;
+        let reserve_before = svm.get_account(&reserve_stake_pda).unwrap().lamports;
+
+        // Phase 1: deactivate
+        let result = execute_remove_validator(
+            &mut svm,
+            &initializer,
+            &pool_state_pda,
+            &reserve_stake_pda,
+            &new_vote,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "RemoveValidator should deactivate the stake");
+
+        let (_, entries) = get_validator_list(&svm, &validator_list_pda);
+        assert_eq!(entries[1].3, 1, "Validator should be deactivating");
+
+        // Phase 2: after cooldown, lamports move to the reserve
+        let slots_per_epoch = 432_000;
+        svm.warp_to_slot(slots_per_epoch * 4);
+        advance_epoch(&mut svm);
+        advance_epoch(&mut svm);
+
+        let result = execute_remove_validator(
+            &mut svm,
+            &initializer,
+            &pool_state_pda,
+            &reserve_stake_pda,
+            &new_vote,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "RemoveValidator should complete after cooldown");
+
+        let (count, entries) = get_validator_list(&svm, &validator_list_pda);
+        assert_eq!(count, 1, "Only the primary validator should remain");
+        assert_eq!(entries.len(), 1);
+
+        let reserve_after = svm.get_account(&reserve_stake_pda).unwrap().lamports;
+        assert_eq!(reserve_after, reserve_before + stake_lamports);
+
+        println!("\n=== Test Passed: RemoveValidator Two Phase ===");
+    }
+
+    #[test]
+    fn test_increase_validator_stake_merges_into_validator() {
+        let mut svm = setup_svm();
+
+        let (initializer, pool_state_pda, lst_mint_pda, _, reserve_stake_pda, _, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+        svm.airdrop(&initializer.pubkey(), 5_000_000_000).unwrap();
+
+        let new_vote = create_vote_account(&mut svm, &Keypair::new().pubkey());
+        execute_add_validator(&mut svm, &initializer, &pool_state_pda, &lst_mint_pda, &new_vote)
+            .expect("AddValidator should succeed");
+
+        let amount = 3_000_000_000u64;
+        fund_reserve(&mut svm, &pool_state_pda, &reserve_stake_pda, amount);
+
+        let pool = Pool::from_account_data(
+            pool_state_pda,
+            &svm.get_account(&pool_state_pda).unwrap().data,
+        )
+        .unwrap();
+        let (validator_stake_pda, _) = derive_validator_stake_pda(&pool_state_pda, &new_vote);
+        let validator_before = svm.get_account(&validator_stake_pda).unwrap().lamports;
+
+        let transient_epoch = svm.get_sysvar::<Clock>().epoch;
+        let transaction = Transaction::new_signed_with_payer(
+            &[client::increase_validator_stake(
+                &pool,
+                &new_vote,
+                amount,
+                transient_epoch,
+            )],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+        let result = send_transaction(&mut svm, transaction);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "IncreaseValidatorStake should succeed");
+
+        let data = svm.get_account(&pool_state_pda).unwrap().data;
+        assert_eq!(PoolState::load(&data).unwrap().transient_lamports, amount);
+        let (validator_list_pda, _) = derive_validator_list_pda(&pool_state_pda);
+        let (_, entries) = get_validator_list(&svm, &validator_list_pda);
+        assert_eq!(entries[1].4, amount, "The validator tracks its transient stake");
+
+        // The transient stake is fully active two epochs later
+        let slots_per_epoch = 432_000;
+        svm.warp_to_slot(slots_per_epoch * 2);
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[client::merge_reserve_into_validator(
+                &pool,
+                transient_epoch,
+                &new_vote,
+            )],
+            Some(&initializer.pubkey()),
+            &[&initializer],
+            svm.latest_blockhash(),
+        );
+        let result = send_transaction(&mut svm, transaction);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "MergeReserve into the validator stake should succeed");
+
+        let validator_after = svm.get_account(&validator_stake_pda).unwrap().lamports;
+        let stake_rent = svm.minimum_balance_for_rent_exemption(200);
+        assert_eq!(validator_after, validator_before + amount - stake_rent);
+
+        let data = svm.get_account(&pool_state_pda).unwrap().data;
+        assert_eq!(PoolState::load(&data).unwrap().transient_lamports, 0);
+        let (_, entries) = get_validator_list(&svm, &validator_list_pda);
+        assert_eq!(entries[1].4, 0);
+
+        println!("\n=== Test Passed: IncreaseValidatorStake Merges Into Validator ===");
+    }
+
+    #[test]
+    fn test_transient_stakes_only_block_their_validator() {
+        let mut svm = setup_svm();
+
+        let (initializer, pool_state_pda, lst_mint_pda, _, reserve_stake_pda, _, _) =
+            initialize_pool_with_fees(&mut svm, 0, 0, 0, 0);
+        svm.airdrop(&initializer.pubkey(), 5_000_000_000).unwrap();
+
+        let vote_a = create_vote_account(&mut svm, &Keypair::new().pubkey());
+        let vote_b = create_vote_account(&mut svm, &Keypair::new().pubkey());
+        for vote in [&vote_a, &vote_b] {
+            execute_add_validator(&mut svm, &initializer, &pool_state_pda, &lst_mint_pda, vote)
+                .expect("AddValidator should succeed");
+        }
+
+        let amount = 3_000_000_000u64;
+        fund_reserve(&mut svm, &pool_state_pda, &reserve_stake_pda, 2 * amount);
+
+        let pool = Pool::from_account_data(
+            pool_state_pda,
+            &svm.get_account(&pool_state_pda).unwrap().data,
+        )
+        .unwrap();
+
+        // Each validator gets its own transient stake in the same epoch
+        let epoch = svm.get_sysvar::<Clock>().epoch;
+        send_instruction(
+            &mut svm,
+            &initializer,
+            client::increase_validator_stake(&pool, &vote_a, amount, epoch),
+        )
+        .expect("IncreaseValidatorStake should succeed");
+        send_instruction(
+            &mut svm,
+            &initializer,
+            client::increase_validator_stake(&pool, &vote_b, amount, epoch),
+        )
+        .expect("A second validator's transient stake should not collide");
+
+        let (validator_list_pda, _) = derive_validator_list_pda(&pool_state_pda);
+        let (_, entries) = get_validator_list(&svm, &validator_list_pda);
+        assert_eq!(entries[0].4, 0);
+        assert_eq!(entries[1].4, amount);
+        assert_eq!(entries[2].4, amount);
+
+        // Once its own transient stake merged, a validator can be removed
+        // while another validator's is still outstanding
+        let result = execute_remove_validator(
+            &mut svm,
+            &initializer,
+            &pool_state_pda,
+            &reserve_stake_pda,
+            &vote_a,
+        );
+        assert!(
+            result.is_err(),
+            "Validator with a transient stake cannot be removed"
+        );
+
+        svm.warp_to_slot(432_000 * 2);
+        send_instruction(
+            &mut svm,
+            &initializer,
+            client::merge_reserve_into_validator(&pool, epoch, &vote_a),
+        )
+        .expect("MergeReserve into the validator stake should succeed");
+
+        let result = execute_remove_validator(
+            &mut svm,
+            &initializer,
+            &pool_state_pda,
+            &reserve_stake_pda,
+            &vote_a,
+        );
+        assert!(
+            result.is_ok(),
+            "RemoveValidator should ignore other transient stakes"
+        );
+
+        // Listed validators' transient stakes do not hold up the pool stake
+        let new_vote = create_vote_account(&mut svm, &Keypair::new().pubkey());
+        send_instruction(&mut svm, &initializer, client::redelegate(&pool, &new_vote))
+            .expect("Redelegate should ignore listed transient stakes");
+
+        println!("\n=== Test Passed: Transient Stakes Only Block Their Validator ===");
+    }
+}
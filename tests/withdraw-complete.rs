@@ -4,6 +4,7 @@ mod tests {
     use pinocchio::sysvars::{clock::CLOCK_ID as CLOCK_SYSVAR, rent::RENT_ID as RENT_SYSVAR};
     use solana_sdk::{
         account::Account,
+        clock::Clock,
         instruction::{AccountMeta, Instruction},
         pubkey::Pubkey,
         signature::{Keypair, Signer},
@@ -66,6 +67,23 @@ mod tests {
         Pubkey::find_program_address(&[b"reserve_stake", pool_state.as_ref()], &PROGRAM_ID)
     }
 
+    fn derive_transient_stake_pda(
+        pool_state: &Pubkey,
+        validator_vote: &Pubkey,
+        epoch: u64,
+    ) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"transient_stake",
+                pool_state.as_ref(),
+                validator_vote.as_ref(),
+                &epoch.to_le_bytes(),
+            ],
+            &PROGRAM_ID,
+        )
+        .0
+    }
+
     fn derive_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
         Pubkey::find_program_address(
             &[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
@@ -77,6 +95,10 @@ mod tests {
     fn create_initialize_instruction_data(seed: u64) -> Vec<u8> {
         let mut data = vec![0u8]; // Discriminator for Initialize
         data.extend_from_slice(&seed.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes()); // deposit_fee_bps
+        data.extend_from_slice(&0u16.to_le_bytes()); // withdrawal_fee_bps
+        data.extend_from_slice(&0u16.to_le_bytes()); // reward_fee_bps
+        data.extend_from_slice(&0u16.to_le_bytes()); // sol_withdrawal_fee_bps
         data
     }
 
@@ -149,6 +171,12 @@ mod tests {
         }
     }
 
+    /// Reads the manager fee account recorded in pool state
+    fn get_manager_fee_account(svm: &LiteSVM, pool_state_pda: &Pubkey) -> Pubkey {
+        let data = svm.get_account(pool_state_pda).unwrap().data;
+        Pubkey::new_from_array(data[192..224].try_into().unwrap())
+    }
+
     /// Helper to initialize a pool and return all the PDAs
     fn initialize_pool(
         svm: &mut LiteSVM,
@@ -270,6 +298,7 @@ mod tests {
                 AccountMeta::new(reserve_stake_pda, false),
                 AccountMeta::new(lst_mint_pda, false),
                 AccountMeta::new(user_lst_ata, false),
+                AccountMeta::new(get_manager_fee_account(&svm, &pool_state_pda), false),
                 AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
                 AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
                 AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
@@ -292,12 +321,19 @@ mod tests {
         let crank = Keypair::new();
         svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
 
+        let transient_stake_pda = derive_transient_stake_pda(
+            &pool_state_pda,
+            &validator_vote,
+            svm.get_sysvar::<Clock>().epoch,
+        );
+
         let init_reserve_ix = Instruction {
             program_id: PROGRAM_ID,
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new_readonly(pool_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
+                AccountMeta::new(transient_stake_pda, false),
                 AccountMeta::new_readonly(validator_vote, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false),
@@ -330,6 +366,7 @@ mod tests {
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new(pool_stake_pda, false),
+                AccountMeta::new(transient_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false),
@@ -376,6 +413,7 @@ mod tests {
                 AccountMeta::new(user_stake_pda, false),
                 AccountMeta::new(lst_mint_pda, false),
                 AccountMeta::new(user_lst_ata, false),
+                AccountMeta::new(get_manager_fee_account(&svm, &pool_state_pda), false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false),
                 AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false),
@@ -524,6 +562,7 @@ mod tests {
                 AccountMeta::new(reserve_stake_pda, false),
                 AccountMeta::new(lst_mint_pda, false),
                 AccountMeta::new(user_lst_ata, false),
+                AccountMeta::new(get_manager_fee_account(svm, &pool_state_pda), false),
                 AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
                 AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
                 AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
@@ -543,12 +582,19 @@ mod tests {
         let crank = Keypair::new();
         svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
 
+        let transient_stake_pda = derive_transient_stake_pda(
+            &pool_state_pda,
+            &validator_vote,
+            svm.get_sysvar::<Clock>().epoch,
+        );
+
         let init_reserve_ix = Instruction {
             program_id: PROGRAM_ID,
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new_readonly(pool_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
+                AccountMeta::new(transient_stake_pda, false),
                 AccountMeta::new_readonly(validator_vote, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false),
@@ -579,6 +625,7 @@ mod tests {
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new(pool_stake_pda, false),
+                AccountMeta::new(transient_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false),
@@ -623,6 +670,7 @@ mod tests {
                 AccountMeta::new(user_stake_pda, false),
                 AccountMeta::new(lst_mint_pda, false),
                 AccountMeta::new(user_lst_ata, false),
+                AccountMeta::new(get_manager_fee_account(svm, &pool_state_pda), false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false),
                 AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false),
@@ -0,0 +1,346 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use liquid_staking_token::{
+        PinocchioError,
+        client::{self, Pool, PoolFees},
+    };
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        clock::Clock,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    /// Creates a pool managed by the returned keypair
+    fn initialize_pool(svm: &mut LiteSVM) -> (Keypair, Pool) {
+        let manager = Keypair::new();
+        let lst_mint = Keypair::new();
+        svm.airdrop(&manager.pubkey(), 10_000_000_000).unwrap();
+        let validator_vote = create_vote_account(svm, &Keypair::new().pubkey());
+
+        let seed = 23u64;
+        let ix = client::initialize(
+            &manager.pubkey(),
+            &lst_mint.pubkey(),
+            &validator_vote,
+            seed,
+            PoolFees::default(),
+        );
+        send(svm, &manager, ix, &[&lst_mint]).expect("Initialize should succeed");
+
+        let pool = load_pool(svm, &client::find_pool_state_address(seed).0);
+        (manager, pool)
+    }
+
+    const SLOTS_PER_EPOCH: u64 = 432_000;
+
+    /// Deposits for a user with a fresh withdraw ledger and moves the
+    /// deposit into the pool stake so it can be withdrawn.
+    fn user_with_ledger(svm: &mut LiteSVM, pool: &Pool) -> Keypair {
+        let user = deposit(svm, pool, 10_000_000_000);
+        send(
+            svm,
+            &user,
+            client::initialize_withdraw_ledger(pool, &user.pubkey()),
+            &[],
+        )
+        .expect("InitializeWithdrawLedger should succeed");
+
+        let crank = Keypair::new();
+        svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
+        let transient_epoch = svm.get_sysvar::<Clock>().epoch;
+        send(
+            svm,
+            &crank,
+            client::initialize_reserve(pool, transient_epoch),
+            &[],
+        )
+        .expect("InitializeReserve should succeed");
+
+        svm.warp_to_slot(SLOTS_PER_EPOCH * 2);
+        send(
+            svm,
+            &crank,
+            client::merge_reserve(pool, transient_epoch),
+            &[],
+        )
+        .expect("MergeReserve should succeed");
+
+        user
+    }
+
+    fn lamports(svm: &LiteSVM, address: &Pubkey) -> u64 {
+        svm.get_account(address)
+            .map_or(0, |account| account.lamports)
+    }
+
+    fn ledger_address(pool: &Pool, user: &Keypair) -> Pubkey {
+        client::find_withdraw_ledger_address(&pool.address, &user.pubkey()).0
+    }
+
+    fn ledger_next_nonce(svm: &LiteSVM, pool: &Pool, user: &Keypair) -> u64 {
+        let data = svm.get_account(&ledger_address(pool, user)).unwrap().data;
+        client::decode_withdraw_ledger(&data).unwrap().0.next_nonce
+    }
+
+    fn withdraw_with_ledger(svm: &mut LiteSVM, pool: &Pool, user: &Keypair, amount: u64) -> u64 {
+        let nonce = ledger_next_nonce(svm, pool, user);
+        send(
+            svm,
+            user,
+            client::withdraw_with_ledger(pool, &user.pubkey(), amount, nonce, 0, false),
+            &[],
+        )
+        .expect("Withdraw with a ledger should succeed");
+        nonce
+    }
+
+    #[test]
+    fn test_ledger_allocates_nonces() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+        let user = user_with_ledger(&mut svm, &pool);
+
+        assert_eq!(
+            withdraw_with_ledger(&mut svm, &pool, &user, 2_000_000_000),
+            1
+        );
+        assert_eq!(
+            withdraw_with_ledger(&mut svm, &pool, &user, 3_000_000_000),
+            2
+        );
+
+        let data = svm.get_account(&ledger_address(&pool, &user)).unwrap().data;
+        let (ledger, entries) = client::decode_withdraw_ledger(&data).unwrap();
+        assert_eq!(ledger.next_nonce, 3);
+        assert_eq!(ledger.owner, user.pubkey().to_bytes());
+        assert_eq!(entries.len(), 2);
+
+        let epoch = svm.get_sysvar::<Clock>().epoch;
+        for (entry, nonce) in entries.iter().zip([1, 2]) {
+            let (user_stake, _) =
+                client::find_withdraw_stake_address(&pool.address, &user.pubkey(), nonce);
+            assert_eq!(entry.nonce, nonce);
+            assert_eq!(entry.withdraw_stake, user_stake.to_bytes());
+            assert_eq!(entry.lamports, lamports(&svm, &user_stake));
+            assert_eq!(entry.claimable_epoch, epoch + 1);
+        }
+    }
+
+    #[test]
+    fn test_ledger_skips_nonces_in_use() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+        let user = user_with_ledger(&mut svm, &pool);
+
+        // Withdrawn by nonce before the ledger handed it out
+        send(
+            &mut svm,
+            &user,
+            client::withdraw(&pool, &user.pubkey(), 2_000_000_000, 1, 0),
+            &[],
+        )
+        .expect("Withdraw should succeed");
+
+        let ix = client::withdraw_with_ledger(&pool, &user.pubkey(), 2_000_000_000, 1, 0, false);
+        assert_eq!(
+            send(&mut svm, &user, ix, &[]),
+            Err(custom_error(PinocchioError::WithdrawStakeAlreadyExists))
+        );
+
+        svm.expire_blockhash();
+        let ix = client::withdraw_with_ledger(&pool, &user.pubkey(), 2_000_000_000, 2, 0, false);
+        send(&mut svm, &user, ix, &[]).expect("A later nonce should skip the one in use");
+
+        let data = svm.get_account(&ledger_address(&pool, &user)).unwrap().data;
+        let (ledger, entries) = client::decode_withdraw_ledger(&data).unwrap();
+        assert_eq!(ledger.next_nonce, 3);
+        assert_eq!(entries[0].nonce, 2);
+
+        // Nonces below the ledger's next one were already handed out
+        let ix = client::withdraw_with_ledger(&pool, &user.pubkey(), 2_000_000_000, 1, 0, false);
+        assert_eq!(
+            send(&mut svm, &user, ix, &[]),
+            Err(custom_error(PinocchioError::InvalidNonce))
+        );
+    }
+
+    #[test]
+    fn test_ledger_must_belong_to_user() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+        let user = user_with_ledger(&mut svm, &pool);
+
+        // Another user's ledger
+        let other = Keypair::new();
+        svm.airdrop(&other.pubkey(), 1_000_000_000).unwrap();
+        send(
+            &mut svm,
+            &other,
+            client::initialize_withdraw_ledger(&pool, &other.pubkey()),
+            &[],
+        )
+        .expect("InitializeWithdrawLedger should succeed");
+        let mut ix = client::withdraw(&pool, &user.pubkey(), 2_000_000_000, 1, 0);
+        ix.data[9..17].copy_from_slice(&0u64.to_le_bytes());
+        client::push_withdraw_ledger(&mut ix, &pool, &other.pubkey());
+        assert_eq!(
+            send(&mut svm, &user, ix, &[]),
+            Err(custom_error(PinocchioError::InvalidWithdrawLedger))
+        );
+    }
+
+    #[test]
+    fn test_complete_cleans_up_ledger() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+        let user = user_with_ledger(&mut svm, &pool);
+        let ledger = ledger_address(&pool, &user);
+
+        let first = withdraw_with_ledger(&mut svm, &pool, &user, 2_000_000_000);
+        let second = withdraw_with_ledger(&mut svm, &pool, &user, 3_000_000_000);
+        let full_rent = lamports(&svm, &ledger);
+
+        assert_eq!(
+            send(
+                &mut svm,
+                &user,
+                client::close_withdraw_ledger(&pool, &user.pubkey()),
+                &[],
+            ),
+            Err(custom_error(PinocchioError::WithdrawLedgerNotEmpty))
+        );
+
+        svm.warp_to_slot(SLOTS_PER_EPOCH * 4);
+        send(
+            &mut svm,
+            &user,
+            client::withdraw_complete_with_ledger(&pool, &user.pubkey(), first),
+            &[],
+        )
+        .expect("WithdrawComplete with a ledger should succeed");
+
+        let data = svm.get_account(&ledger).unwrap().data;
+        let (_, entries) = client::decode_withdraw_ledger(&data).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].nonce, second);
+        assert!(lamports(&svm, &ledger) < full_rent);
+
+        send(
+            &mut svm,
+            &user,
+            client::withdraw_complete_with_ledger(&pool, &user.pubkey(), second),
+            &[],
+        )
+        .expect("WithdrawComplete with a ledger should succeed");
+
+        send(
+            &mut svm,
+            &user,
+            client::close_withdraw_ledger(&pool, &user.pubkey()),
+            &[],
+        )
+        .expect("Empty ledger should close");
+        assert_eq!(lamports(&svm, &ledger), 0);
+    }
+
+    #[test]
+    fn test_complete_rejects_unlisted_stake() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+        let user = user_with_ledger(&mut svm, &pool);
+
+        // Withdrawn by nonce, so the ledger does not list it
+        send(
+            &mut svm,
+            &user,
+            client::withdraw(&pool, &user.pubkey(), 2_000_000_000, 7, 0),
+            &[],
+        )
+        .expect("Withdraw should succeed");
+
+        svm.warp_to_slot(SLOTS_PER_EPOCH * 4);
+        assert_eq!(
+            send(
+                &mut svm,
+                &user,
+                client::withdraw_complete_with_ledger(&pool, &user.pubkey(), 7),
+                &[],
+            ),
+            Err(custom_error(PinocchioError::WithdrawLedgerEntryNotFound))
+        );
+    }
+
+    #[test]
+    fn test_prune_entries_claimed_by_ticket() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+        let user = user_with_ledger(&mut svm, &pool);
+        let ledger = ledger_address(&pool, &user);
+
+        let nonce = ledger_next_nonce(&svm, &pool, &user);
+        send(
+            &mut svm,
+            &user,
+            client::withdraw_with_ledger(&pool, &user.pubkey(), 2_000_000_000, nonce, 0, true),
+            &[],
+        )
+        .expect("Ticketed withdraw with a ledger should succeed");
+        let (user_stake, _) =
+            client::find_withdraw_stake_address(&pool.address, &user.pubkey(), nonce);
+
+        assert_eq!(
+            send(
+                &mut svm,
+                &user,
+                client::prune_withdraw_ledger(&pool, &user.pubkey(), &[user_stake]),
+                &[],
+            ),
+            Err(custom_error(PinocchioError::WithdrawStakeNotClaimed))
+        );
+
+        // Claimed through the ticket alone, so the entry is left behind
+        svm.warp_to_slot(SLOTS_PER_EPOCH * 4);
+        send(
+            &mut svm,
+            &user,
+            client::withdraw_complete_with_ticket(&pool, &user.pubkey(), &user_stake),
+            &[],
+        )
+        .expect("WithdrawComplete with the ticket should succeed");
+        assert_eq!(
+            send(
+                &mut svm,
+                &user,
+                client::close_withdraw_ledger(&pool, &user.pubkey()),
+                &[],
+            ),
+            Err(custom_error(PinocchioError::WithdrawLedgerNotEmpty))
+        );
+
+        let full_rent = lamports(&svm, &ledger);
+        send(
+            &mut svm,
+            &user,
+            client::prune_withdraw_ledger(&pool, &user.pubkey(), &[user_stake]),
+            &[],
+        )
+        .expect("PruneWithdrawLedger should drop the claimed entry");
+
+        let data = svm.get_account(&ledger).unwrap().data;
+        assert!(client::decode_withdraw_ledger(&data).unwrap().1.is_empty());
+        assert!(lamports(&svm, &ledger) < full_rent);
+
+        svm.expire_blockhash();
+        send(
+            &mut svm,
+            &user,
+            client::close_withdraw_ledger(&pool, &user.pubkey()),
+            &[],
+        )
+        .expect("Pruned ledger should close");
+    }
+}
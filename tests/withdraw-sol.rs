@@ -0,0 +1,262 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+        transaction::Transaction,
+    };
+    use spl_token::ID as TOKEN_PROGRAM_ID;
+
+    /// Helper to execute WithdrawSol
+    fn execute_withdraw_sol(
+        svm: &mut LiteSVM,
+        user: &Keypair,
+        pool_state_pda: &Pubkey,
+        reserve_stake_pda: &Pubkey,
+        lst_mint_pda: &Pubkey,
+        user_lst_ata: &Pubkey,
+        amount: u64,
+    ) -> TransactionResult {
+        let mut data = vec![12u8]; // WithdrawSol discriminator
+        data.extend_from_slice(&amount.to_le_bytes());
+
+        let instruction = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(user.pubkey(), true),
+                AccountMeta::new(*pool_state_pda, false),
+                AccountMeta::new(*reserve_stake_pda, false),
+                AccountMeta::new(*lst_mint_pda, false),
+                AccountMeta::new(*user_lst_ata, false),
+                AccountMeta::new(get_manager_fee_account(svm, pool_state_pda), false),
+                AccountMeta::new_readonly(CLOCK_SYSVAR, false),
+                AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false),
+                AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ],
+            data,
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&user.pubkey()),
+            &[user],
+            svm.latest_blockhash(),
+        );
+
+        send_transaction(svm, transaction)
+    }
+
+    /// Initializes a pool and deposits `deposit_amount` SOL into the reserve
+    fn setup_pool_with_reserve(
+        svm: &mut LiteSVM,
+        sol_withdrawal_fee_bps: u16,
+        deposit_amount: u64,
+    ) -> (Keypair, Keypair, Pubkey, Pubkey, Pubkey, Pubkey) {
+        let (initializer, pool_state_pda, lst_mint_pda, _, reserve_stake_pda, _, _) =
+            initialize_pool_with_fees(svm, 0, 0, 0, sol_withdrawal_fee_bps);
+
+        let user = Keypair::new();
+        svm.airdrop(&user.pubkey(), deposit_amount + 1_000_000_000)
+            .unwrap();
+        let user_lst_ata = create_depositor_ata(svm, &user, &lst_mint_pda);
+
+        execute_deposit(
+            svm,
+            &user,
+            &pool_state_pda,
+            &lst_mint_pda,
+            &user_lst_ata,
+            deposit_amount,
+        )
+        .expect("Deposit should succeed");
+
+        (
+            initializer,
+            user,
+            pool_state_pda,
+            lst_mint_pda,
+            reserve_stake_pda,
+            user_lst_ata,
+        )
+    }
+
+    #[test]
+    fn test_withdraw_sol_success() {
+        let mut svm = setup_svm();
+        let (_, user, pool_state_pda, lst_mint_pda, reserve_stake_pda, user_lst_ata) =
+            setup_pool_with_reserve(&mut svm, 0, 3_000_000_000);
+
+        let lst_before = get_token_balance(&svm.get_account(&user_lst_ata).unwrap().data);
+        let supply_before = get_mint_supply(&svm.get_account(&lst_mint_pda).unwrap().data);
+        let reserve_before = svm.get_account(&reserve_stake_pda).unwrap().lamports;
+        let user_before = svm.get_account(&user.pubkey()).unwrap().lamports;
+        let (total_before, _) = get_cached_balance(&svm, &pool_state_pda);
+
+        let withdraw_amount = 1_000_000_000u64;
+        let result = execute_withdraw_sol(
+            &mut svm,
+            &user,
+            &pool_state_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+            &user_lst_ata,
+            withdraw_amount,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "WithdrawSol should succeed");
+
+        let lst_after = get_token_balance(&svm.get_account(&user_lst_ata).unwrap().data);
+        let supply_after = get_mint_supply(&svm.get_account(&lst_mint_pda).unwrap().data);
+        assert_eq!(lst_after, lst_before - withdraw_amount, "LST should be burned");
+        assert_eq!(supply_after, supply_before - withdraw_amount);
+
+        let reserve_after = svm.get_account(&reserve_stake_pda).unwrap().lamports;
+        let user_after = svm.get_account(&user.pubkey()).unwrap().lamports;
+        let sol_received = reserve_before - reserve_after;
+        assert!(sol_received >= withdraw_amount, "Rate should be at least 1:1");
+        assert!(user_after > user_before, "User should receive SOL immediately");
+
+        let (total_after, _) = get_cached_balance(&svm, &pool_state_pda);
+        assert_eq!(total_after, total_before - sol_received);
+
+        println!("\n=== Test Passed: WithdrawSol Success ===");
+    }
+
+    #[test]
+    fn test_withdraw_sol_fee_transferred_to_manager() {
+        let mut svm = setup_svm();
+        let (_, user, pool_state_pda, lst_mint_pda, reserve_stake_pda, user_lst_ata) =
+            setup_pool_with_reserve(&mut svm, 100, 3_000_000_000);
+
+        let manager_fee_account = get_manager_fee_account(&svm, &pool_state_pda);
+        let manager_before =
+            get_token_balance(&svm.get_account(&manager_fee_account).unwrap().data);
+
+        let withdraw_amount = 1_000_000_000u64;
+        let result = execute_withdraw_sol(
+            &mut svm,
+            &user,
+            &pool_state_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+            &user_lst_ata,
+            withdraw_amount,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "WithdrawSol should succeed");
+
+        let manager_after =
+            get_token_balance(&svm.get_account(&manager_fee_account).unwrap().data);
+        assert_eq!(
+            manager_after - manager_before,
+            withdraw_amount / 100,
+            "Manager should receive 1% of the LST"
+        );
+
+        println!("\n=== Test Passed: WithdrawSol Fee Transferred ===");
+    }
+
+    #[test]
+    fn test_withdraw_sol_leaving_undelegatable_reserve() {
+        let mut svm = setup_svm();
+        let (_, user, pool_state_pda, lst_mint_pda, reserve_stake_pda, user_lst_ata) =
+            setup_pool_with_reserve(&mut svm, 0, 1_500_000_000);
+        let reserve_before = svm.get_account(&reserve_stake_pda).unwrap().lamports;
+
+        // Leaves roughly 0.5 SOL above rent: too little to delegate, which
+        // only waits for further deposits
+        let result = execute_withdraw_sol(
+            &mut svm,
+            &user,
+            &pool_state_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+            &user_lst_ata,
+            1_000_000_000,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "WithdrawSol may leave less than a delegation");
+
+        let reserve_after = svm.get_account(&reserve_stake_pda).unwrap().lamports;
+        let reserve_rent = svm.minimum_balance_for_rent_exemption(200);
+        assert!(reserve_after > reserve_rent);
+        assert!(reserve_after < reserve_rent * 2 + 1_000_000_000);
+        assert!(reserve_before - reserve_after >= 1_000_000_000);
+
+        println!("\n=== Test Passed: WithdrawSol Leaves Undelegatable Reserve ===");
+    }
+
+    #[test]
+    fn test_withdraw_sol_exceeds_reserve_fails() {
+        let mut svm = setup_svm();
+        let (initializer, _, pool_state_pda, lst_mint_pda, reserve_stake_pda, _) =
+            setup_pool_with_reserve(&mut svm, 0, 1_000_000_000);
+
+        // The initializer's LST is worth more than the 1 SOL sitting in the reserve
+        let initializer_lst_ata = get_manager_fee_account(&svm, &pool_state_pda);
+        let lst_balance =
+            get_token_balance(&svm.get_account(&initializer_lst_ata).unwrap().data);
+
+        let result = execute_withdraw_sol(
+            &mut svm,
+            &initializer,
+            &pool_state_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+            &initializer_lst_ata,
+            lst_balance,
+        );
+        assert!(result.is_err(), "WithdrawSol cannot pay out more than the reserve holds");
+
+        println!("\n=== Test Passed: WithdrawSol Exceeds Reserve Fails ===");
+    }
+
+    #[test]
+    fn test_withdraw_sol_requires_fresh_pool_balance() {
+        let mut svm = setup_svm();
+        let (_, user, pool_state_pda, lst_mint_pda, reserve_stake_pda, user_lst_ata) =
+            setup_pool_with_reserve(&mut svm, 0, 3_000_000_000);
+
+        advance_epoch(&mut svm);
+        let result = execute_withdraw_sol(
+            &mut svm,
+            &user,
+            &pool_state_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+            &user_lst_ata,
+            1_000_000_000,
+        );
+        assert!(result.is_err(), "WithdrawSol should fail on a stale pool balance");
+
+        let (pool_stake_pda, _) = derive_stake_account_pda(&pool_state_pda);
+        execute_update_pool_balance(
+            &mut svm,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+        )
+        .expect("UpdatePoolBalance should succeed");
+
+        let result = execute_withdraw_sol(
+            &mut svm,
+            &user,
+            &pool_state_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+            &user_lst_ata,
+            1_000_000_000,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "WithdrawSol should succeed after the crank");
+
+        println!("\n=== Test Passed: WithdrawSol Requires Fresh Pool Balance ===");
+    }
+}
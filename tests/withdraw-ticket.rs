@@ -0,0 +1,182 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::*;
+    use liquid_staking_token::{
+        PinocchioError,
+        client::{self, Pool, PoolFees},
+    };
+    use litesvm::LiteSVM;
+    use solana_sdk::{
+        clock::Clock,
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    };
+
+    /// Creates a pool managed by the returned keypair
+    fn initialize_pool(svm: &mut LiteSVM) -> (Keypair, Pool) {
+        let manager = Keypair::new();
+        let lst_mint = Keypair::new();
+        svm.airdrop(&manager.pubkey(), 10_000_000_000).unwrap();
+        let validator_vote = create_vote_account(svm, &Keypair::new().pubkey());
+
+        let seed = 20u64;
+        let ix = client::initialize(
+            &manager.pubkey(),
+            &lst_mint.pubkey(),
+            &validator_vote,
+            seed,
+            PoolFees::default(),
+        );
+        send(svm, &manager, ix, &[&lst_mint]).expect("Initialize should succeed");
+
+        let pool = load_pool(svm, &client::find_pool_state_address(seed).0);
+        (manager, pool)
+    }
+
+    const SLOTS_PER_EPOCH: u64 = 432_000;
+
+    /// Deposits, moves the deposit into the pool stake and withdraws half of
+    /// it with a ticket. Returns the user and the withdraw stake account.
+    fn ticketed_withdrawal(svm: &mut LiteSVM, pool: &Pool) -> (Keypair, Pubkey) {
+        let user = deposit(svm, pool, 10_000_000_000);
+
+        let crank = Keypair::new();
+        svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
+        let transient_epoch = svm.get_sysvar::<Clock>().epoch;
+        send(
+            svm,
+            &crank,
+            client::initialize_reserve(pool, transient_epoch),
+            &[],
+        )
+        .expect("InitializeReserve should succeed");
+
+        svm.warp_to_slot(SLOTS_PER_EPOCH * 2);
+        send(
+            svm,
+            &crank,
+            client::merge_reserve(pool, transient_epoch),
+            &[],
+        )
+        .expect("MergeReserve should succeed");
+
+        let nonce = 1;
+        send(
+            svm,
+            &user,
+            client::withdraw_with_ticket(pool, &user.pubkey(), 5_000_000_000, nonce, 0),
+            &[],
+        )
+        .expect("Withdraw with a ticket should succeed");
+
+        let (user_stake, _) =
+            client::find_withdraw_stake_address(&pool.address, &user.pubkey(), nonce);
+        (user, user_stake)
+    }
+
+    fn lamports(svm: &LiteSVM, address: &Pubkey) -> u64 {
+        svm.get_account(address)
+            .map_or(0, |account| account.lamports)
+    }
+
+    fn ticket_address(user_stake: &Pubkey) -> Pubkey {
+        client::find_withdraw_ticket_address(user_stake).0
+    }
+
+    #[test]
+    fn test_withdraw_issues_ticket() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+        let (user, user_stake) = ticketed_withdrawal(&mut svm, &pool);
+
+        let ticket = client::decode_withdraw_ticket(
+            &svm.get_account(&ticket_address(&user_stake)).unwrap().data,
+        )
+        .expect("Ticket should decode");
+        assert_eq!(ticket.owner, user.pubkey().to_bytes());
+        assert_eq!(ticket.withdraw_stake, user_stake.to_bytes());
+        assert_eq!(ticket.pool_state, pool.address.to_bytes());
+    }
+
+    #[test]
+    fn test_transferred_ticket_pays_new_owner() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+        let (user, user_stake) = ticketed_withdrawal(&mut svm, &pool);
+        let ticket = ticket_address(&user_stake);
+
+        let buyer = Keypair::new();
+        svm.airdrop(&buyer.pubkey(), 1_000_000_000).unwrap();
+        send(
+            &mut svm,
+            &user,
+            client::transfer_withdraw_ticket(&ticket, &user.pubkey(), &buyer.pubkey()),
+            &[],
+        )
+        .expect("Owner should transfer the ticket");
+
+        svm.warp_to_slot(SLOTS_PER_EPOCH * 4);
+
+        // The seller can no longer claim by nonce or with the ticket
+        assert_eq!(
+            send(
+                &mut svm,
+                &user,
+                client::withdraw_complete(&pool, &user.pubkey(), 1),
+                &[],
+            ),
+            Err(custom_error(PinocchioError::WithdrawTicketIssued))
+        );
+        assert_eq!(
+            send(
+                &mut svm,
+                &user,
+                client::withdraw_complete_with_ticket(&pool, &user.pubkey(), &user_stake),
+                &[],
+            ),
+            Err(custom_error(PinocchioError::InvalidAuthority))
+        );
+
+        let stake_lamports = lamports(&svm, &user_stake);
+        let ticket_lamports = lamports(&svm, &ticket);
+        let balance_before = lamports(&svm, &buyer.pubkey());
+        send(
+            &mut svm,
+            &buyer,
+            client::withdraw_complete_with_ticket(&pool, &buyer.pubkey(), &user_stake),
+            &[],
+        )
+        .expect("Ticket owner should claim the withdrawal");
+
+        // Paid the stake and the ticket rent, less the transaction fee
+        let balance_after = lamports(&svm, &buyer.pubkey());
+        assert!(balance_after > balance_before + stake_lamports + ticket_lamports - 10_000);
+        assert_eq!(lamports(&svm, &ticket), 0);
+        assert_eq!(lamports(&svm, &user_stake), 0);
+    }
+
+    #[test]
+    fn test_transfer_ticket_requires_owner() {
+        let mut svm = setup_svm();
+        let (_manager, pool) = initialize_pool(&mut svm);
+        let (_user, user_stake) = ticketed_withdrawal(&mut svm, &pool);
+
+        let impostor = Keypair::new();
+        svm.airdrop(&impostor.pubkey(), 1_000_000_000).unwrap();
+        assert_eq!(
+            send(
+                &mut svm,
+                &impostor,
+                client::transfer_withdraw_ticket(
+                    &ticket_address(&user_stake),
+                    &impostor.pubkey(),
+                    &impostor.pubkey(),
+                ),
+                &[],
+            ),
+            Err(custom_error(PinocchioError::InvalidAuthority))
+        );
+    }
+}
@@ -1,13 +1,15 @@
 #[cfg(test)]
 mod tests {
+    use liquid_staking_token::PinocchioError;
     use litesvm::LiteSVM;
     use pinocchio::sysvars::{clock::CLOCK_ID as CLOCK_SYSVAR, rent::RENT_ID as RENT_SYSVAR};
     use solana_sdk::{
         account::Account,
-        instruction::{AccountMeta, Instruction},
+        clock::Clock,
+        instruction::{AccountMeta, Instruction, InstructionError},
         pubkey::Pubkey,
         signature::{Keypair, Signer},
-        transaction::Transaction,
+        transaction::{Transaction, TransactionError},
     };
     use spl_associated_token_account::{ID as ATA_PROGRAM_ID, get_associated_token_address};
     use spl_token::ID as TOKEN_PROGRAM_ID;
@@ -66,6 +68,23 @@ mod tests {
         Pubkey::find_program_address(&[b"reserve_stake", pool_state.as_ref()], &PROGRAM_ID)
     }
 
+    fn derive_transient_stake_pda(
+        pool_state: &Pubkey,
+        validator_vote: &Pubkey,
+        epoch: u64,
+    ) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                b"transient_stake",
+                pool_state.as_ref(),
+                validator_vote.as_ref(),
+                &epoch.to_le_bytes(),
+            ],
+            &PROGRAM_ID,
+        )
+        .0
+    }
+
     fn derive_ata(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
         Pubkey::find_program_address(
             &[owner.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
@@ -76,9 +95,16 @@ mod tests {
 
     fn create_initialize_instruction_data(
         seed: u64,
+        deposit_fee_bps: u16,
+        withdrawal_fee_bps: u16,
+        reward_fee_bps: u16,
     ) -> Vec<u8> {
         let mut data = vec![0u8]; // Discriminator for Initialize
         data.extend_from_slice(&seed.to_le_bytes());
+        data.extend_from_slice(&deposit_fee_bps.to_le_bytes());
+        data.extend_from_slice(&withdrawal_fee_bps.to_le_bytes());
+        data.extend_from_slice(&reward_fee_bps.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes()); // sol_withdrawal_fee_bps
         data
     }
 
@@ -95,6 +121,30 @@ mod tests {
         data
     }
 
+    fn create_withdraw_instruction_data_with_min(
+        amount: u64,
+        nonce: u64,
+        minimum_lamports_out: u64,
+    ) -> Vec<u8> {
+        let mut data = create_withdraw_instruction_data(amount, nonce);
+        data.extend_from_slice(&minimum_lamports_out.to_le_bytes());
+        data
+    }
+
+    fn assert_program_error(
+        result: &Result<
+            litesvm::types::TransactionMetadata,
+            litesvm::types::FailedTransactionMetadata,
+        >,
+        expected: PinocchioError,
+    ) {
+        let err = result.as_ref().expect_err("Transaction should fail");
+        assert_eq!(
+            err.err,
+            TransactionError::InstructionError(0, InstructionError::Custom(expected as u32)),
+        );
+    }
+
     fn setup_svm() -> LiteSVM {
         let mut svm = LiteSVM::new().with_builtins().with_sigverify(false);
 
@@ -151,9 +201,64 @@ mod tests {
         }
     }
 
+    /// Reads the manager fee account recorded in pool state
+    fn get_manager_fee_account(svm: &LiteSVM, pool_state_pda: &Pubkey) -> Pubkey {
+        let data = svm.get_account(pool_state_pda).unwrap().data;
+        Pubkey::new_from_array(data[192..224].try_into().unwrap())
+    }
+
+    /// Moves the clock into the next epoch and runs the UpdatePoolBalance crank
+    fn advance_epoch_and_update_pool_balance(
+        svm: &mut LiteSVM,
+        pool_state_pda: &Pubkey,
+        pool_stake_pda: &Pubkey,
+        reserve_stake_pda: &Pubkey,
+        lst_mint_pda: &Pubkey,
+    ) {
+        let mut clock = svm.get_sysvar::<Clock>();
+        clock.epoch += 1;
+        svm.set_sysvar(&clock);
+
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+
+        let update_ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(*pool_state_pda, false),
+                AccountMeta::new_readonly(*pool_stake_pda, false),
+                AccountMeta::new_readonly(*reserve_stake_pda, false),
+                AccountMeta::new(*lst_mint_pda, false),
+                AccountMeta::new(get_manager_fee_account(svm, pool_state_pda), false),
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            ],
+            data: vec![6u8], // UpdatePoolBalance discriminator
+        };
+
+        let tx = Transaction::new_signed_with_payer(
+            &[update_ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "UpdatePoolBalance should succeed");
+    }
+
     /// Helper to initialize a pool and return all the PDAs
     fn initialize_pool(
         svm: &mut LiteSVM,
+    ) -> (Keypair, Pubkey, Pubkey, Pubkey, Pubkey, Pubkey, u64) {
+        initialize_pool_with_fees(svm, 0, 0, 0)
+    }
+
+    /// Helper to initialize a pool with the given fees and return all the PDAs
+    fn initialize_pool_with_fees(
+        svm: &mut LiteSVM,
+        deposit_fee_bps: u16,
+        withdrawal_fee_bps: u16,
+        reward_fee_bps: u16,
     ) -> (Keypair, Pubkey, Pubkey, Pubkey, Pubkey, Pubkey, u64) {
         let initializer = Keypair::new();
         svm.airdrop(&initializer.pubkey(), 2_000_000_000).unwrap();
@@ -170,7 +275,12 @@ mod tests {
         let initializer_lst_ata =
             get_associated_token_address(&initializer.pubkey(), &lst_mint.pubkey());
 
-        let instruction_data = create_initialize_instruction_data(seed);
+        let instruction_data = create_initialize_instruction_data(
+            seed,
+            deposit_fee_bps,
+            withdrawal_fee_bps,
+            reward_fee_bps,
+        );
 
         let instruction = Instruction {
             program_id: PROGRAM_ID,
@@ -266,6 +376,7 @@ mod tests {
                 AccountMeta::new(reserve_stake_pda, false),
                 AccountMeta::new(lst_mint_pda, false),
                 AccountMeta::new(user_lst_ata, false),
+                AccountMeta::new(get_manager_fee_account(&svm, &pool_state_pda), false),
                 AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
                 AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
                 AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
@@ -284,16 +395,23 @@ mod tests {
         print_transaction_logs(&result);
         assert!(result.is_ok(), "Deposit should succeed before withdraw");
 
-        // 3. Initialize and delegate reserve_stake
+        // 3. Activate the reserve into this epoch's transient stake
         let crank = Keypair::new();
         svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
 
+        let transient_stake_pda = derive_transient_stake_pda(
+            &pool_state_pda,
+            &validator_vote,
+            svm.get_sysvar::<Clock>().epoch,
+        );
+
         let init_reserve_ix = Instruction {
             program_id: PROGRAM_ID,
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false), // pool_state
                 AccountMeta::new_readonly(pool_stake_pda, false), // pool_stake
                 AccountMeta::new(reserve_stake_pda, false), // reserve_stake
+                AccountMeta::new(transient_stake_pda, false), // transient_stake
                 AccountMeta::new_readonly(validator_vote, false), // validator_vote
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false), // clock
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false), // rent
@@ -316,7 +434,7 @@ mod tests {
         print_transaction_logs(&result);
         assert!(result.is_ok(), "InitializeReserve should succeed");
 
-        println!("\n=== Reserve Initialized & Delegated ===");
+        println!("\n=== Reserve Activated ===");
 
         // 4. Warp forward to next epoch so both stakes become active
         // Stakes need to be in the same state (both active) to merge
@@ -325,13 +443,13 @@ mod tests {
 
         // 5. Call MergeReserve
         let pool_stake_before = svm.get_account(&pool_stake_pda).unwrap();
-        let reserve_stake_before = svm.get_account(&reserve_stake_pda).unwrap();
+        let transient_stake_before = svm.get_account(&transient_stake_pda).unwrap();
 
         println!("\n=== Before Merge ===");
         println!("  Pool stake lamports: {}", pool_stake_before.lamports);
         println!(
-            "  Reserve stake lamports: {}",
-            reserve_stake_before.lamports
+            "  Transient stake lamports: {}",
+            transient_stake_before.lamports
         );
 
         let merge_ix = Instruction {
@@ -339,7 +457,8 @@ mod tests {
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false),    // pool_state
                 AccountMeta::new(pool_stake_pda, false),    // pool_stake (destination)
-                AccountMeta::new(reserve_stake_pda, false), // reserve_stake (source)
+                AccountMeta::new(transient_stake_pda, false), // transient_stake (source)
+                AccountMeta::new(reserve_stake_pda, false), // reserve_stake
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false), // clock
                 AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false), // stake_history
                 AccountMeta::new_readonly(STAKE_PROGRAM_ID, false), // stake_program
@@ -360,32 +479,32 @@ mod tests {
 
         // 6. Verify merge results
         let pool_stake_after = svm.get_account(&pool_stake_pda).unwrap();
-        let reserve_stake_after = svm.get_account(&reserve_stake_pda);
+        let transient_stake_after = svm.get_account(&transient_stake_pda);
 
         println!("\n=== After Merge ===");
         println!("  Pool stake lamports: {}", pool_stake_after.lamports);
 
-        // Reserve should be closed (absorbed into pool_stake)
-        match reserve_stake_after {
+        // Transient stake should be closed (absorbed into pool_stake)
+        match transient_stake_after {
             Some(acc) => {
-                println!("  Reserve stake lamports: {}", acc.lamports);
-                println!("  Reserve stake owner: {:?}", acc.owner);
-                // After merge, reserve should be empty/system-owned
+                println!("  Transient stake lamports: {}", acc.lamports);
                 assert_eq!(
                     acc.lamports, 0,
-                    "Reserve should have 0 lamports after merge"
+                    "Transient stake should have 0 lamports after merge"
                 );
             }
             None => {
-                println!("  Reserve stake: CLOSED");
+                println!("  Transient stake: CLOSED");
             }
         }
 
-        // Pool stake should have absorbed reserve's lamports
-        let expected_lamports = pool_stake_before.lamports + reserve_stake_before.lamports;
+        // Pool stake absorbs the transient delegation; its rent goes back to the reserve
+        let transient_rent = svm.minimum_balance_for_rent_exemption(200);
+        let expected_lamports =
+            pool_stake_before.lamports + transient_stake_before.lamports - transient_rent;
         assert_eq!(
             pool_stake_after.lamports, expected_lamports,
-            "Pool stake should have absorbed reserve lamports"
+            "Pool stake should have absorbed the transient delegation"
         );
 
         println!("\n=== Merge Verified Successfully ===");
@@ -418,6 +537,7 @@ mod tests {
                 AccountMeta::new(user_stake_pda, false),                // user_stake
                 AccountMeta::new(lst_mint_pda, false),                  // lst_mint
                 AccountMeta::new(user_lst_ata, false),                  // user_lst_ata
+                AccountMeta::new(get_manager_fee_account(&svm, &pool_state_pda), false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),  // clock
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false),   // rent
                 AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false), // validator_vote
@@ -484,10 +604,26 @@ mod tests {
         Pubkey,  // reserve_stake_pda
         Pubkey,  // user_lst_ata
         Pubkey,  // validator_vote
+    ) {
+        setup_pool_for_withdraw_with_fee(svm, 0)
+    }
+
+    /// Helper to setup a withdraw-ready pool charging `withdrawal_fee_bps`
+    fn setup_pool_for_withdraw_with_fee(
+        svm: &mut LiteSVM,
+        withdrawal_fee_bps: u16,
+    ) -> (
+        Keypair, // user
+        Pubkey,  // pool_state_pda
+        Pubkey,  // lst_mint_pda
+        Pubkey,  // pool_stake_pda
+        Pubkey,  // reserve_stake_pda
+        Pubkey,  // user_lst_ata
+        Pubkey,  // validator_vote
     ) {
         // Initialize pool
         let (_, pool_state_pda, lst_mint_pda, pool_stake_pda, reserve_stake_pda, validator_vote, _) =
-            initialize_pool(svm);
+            initialize_pool_with_fees(svm, 0, withdrawal_fee_bps, 0);
 
         // Create user and deposit
         let user = Keypair::new();
@@ -525,6 +661,7 @@ mod tests {
                 AccountMeta::new(reserve_stake_pda, false),
                 AccountMeta::new(lst_mint_pda, false),
                 AccountMeta::new(user_lst_ata, false),
+                AccountMeta::new(get_manager_fee_account(svm, &pool_state_pda), false),
                 AccountMeta::new_readonly(SYSTEM_PROGRAM_ID, false),
                 AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
                 AccountMeta::new_readonly(STAKE_PROGRAM_ID, false),
@@ -544,12 +681,19 @@ mod tests {
         let crank = Keypair::new();
         svm.airdrop(&crank.pubkey(), 1_000_000_000).unwrap();
 
+        let transient_stake_pda = derive_transient_stake_pda(
+            &pool_state_pda,
+            &validator_vote,
+            svm.get_sysvar::<Clock>().epoch,
+        );
+
         let init_reserve_ix = Instruction {
             program_id: PROGRAM_ID,
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new_readonly(pool_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
+                AccountMeta::new(transient_stake_pda, false),
                 AccountMeta::new_readonly(validator_vote, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false),
@@ -580,6 +724,7 @@ mod tests {
             accounts: vec![
                 AccountMeta::new(pool_state_pda, false),
                 AccountMeta::new(pool_stake_pda, false),
+                AccountMeta::new(transient_stake_pda, false),
                 AccountMeta::new(reserve_stake_pda, false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false),
@@ -620,6 +765,32 @@ mod tests {
         amount: u64,
         nonce: u64,
     ) -> Result<litesvm::types::TransactionMetadata, litesvm::types::FailedTransactionMetadata>
+    {
+        execute_withdraw_with_data(
+            svm,
+            user,
+            pool_state_pda,
+            pool_stake_pda,
+            reserve_stake_pda,
+            lst_mint_pda,
+            user_lst_ata,
+            nonce,
+            create_withdraw_instruction_data(amount, nonce),
+        )
+    }
+
+    /// Helper to execute withdraw with raw instruction data
+    fn execute_withdraw_with_data(
+        svm: &mut LiteSVM,
+        user: &Keypair,
+        pool_state_pda: &Pubkey,
+        pool_stake_pda: &Pubkey,
+        reserve_stake_pda: &Pubkey,
+        lst_mint_pda: &Pubkey,
+        user_lst_ata: &Pubkey,
+        nonce: u64,
+        withdraw_data: Vec<u8>,
+    ) -> Result<litesvm::types::TransactionMetadata, litesvm::types::FailedTransactionMetadata>
     {
         let (user_stake_pda, user_stake_bump) = Pubkey::find_program_address(
             &[
@@ -631,8 +802,6 @@ mod tests {
             &PROGRAM_ID,
         );
 
-        let withdraw_data = create_withdraw_instruction_data(amount, nonce);
-
         let withdraw_ix = Instruction {
             program_id: PROGRAM_ID,
             accounts: vec![
@@ -643,6 +812,7 @@ mod tests {
                 AccountMeta::new(user_stake_pda, false),
                 AccountMeta::new(*lst_mint_pda, false),
                 AccountMeta::new(*user_lst_ata, false),
+                AccountMeta::new(get_manager_fee_account(svm, pool_state_pda), false),
                 AccountMeta::new_readonly(CLOCK_SYSVAR.into(), false),
                 AccountMeta::new_readonly(RENT_SYSVAR.into(), false),
                 AccountMeta::new_readonly(STAKE_HISTORY_SYSVAR, false),
@@ -891,19 +1061,20 @@ mod tests {
         eprintln!("  Pool stake: {}", pool_stake_before_rewards);
         eprintln!("  Mint supply: {}", mint_supply);
 
-        // Simulate rewards by adding SOL to pool_stake
+        // Simulate rewards, which raise the pool stake's balance and delegation
         let reward_amount = 1_000_000_000u64; // 1 SOL rewards
-        let pool_account = svm.get_account(&pool_stake_pda).unwrap();
-        svm.set_account(
-            pool_stake_pda,
-            Account {
-                lamports: pool_account.lamports + reward_amount,
-                data: pool_account.data.clone(),
-                owner: pool_account.owner,
-                executable: false,
-                rent_epoch: 0,
-            }
-            .into(),
+        let mut pool_account = svm.get_account(&pool_stake_pda).unwrap();
+        pool_account.lamports += reward_amount;
+        let stake = u64::from_le_bytes(pool_account.data[156..164].try_into().unwrap());
+        pool_account.data[156..164].copy_from_slice(&(stake + reward_amount).to_le_bytes());
+        svm.set_account(pool_stake_pda, pool_account).unwrap();
+
+        advance_epoch_and_update_pool_balance(
+            &mut svm,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
         );
 
         let pool_stake_after_rewards = svm.get_account(&pool_stake_pda).unwrap().lamports;
@@ -1093,4 +1264,94 @@ mod tests {
 
         println!("\n=== Test Passed: User Without LST Rejected ===");
     }
+
+    #[test]
+    fn test_withdraw_fee_transferred_to_manager() {
+        let mut svm = setup_svm();
+
+        // 1% withdrawal fee
+        let withdrawal_fee_bps = 100u16;
+        let (user, pool_state_pda, lst_mint_pda, pool_stake_pda, reserve_stake_pda, user_lst_ata, _) =
+            setup_pool_for_withdraw_with_fee(&mut svm, withdrawal_fee_bps);
+
+        let manager_fee_account = get_manager_fee_account(&svm, &pool_state_pda);
+        let manager_before =
+            get_token_balance(&svm.get_account(&manager_fee_account).unwrap().data);
+        let supply_before = get_mint_supply(&svm.get_account(&lst_mint_pda).unwrap().data);
+
+        let withdraw_amount = 5_000_000_000u64;
+        let result = execute_withdraw(
+            &mut svm,
+            &user,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+            &user_lst_ata,
+            withdraw_amount,
+            1,
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Withdraw with fee should succeed");
+
+        let expected_fee = withdraw_amount * withdrawal_fee_bps as u64 / 10_000;
+
+        let manager_after =
+            get_token_balance(&svm.get_account(&manager_fee_account).unwrap().data);
+        assert_eq!(
+            manager_after - manager_before,
+            expected_fee,
+            "Manager should receive the withdrawal fee in LST"
+        );
+
+        // Only the post-fee amount is burned
+        let supply_after = get_mint_supply(&svm.get_account(&lst_mint_pda).unwrap().data);
+        assert_eq!(
+            supply_before - supply_after,
+            withdraw_amount - expected_fee,
+            "Only the post-fee amount should be burned"
+        );
+
+        println!("\n=== Test Passed: Withdrawal Fee Transferred To Manager ===");
+    }
+
+    #[test]
+    fn test_withdraw_minimum_lamports_out() {
+        let mut svm = setup_svm();
+
+        let (user, pool_state_pda, lst_mint_pda, pool_stake_pda, reserve_stake_pda, user_lst_ata, _) =
+            setup_pool_for_withdraw(&mut svm);
+
+        let withdraw_amount = 2_000_000_000u64;
+        let result = execute_withdraw_with_data(
+            &mut svm,
+            &user,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+            &user_lst_ata,
+            1,
+            create_withdraw_instruction_data_with_min(withdraw_amount, 1, u64::MAX),
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_err(), "Withdraw below minimum_lamports_out should fail");
+        assert_program_error(&result, PinocchioError::SlippageExceeded);
+
+        let result = execute_withdraw_with_data(
+            &mut svm,
+            &user,
+            &pool_state_pda,
+            &pool_stake_pda,
+            &reserve_stake_pda,
+            &lst_mint_pda,
+            &user_lst_ata,
+            1,
+            create_withdraw_instruction_data_with_min(withdraw_amount, 1, withdraw_amount / 2),
+        );
+        print_transaction_logs(&result);
+        assert!(result.is_ok(), "Withdraw meeting minimum_lamports_out should succeed");
+
+        println!("\n=== Test Passed: Withdraw Minimum Lamports Out Enforced ===");
+    }
 }